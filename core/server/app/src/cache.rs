@@ -1,8 +1,9 @@
 /**
-* Módulo de gestión de caché para códigos OTP (One-Time Password).
+* Módulo de gestión de caché para códigos OTP (One-Time Password) y datos de scraping.
 * 
 * Este módulo proporciona estructuras de datos y funcionalidades para el
-* almacenamiento temporal de códigos de autenticación de un solo uso.
+* almacenamiento temporal de códigos de autenticación de un solo uso y de
+* datos que cambian poco entre scrapes, como los miembros de un subreddit.
 * Utiliza DashMap para acceso concurrente thread-safe y manejo de expiración
 * basado en timestamps UTC para garantizar la seguridad temporal de los códigos.
 * 
@@ -18,4 +19,12 @@ use chrono::{DateTime, Utc};
 * Estructura de datos concurrente que asocia IDs de usuario con tuplas
 * que contienen el código OTP y su timestamp de expiración UTC.
 */
-pub type OtpCache = DashMap<i32, (String, DateTime<Utc>)>;
\ No newline at end of file
+pub type OtpCache = DashMap<i32, (String, DateTime<Utc>)>;
+
+/**
+* Tipo de caché para el número de miembros de los subreddits.
+* 
+* Asocia el nombre del subreddit con su número de miembros y el timestamp
+* UTC en que deja de ser válido, para no consultarlo en cada búsqueda.
+*/
+pub type MembersCache = DashMap<String, (u32, DateTime<Utc>)>;
@@ -72,6 +72,52 @@ lazy_static! {
             },
         }
         
+        // Optional comma separated proxy URLs for scraping
+        match env::var("PROXIES") {
+            Ok(value) => {
+                config.proxies = value
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|proxy| !proxy.is_empty())
+                    .map(String::from)
+                    .collect()
+            }
+            Err(_) => warn!("PROXIES is not set, scraping without proxies"),
+        }
+
+        // Maximum number of scraping tasks waiting for a worker
+        config.max_queue_scraper = env::var("SCRAPER_MAX_QUEUE")
+            .ok()
+            .and_then(|value| value.parse().ok())
+            .unwrap_or_else(|| {
+                warn!("SCRAPER_MAX_QUEUE is not set, using default value: {}", config.max_queue_scraper);
+                config.max_queue_scraper
+            });
+
+        // Maximum JavaScript heap of a scraped page in megabytes, 0 for no limit
+        config.max_heap_mb_scraper = env::var("SCRAPER_MAX_HEAP_MB")
+            .ok()
+            .and_then(|value| value.parse().ok())
+            .unwrap_or_else(|| {
+                warn!("SCRAPER_MAX_HEAP_MB is not set, using default value: {}", config.max_heap_mb_scraper);
+                config.max_heap_mb_scraper
+            });
+
+        // Maximum running time of a scraping task in seconds, 0 for no limit
+        config.max_task_secs_scraper = env::var("SCRAPER_MAX_TASK_SECS")
+            .ok()
+            .and_then(|value| value.parse().ok())
+            .unwrap_or_else(|| {
+                warn!("SCRAPER_MAX_TASK_SECS is not set, using default value: {}", config.max_task_secs_scraper);
+                config.max_task_secs_scraper
+            });
+
+        // Directory for the snapshots of failed scrapes
+        config.snapshots_dir = env::var("SNAPSHOTS_DIR").unwrap_or_else(|_| {
+            warn!("SNAPSHOTS_DIR is not set, using default value: {}", config.snapshots_dir);
+            config.snapshots_dir
+        });
+
         // Instagram credentials for scraping
         config.instagram_username = env::var("INSTAGRAM_USERNAME").unwrap_or_else(|_| {
             warn!("INSTAGRAM_USERNAME is not set, using default value: {}", config.instagram_username);
@@ -94,6 +140,22 @@ lazy_static! {
             config.twitter_password
         });
 
+        // Public APIs of the sources that need no browser
+        config.reddit_api = env::var("REDDIT_API").unwrap_or_else(|_| {
+            warn!("REDDIT_API is not set, using default value: {}", config.reddit_api);
+            config.reddit_api
+        });
+
+        config.bluesky_api = env::var("BLUESKY_API").unwrap_or_else(|_| {
+            warn!("BLUESKY_API is not set, using default value: {}", config.bluesky_api);
+            config.bluesky_api
+        });
+
+        config.mastodon_instance = env::var("MASTODON_INSTANCE").unwrap_or_else(|_| {
+            warn!("MASTODON_INSTANCE is not set, using default value: {}", config.mastodon_instance);
+            config.mastodon_instance
+        });
+
         config.email_from = env::var("EMAIL_FROM").unwrap_or_else(|_| {
             warn!("EMAIL_FROM is not set, using default value: {}", config.email_from);
             config.email_from
@@ -125,6 +187,16 @@ pub struct Config {
     pub browserless_ws: Option<String>,
     #[builder(default = "10")]
     pub workers_scraper: i64,
+    #[builder(default = "100")]
+    pub max_queue_scraper: usize,
+    #[builder(default = "1024")]
+    pub max_heap_mb_scraper: u64,
+    #[builder(default = "300")]
+    pub max_task_secs_scraper: u64,
+    #[builder(default = "Vec::new()")]
+    pub proxies: Vec<String>,
+    #[builder(default = "String::from(\"snapshots\")")]
+    pub snapshots_dir: String,
     #[builder(default = "String::from(\"\")")]
     pub instagram_username: String,
     #[builder(default = "String::from(\"\")")]
@@ -133,6 +205,12 @@ pub struct Config {
     pub twitter_username: String,
     #[builder(default = "String::from(\"\")")]
     pub twitter_password: String,
+    #[builder(default = "String::from(\"https://www.reddit.com\")")]
+    pub reddit_api: String,
+    #[builder(default = "String::from(\"https://public.api.bsky.app\")")]
+    pub bluesky_api: String,
+    #[builder(default = "String::from(\"https://mastodon.social\")")]
+    pub mastodon_instance: String,
     #[builder(default = "String::from(\"\")")] // Default email address
     pub email_from: String,
 }
@@ -190,6 +268,31 @@ impl Config {
         CONFIG.workers_scraper
     }
 
+    /// Returns maximum number of scraping tasks waiting for a worker
+    pub fn get_max_queue_scraper() -> usize {
+        CONFIG.max_queue_scraper
+    }
+
+    /// Returns maximum JavaScript heap of a scraped page in megabytes, 0 for no limit
+    pub fn get_max_heap_mb_scraper() -> u64 {
+        CONFIG.max_heap_mb_scraper
+    }
+
+    /// Returns maximum running time of a scraping task in seconds, 0 for no limit
+    pub fn get_max_task_secs_scraper() -> u64 {
+        CONFIG.max_task_secs_scraper
+    }
+
+    /// Returns proxy URLs used to spread scraping traffic
+    pub fn get_proxies() -> &'static [String] {
+        &CONFIG.proxies
+    }
+
+    /// Returns the directory where snapshots of failed scrapes are stored
+    pub fn get_snapshots_dir() -> &'static str {
+        &CONFIG.snapshots_dir
+    }
+
     /// Returns Instagram username for scraping operations
     pub fn get_instagram_username() -> &'static str {
         &CONFIG.instagram_username
@@ -210,6 +313,21 @@ impl Config {
         &CONFIG.twitter_password
     }
 
+    /// Returns the base URL of the Reddit JSON endpoints used to search posts
+    pub fn get_reddit_api() -> &'static str {
+        &CONFIG.reddit_api
+    }
+
+    /// Returns the base URL of the Bluesky AppView API used to search posts
+    pub fn get_bluesky_api() -> &'static str {
+        &CONFIG.bluesky_api
+    }
+
+    /// Returns the Mastodon instance whose hashtag timelines are read
+    pub fn get_mastodon_instance() -> &'static str {
+        &CONFIG.mastodon_instance
+    }
+
     pub fn get_email_from() -> &'static str {
         &CONFIG.email_from
     }
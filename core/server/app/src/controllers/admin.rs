@@ -13,6 +13,7 @@ use crate::{
   database::DbResponder,
   middlewares,
   models::{AdminCredentials, Admin},
+  scraping::SCRAPER,
 };
 use actix_web::{
   HttpMessage, HttpRequest, HttpResponse, Responder, Result, error, get, middleware::from_fn,
@@ -97,10 +98,43 @@ pub async fn check(req: HttpRequest) -> Result<impl Responder> {
   Ok(HttpResponse::Unauthorized().finish())
 }
 
+/**
+ * Endpoint para consultar el estado del scraper
+ * Reporta si el navegador está conectado, las métricas de su cola de tareas:
+ * tareas en espera y en ejecución, tareas rechazadas e histogramas de tiempos
+ * de espera y de ejecución, y los fallos del navegador de los que se recuperó:
+ * caídas, desconexiones, reinicios y pestañas cerradas por superar sus límites.
+ * Solo responde a administradores; el token de un usuario se rechaza
+ * 
+ * @param req Solicitud HTTP que contiene el token de autenticación en los headers
+ * @return Respuesta HTTP con el estado del scraper o error de autorización
+ */
+#[get("")]
+pub async fn scraper_metrics(req: HttpRequest) -> impl Responder {
+  let Some(id) = req.extensions().get::<i32>().copied() else {
+      error!("No id found in request");
+      return HttpResponse::Unauthorized().finish();
+  };
+
+  match Admin::get_by_id(id).await {
+      Ok(Some(_)) => HttpResponse::Ok().json(json!({
+          "connected": SCRAPER.is_connected(),
+          "scheduler": SCRAPER.metrics(),
+          "browser": SCRAPER.browser_metrics(),
+      })),
+      Ok(None) => HttpResponse::Forbidden().finish(),
+      Err(e) => {
+          error!("Failed to verify admin {}: {:?}", id, e);
+          HttpResponse::Forbidden().finish()
+      }
+  }
+}
+
 /**
  * Configuración de rutas para el módulo de administradores
  * Define todos los endpoints disponibles bajo el prefijo /admin
  * Incluye rutas públicas para registro/login y rutas protegidas para verificación
+ * y para el estado del scraper
  * 
  * @return Scope configurado con todas las rutas del módulo y middlewares aplicados
  */
@@ -113,4 +147,9 @@ pub fn routes() -> actix_web::Scope {
               .wrap(from_fn(middlewares::auth))
               .service(check),
       )
+      .service(
+          web::scope("/scraper")
+              .wrap(from_fn(middlewares::auth))
+              .service(scraper_metrics),
+      )
 }
\ No newline at end of file
@@ -15,9 +15,10 @@ use crate::{
     middlewares,
     models::{Resource, User},
     scraping::{
-        instagram::InstagramPost,
         notices::Params,
-        trends::{Data, Trends, TrendsScraper},
+        region::Region,
+        source::{SOURCES, SocialPost},
+        trends::{Trends, TrendsScraper},
     },
 };
 use actix_web::{
@@ -28,6 +29,7 @@ use regex::Regex;
 use rig::{completion::Prompt, providers};
 use serde::Deserialize;
 use tracing::{error, info};
+use zbrowser::TaskOptions;
 
 use crate::nosql::controllers::analytics::{
     AnalyticsRequest, HashtagData, TrendsData, process_all_hashtags,
@@ -40,126 +42,33 @@ pub struct FlowRequest {
     resource_id: i32,
 }
 
-// Guarda todos los datos scraped de las tres plataformas en DynamoDB de forma paralela
+// Guarda todos los datos scraped de cada plataforma en DynamoDB
 async fn save_all_scraped_data(scraped_data: &Trends) -> Vec<String> {
     let mut saved_hashtags = Vec::new();
 
-    for item in scraped_data.data.instagram.iter() {
-        let scraped_posts: Vec<ScrapedPost> = item.posts
-            .iter()
-            .map(|post| ScrapedPost {
-                comments: post.comments as i32,
-                followers: Some(post.followers as i32),
-                likes: post.likes as i32,
-                link: post.link.clone(),
-                time: post.time.clone(),
-                members: None,
-                subreddit: None,
-                title: None,
-                vote: None,
-            })
-            .collect();
-
-        match save_scraped_data_to_dynamo(
-            item.keyword.clone(),
-            "instagram".to_string(),
-            scraped_posts.clone(),
-        )
-        .await
-        {
-            Ok(saved) => {
-                if saved {
-                    saved_hashtags.push(format!("{}:instagram", item.keyword));
-                }
-            }
-            Err(_) => {}
-        }
-    }
-
-    for item in scraped_data.data.reddit.iter() {
-        let scraped_posts: Vec<ScrapedPost> = item.posts
-            .iter()
-            .map(|post| ScrapedPost {
-                comments: post.comments as i32,
-                followers: None,
-                likes: 0,
-                link: post.title.clone(),
-                time: post.title.clone(),
-                members: Some(post.members as i32),
-                subreddit: Some(post.subreddit.clone()),
-                title: Some(post.title.clone()),
-                vote: Some(post.vote as i32),
-            })
-            .collect();
-
-        match save_scraped_data_to_dynamo(
-            item.keyword.clone(),
-            "reddit".to_string(),
-            scraped_posts.clone(),
-        )
-        .await
-        {
-            Ok(saved) => {
-                if saved {
-                    saved_hashtags.push(format!("{}:reddit", item.keyword));
-                }
-            }
-            Err(_) => {}
-        }
-    }
+    for (platform, metrics) in scraped_data.data.iter() {
+        for item in metrics.iter() {
+            let scraped_posts: Vec<ScrapedPost> = item.posts.iter().map(ScrapedPost::from).collect();
 
-    for item in scraped_data.data.twitter.iter() {
-        let scraped_posts: Vec<ScrapedPost> = item.posts
-            .iter()
-            .map(|post| ScrapedPost {
-                comments: post.replies as i32,
-                followers: Some(post.followers as i32),
-                likes: post.likes as i32,
-                link: post.link.clone(),
-                time: post.time.clone(),
-                members: None,
-                subreddit: None,
-                title: Some(post.text.clone()),
-                vote: Some(post.retweets as i32),
-            })
-            .collect();
-
-        match save_scraped_data_to_dynamo(
-            item.keyword.clone(),
-            "twitter".to_string(),
-            scraped_posts.clone(),
-        )
-        .await
-        {
-            Ok(saved) => {
-                if saved {
-                    saved_hashtags.push(format!("{}:twitter", item.keyword));
+            match save_scraped_data_to_dynamo(item.keyword.clone(), platform.clone(), scraped_posts).await {
+                Ok(saved) => {
+                    if saved {
+                        saved_hashtags.push(format!("{}:{}", item.keyword, platform));
+                    }
                 }
+                Err(_) => {}
             }
-            Err(_) => {}
         }
     }
 
     saved_hashtags
 }
 
-// Extrae todos los hashtags únicos de los datos scraped de las tres plataformas
+// Extrae todos los hashtags únicos de los datos scraped de todas las plataformas
 fn extract_all_hashtags_from_scraped_data(scraped_data: &Trends) -> Vec<String> {
     let mut all_hashtags = Vec::new();
-    
-    for item in &scraped_data.data.instagram {
-        if !all_hashtags.contains(&item.keyword) {
-            all_hashtags.push(item.keyword.clone());
-        }
-    }
 
-    for item in &scraped_data.data.reddit {
-        if !all_hashtags.contains(&item.keyword) {
-            all_hashtags.push(item.keyword.clone());
-        }
-    }
-    
-    for item in &scraped_data.data.twitter {
+    for item in scraped_data.data.values().flatten() {
         if !all_hashtags.contains(&item.keyword) {
             all_hashtags.push(item.keyword.clone());
         }
@@ -176,41 +85,14 @@ async fn enhance_trends_with_fallback(mut trends: Trends, hashtags: &[String]) -
     let client = aws_sdk_dynamodb::Client::new(&config);
     let table_name = "trendhash_hashtag_cache";
 
-    for item in trends.data.instagram.iter_mut() {
-        let keyword_opt = item.keyword.clone();
-
-        if item.posts.is_empty() && hashtags.contains(&keyword_opt) {
-            if let Ok(fallback_data) =
-                get_fallback_data(&client, table_name, &keyword_opt, "instagram").await
-            {
-                item.posts = fallback_data;
-            }
-        }
-    }
-
-    for item in trends.data.twitter.iter_mut() {
-        let keyword_opt = item.keyword.clone();
-
-        if item.posts.is_empty() && hashtags.contains(&keyword_opt) {
-            if let Ok(fallback_data) =
-                get_fallback_data(&client, table_name, &keyword_opt, "twitter").await
-            {
-                let twitter_posts: Vec<crate::scraping::twitter::TweetData> = fallback_data
-                    .into_iter()
-                    .map(|ig_post| crate::scraping::twitter::TweetData {
-                        username: "fallback_user".to_string(),
-                        handle: "@fallback".to_string(),
-                        text: ig_post.link.clone(), 
-                        link: ig_post.link,
-                        time: ig_post.time,
-                        likes: ig_post.likes,
-                        retweets: 0,
-                        replies: ig_post.comments,
-                        followers: ig_post.followers,
-                    })
-                    .collect();
-                
-                item.posts = twitter_posts;
+    for (platform, metrics) in trends.data.iter_mut() {
+        for item in metrics.iter_mut() {
+            if item.posts.is_empty() && hashtags.contains(&item.keyword) {
+                if let Ok(fallback_data) =
+                    get_fallback_data(&client, table_name, &item.keyword, platform).await
+                {
+                    item.posts = fallback_data;
+                }
             }
         }
     }
@@ -224,7 +106,7 @@ async fn get_fallback_data(
     table_name: &str,
     hashtag: &str,
     platform: &str,
-) -> Result<Vec<InstagramPost>, Box<dyn std::error::Error>> {
+) -> Result<Vec<SocialPost>, Box<dyn std::error::Error>> {
     let pk = format!("HASHTAG#{}", hashtag);
     let sk = format!("DATA#{}", platform);
 
@@ -241,19 +123,7 @@ async fn get_fallback_data(
             if let Ok(scraped_data) =
                 serde_json::from_str::<crate::nosql::ScrapedHashtagData>(scraped_posts_json)
             {
-                let instagram_posts: Vec<InstagramPost> = scraped_data
-                    .posts
-                    .into_iter()
-                    .map(|scraped_post| InstagramPost {
-                        likes: scraped_post.likes as u32,
-                        comments: scraped_post.comments as u32,
-                        link: scraped_post.link,
-                        time: scraped_post.time,
-                        followers: scraped_post.followers.unwrap_or(0) as u32,
-                    })
-                    .collect();
-
-                return Ok(instagram_posts);
+                return Ok(scraped_data.posts.into_iter().map(SocialPost::from).collect());
             }
         }
 
@@ -277,19 +147,7 @@ async fn get_fallback_data(
                     if let Ok(scraped_data) =
                         serde_json::from_str::<crate::nosql::ScrapedHashtagData>(scraped_posts_json)
                     {
-                        let instagram_posts: Vec<InstagramPost> = scraped_data
-                            .posts
-                            .into_iter()
-                            .map(|scraped_post| InstagramPost {
-                                likes: scraped_post.likes as u32,
-                                comments: scraped_post.comments as u32,
-                                link: scraped_post.link,
-                                time: scraped_post.time,
-                                followers: scraped_post.followers.unwrap_or(0) as u32,
-                            })
-                            .collect();
-
-                        return Ok(instagram_posts);
+                        return Ok(scraped_data.posts.into_iter().map(SocialPost::from).collect());
                     }
                 }
             }
@@ -314,7 +172,10 @@ async fn process_trends_with_analytics(
         "formulas_used": [
             "insta_ratio()", "insta_viral_rate()",
             "reddit_hourly_ratio()", "reddit_viral_rate()",
-            "x_interaction_rate()", "x_viral_rate()"
+            "x_interaction_rate()", "x_viral_rate()",
+            "tiktok_interaction_rate()", "tiktok_viral_rate()",
+            "youtube_interaction_rate()", "youtube_viral_rate()",
+            "social_interaction_rate()", "social_viral_rate()"
         ]
     })
 }
@@ -324,71 +185,37 @@ fn convert_trends_to_analytics_request(
     trends: &serde_json::Value,
     hashtags: &[String],
 ) -> AnalyticsRequest {
-    let mut instagram_data = Vec::new();
-    let mut reddit_data = Vec::new();
-    let mut twitter_data = Vec::new();
-
-    if let Some(instagram_array) = trends
-        .get("data")
-        .and_then(|d| d.get("instagram"))
-        .and_then(|i| i.as_array())
-    {
-        for item in instagram_array {
-            if let (Some(keyword), Some(posts)) = (
-                item.get("keyword").and_then(|k| k.as_str()),
-                item.get("posts").and_then(|p| p.as_array()),
-            ) {
-                instagram_data.push(HashtagData {
-                    keyword: keyword.to_string(),
-                    posts: posts.clone(),
-                });
-            }
-        }
-    }
-
-    if let Some(reddit_array) = trends
-        .get("data")
-        .and_then(|d| d.get("reddit"))
-        .and_then(|r| r.as_array())
-    {
-        for item in reddit_array {
-            if let (Some(keyword), Some(posts)) = (
-                item.get("keyword").and_then(|k| k.as_str()),
-                item.get("posts").and_then(|p| p.as_array()),
-            ) {
-                reddit_data.push(HashtagData {
-                    keyword: keyword.to_string(),
-                    posts: posts.clone(),
-                });
-            }
-        }
-    }
+    let mut trends_data = TrendsData::new();
+
+    if let Some(platforms) = trends.get("data").and_then(|d| d.as_object()) {
+        for (platform, items) in platforms {
+            let Some(items) = items.as_array() else {
+                continue;
+            };
+
+            let platform_data: Vec<HashtagData> = items
+                .iter()
+                .filter_map(|item| {
+                    match (
+                        item.get("keyword").and_then(|k| k.as_str()),
+                        item.get("posts").and_then(|p| p.as_array()),
+                    ) {
+                        (Some(keyword), Some(posts)) => Some(HashtagData {
+                            keyword: keyword.to_string(),
+                            posts: posts.clone(),
+                        }),
+                        _ => None,
+                    }
+                })
+                .collect();
 
-    if let Some(twitter_array) = trends
-        .get("data")
-        .and_then(|d| d.get("twitter"))
-        .and_then(|t| t.as_array())
-    {
-        for item in twitter_array {
-            if let (Some(keyword), Some(posts)) = (
-                item.get("keyword").and_then(|k| k.as_str()),
-                item.get("posts").and_then(|p| p.as_array()),
-            ) {
-                twitter_data.push(HashtagData {
-                    keyword: keyword.to_string(),
-                    posts: posts.clone(),
-                });
-            }
+            trends_data.insert(platform.clone(), platform_data);
         }
     }
 
     AnalyticsRequest {
         hashtags: hashtags.to_vec(),
-        trends: TrendsData {
-            instagram: instagram_data,
-            reddit: reddit_data,
-            twitter: twitter_data,
-        },
+        trends: trends_data,
         sales: vec![],
     }
 }
@@ -485,7 +312,12 @@ async fn generate_prompt_from_flow(
         today,
         String::from("English"),
     );
-    let trends = TrendsScraper::get_trends_with_hashtags(params, Some(hashtags))
+    // Las búsquedas se hacen con el idioma, zona horaria y ubicación del usuario
+    let region = Region::from_locations(&user.locations);
+    // Las tareas de cada usuario se turnan en la cola del scraper con las de los demás
+    let trends = TaskOptions::new()
+        .with_tenant(format!("user:{}", user_id))
+        .scope(TrendsScraper::get_trends_with_hashtags(params, Some(hashtags), region))
         .await
         .to_web()?;
 
@@ -591,11 +423,7 @@ async fn test_generate_prompt_from_flow(payload: web::Json<FlowRequest>) -> Resu
 
     let trends = Trends {
         metadata: vec![],
-        data: Data {
-            instagram: vec![],
-            reddit: vec![],
-            twitter: vec![],
-        },
+        data: TrendsScraper::empty_data(),
     };
 
     let all_hashtags = extract_all_hashtags_from_scraped_data(&trends);
@@ -634,87 +462,22 @@ async fn debug_check_scraped_data(body: web::Json<serde_json::Value>) -> Result<
     let mut debug_info = serde_json::json!({
         "status": "DEBUGGING",
         "found_hashtags": [],
-        "instagram_analysis": {},
-        "reddit_analysis": {},
-        "twitter_analysis": {},
         "summary": {}
     });
-
-    let mut all_found_hashtags = Vec::new();
-
-    if let Some(instagram_array) = scraped_data
-        .get("data")
-        .and_then(|d| d.get("instagram"))
-        .and_then(|i| i.as_array())
-    {
-        let mut instagram_hashtags = Vec::new();
-        for (index, item) in instagram_array.iter().enumerate() {
-            if let Some(keyword) = item.get("keyword").and_then(|k| k.as_str()) {
-                let posts_count = item
-                    .get("posts")
-                    .and_then(|p| p.as_array())
-                    .map(|arr| arr.len())
-                    .unwrap_or(0);
-
-                instagram_hashtags.push(serde_json::json!({
-                    "index": index,
-                    "keyword": keyword,
-                    "posts_count": posts_count,
-                    "has_posts": posts_count > 0,
-                    "will_be_saved": posts_count > 0
-                }));
-
-                if !all_found_hashtags.contains(&keyword.to_string()) {
-                    all_found_hashtags.push(keyword.to_string());
-                }
-            }
-        }
-        debug_info["instagram_analysis"] = serde_json::json!({
-            "total_items": instagram_array.len(),
-            "hashtags": instagram_hashtags
-        });
+    for source in SOURCES {
+        debug_info[format!("{}_analysis", source.name())] = serde_json::json!({});
     }
 
-    if let Some(reddit_array) = scraped_data
-        .get("data")
-        .and_then(|d| d.get("reddit"))
-        .and_then(|r| r.as_array())
-    {
-        let mut reddit_hashtags = Vec::new();
-        for (index, item) in reddit_array.iter().enumerate() {
-            if let Some(keyword) = item.get("keyword").and_then(|k| k.as_str()) {
-                let posts_count = item
-                    .get("posts")
-                    .and_then(|p| p.as_array())
-                    .map(|arr| arr.len())
-                    .unwrap_or(0);
+    let mut all_found_hashtags = Vec::new();
 
-                reddit_hashtags.push(serde_json::json!({
-                    "index": index,
-                    "keyword": keyword,
-                    "posts_count": posts_count,
-                    "has_posts": posts_count > 0,
-                    "will_be_saved": posts_count > 0
-                }));
+    let platforms = scraped_data.get("data").and_then(|d| d.as_object());
+    for (platform, items) in platforms.into_iter().flatten() {
+        let Some(items) = items.as_array() else {
+            continue;
+        };
 
-                if !all_found_hashtags.contains(&keyword.to_string()) {
-                    all_found_hashtags.push(keyword.to_string());
-                }
-            }
-        }
-        debug_info["reddit_analysis"] = serde_json::json!({
-            "total_items": reddit_array.len(),
-            "hashtags": reddit_hashtags
-        });
-    }
-
-    if let Some(twitter_array) = scraped_data
-        .get("data")
-        .and_then(|d| d.get("twitter"))
-        .and_then(|t| t.as_array())
-    {
-        let mut twitter_hashtags = Vec::new();
-        for (index, item) in twitter_array.iter().enumerate() {
+        let mut platform_hashtags = Vec::new();
+        for (index, item) in items.iter().enumerate() {
             if let Some(keyword) = item.get("keyword").and_then(|k| k.as_str()) {
                 let posts_count = item
                     .get("posts")
@@ -722,7 +485,7 @@ async fn debug_check_scraped_data(body: web::Json<serde_json::Value>) -> Result<
                     .map(|arr| arr.len())
                     .unwrap_or(0);
 
-                twitter_hashtags.push(serde_json::json!({
+                platform_hashtags.push(serde_json::json!({
                     "index": index,
                     "keyword": keyword,
                     "posts_count": posts_count,
@@ -735,9 +498,9 @@ async fn debug_check_scraped_data(body: web::Json<serde_json::Value>) -> Result<
                 }
             }
         }
-        debug_info["twitter_analysis"] = serde_json::json!({
-            "total_items": twitter_array.len(),
-            "hashtags": twitter_hashtags
+        debug_info[format!("{}_analysis", platform)] = serde_json::json!({
+            "total_items": items.len(),
+            "hashtags": platform_hashtags
         });
     }
 
@@ -765,30 +528,13 @@ async fn debug_force_save_empty_hashtags(
     let all_hashtags = extract_all_hashtags_from_scraped_data(&trends_from_json);
 
     for hashtag in &all_hashtags {
-        match save_scraped_data_to_dynamo(
-            hashtag.clone(),
-            "instagram".to_string(),
-            vec![],
-        )
-        .await
-        {
-            Ok(_) => {
-                force_saved.push(format!("{}:instagram", hashtag));
-            }
-            Err(_) => {}
-        }
-
-        match save_scraped_data_to_dynamo(
-            hashtag.clone(),
-            "reddit".to_string(),
-            vec![], 
-        )
-        .await
-        {
-            Ok(_) => {
-                force_saved.push(format!("{}:reddit", hashtag));
+        for source in SOURCES {
+            match save_scraped_data_to_dynamo(hashtag.clone(), source.name().to_string(), vec![]).await {
+                Ok(_) => {
+                    force_saved.push(format!("{}:{}", hashtag, source.name()));
+                }
+                Err(_) => {}
             }
-            Err(_) => {}
         }
     }
 
@@ -12,6 +12,7 @@ use crate::scraping::{
    instagram::InstagramScraper,
    notices::{NoticesScraper, Params},
    reddit::RedditScraper,
+   region::Region,
    twitter::TwitterScraper,
    trends::TrendsScraper,
 };
@@ -27,6 +28,7 @@ use tracing::warn;
 * @param startdatetime Fecha de inicio en formato YYYY-MM-DD
 * @param enddatetime Fecha de fin en formato YYYY-MM-DD
 * @param language Idioma de búsqueda
+* @param locations Ubicaciones opcionales del usuario (ej. "Monterrey, CDMX")
 */
 #[derive(Serialize, Deserialize, Debug)]
 pub struct Query {
@@ -35,6 +37,8 @@ pub struct Query {
    startdatetime: String,
    enddatetime: String,
    language: String,
+   #[serde(default)]
+   locations: Option<String>,
 }
 
 /**
@@ -70,8 +74,10 @@ pub async fn get_twitter_posts_from_hashtag(path: web::Path<String>) -> impl Res
 */
 #[get("/reddit/get-simple-posts/{keyword}")]
 pub async fn get_simple_posts_reddit(path: web::Path<String>) -> impl Responder {
-   let posts = RedditScraper::get_simple_posts_by_keyword(path.into_inner()).await;
-   HttpResponse::Ok().json(posts)
+   match RedditScraper::get_simple_posts_by_keyword(path.into_inner()).await {
+       Ok(posts) => HttpResponse::Ok().json(posts),
+       Err(_) => HttpResponse::InternalServerError().finish()
+   }
 }
 
 /**
@@ -82,8 +88,10 @@ pub async fn get_simple_posts_reddit(path: web::Path<String>) -> impl Responder
 */
 #[get("/reddit/get-simple-posts-with-members/{keyword}")]
 pub async fn get_simple_posts_with_members_reddit(path: web::Path<String>) -> impl Responder {
-   let posts = RedditScraper::get_simple_posts_with_members(path.into_inner()).await;
-   HttpResponse::Ok().json(posts)
+   match RedditScraper::get_simple_posts_with_members(path.into_inner()).await {
+       Ok(posts) => HttpResponse::Ok().json(posts),
+       Err(_) => HttpResponse::InternalServerError().finish()
+   }
 }
 
 /**
@@ -188,7 +196,9 @@ pub async fn get_trends(query: web::Json<Query>) -> actix_web::Result<impl Respo
 
    let params = Params::new(query.query, start_date, end_date, query.language);
 
-   match TrendsScraper::get_trends_with_hashtags(params, query.hashtags).await {
+   let region = query.locations.as_deref().and_then(Region::from_locations);
+
+   match TrendsScraper::get_trends_with_hashtags(params, query.hashtags, region).await {
        Ok(trends) => Ok(HttpResponse::Ok().json(trends)),
        Err(e) => {
            warn!("Failed to get trends: {}", e);
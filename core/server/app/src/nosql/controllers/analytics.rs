@@ -9,7 +9,10 @@
  */
 
 use actix_web::{web, HttpResponse, Responder, post, Result};
+use crate::scraping::source::{SOURCES, SocialPost};
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use tracing::{info, warn};
 
 /**
@@ -131,6 +134,117 @@ pub fn insta_viral_rate(comments: u32, shares: u32, followers: u32) -> f64 {
     viral_ratio.min(100.0).max(0.0)
 }
 
+/**
+ * Función para calcular la tasa de interacción en TikTok
+ * Relaciona todas las acciones sobre el video con sus reproducciones
+ */
+pub fn tiktok_interaction_rate(likes: u32, comments: u32, shares: u32, views: u32) -> f64 {
+    if views == 0 {
+        return 0.0;
+    }
+
+    let engagement = (likes + comments + shares) as f64;
+    let views_f = views as f64;
+    let interaction_rate = (engagement / views_f) * 100.0;
+    interaction_rate.min(100.0).max(0.0)
+}
+
+/**
+ * Función para calcular la tasa de viralidad en TikTok
+ * La página Para ti lleva los videos más allá de los seguidores del autor, por lo que
+ * se comparan las reproducciones y las veces compartido con el tamaño de la cuenta
+ */
+pub fn tiktok_viral_rate(views: u32, shares: u32, followers: u32) -> f64 {
+    if followers == 0 {
+        return 0.0;
+    }
+
+    let spread = views as f64 + shares as f64 * 10.0;
+    let log_spread = (spread + 1.0).ln();
+    let log_followers = (followers as f64 + 1.0).ln();
+    let viral_ratio = if log_followers > 0.0 {
+        (log_spread / log_followers) * 25.0
+    } else {
+        0.0
+    };
+
+    viral_ratio.min(100.0).max(0.0)
+}
+
+/**
+ * Función para calcular la tasa de interacción en YouTube
+ * Los comentarios cuestan más que un like, por lo que cuentan doble sobre las reproducciones
+ */
+pub fn youtube_interaction_rate(likes: u32, comments: u32, views: u32) -> f64 {
+    if views == 0 {
+        return 0.0;
+    }
+
+    let engagement = likes as f64 + comments as f64 * 2.0;
+    let views_f = views as f64;
+    let interaction_rate = (engagement / views_f) * 100.0;
+    interaction_rate.min(100.0).max(0.0)
+}
+
+/**
+ * Función para calcular la tasa de viralidad en YouTube
+ * Un video se vuelve viral cuando lo ven muchas más personas que los suscriptores del canal,
+ * por lo que se comparan sus reproducciones con el tamaño del canal
+ */
+pub fn youtube_viral_rate(views: u32, subscribers: u32) -> f64 {
+    if subscribers == 0 {
+        return 0.0;
+    }
+
+    let log_views = (views as f64 + 1.0).ln();
+    let log_subscribers = (subscribers as f64 + 1.0).ln();
+    let viral_ratio = if log_subscribers > 0.0 {
+        (log_views / log_subscribers) * 30.0
+    } else {
+        0.0
+    };
+
+    viral_ratio.min(100.0).max(0.0)
+}
+
+/**
+ * Función para calcular la tasa de interacción en plataformas sin fórmulas propias
+ * Estas plataformas no muestran reproducciones, por lo que todas las acciones sobre
+ * la publicación se relacionan con los seguidores del autor
+ */
+pub fn social_interaction_rate(likes: u32, comments: u32, shares: u32, followers: u32) -> f64 {
+    if followers == 0 {
+        return 0.0;
+    }
+
+    let engagement = (likes + comments + shares) as f64;
+    let followers_f = followers as f64;
+    let interaction_rate = (engagement / followers_f) * 100.0;
+    interaction_rate.min(100.0).max(0.0)
+}
+
+/**
+ * Función para calcular la tasa de viralidad en plataformas sin fórmulas propias
+ * Solo cuenta las acciones que propagan la publicación (comentarios y veces compartido)
+ * y las compara en escala logarítmica con los seguidores del autor
+ */
+pub fn social_viral_rate(comments: u32, shares: u32, followers: u32) -> f64 {
+    if followers == 0 {
+        return 0.0;
+    }
+
+    let viral_actions = (comments + shares) as f64;
+    let log_viral = (viral_actions + 1.0).ln();
+    let log_followers = (followers as f64 + 1.0).ln();
+    let viral_ratio = if log_followers > 0.0 {
+        (log_viral / log_followers) * 30.0
+    } else {
+        0.0
+    };
+
+    viral_ratio.min(100.0).max(0.0)
+}
+
 /**
  * Función utilitaria para aplicar límites de porcentaje de forma consistente
  * Redondea a 2 decimales y aplica caps máximos y mínimos
@@ -171,25 +285,11 @@ fn sanitize_percentage(value: f64, context: &str) -> f64 {
     sanitized
 }
 
-/**
- * Estructura para datos de posts de Instagram
- * Define los campos necesarios para cálculos de métricas de la plataforma
- */
-#[derive(Deserialize, Debug)]
-pub struct InstagramPost {
-    pub date: String,
-    pub likes: u32,
-    pub comments: u32,
-    pub views: u32,
-    pub followers: u32,
-    pub shares: u32,
-}
-
 /**
  * Estructura para datos de posts de Reddit
  * Incluye métricas específicas de la plataforma como upvotes y tiempo
  */
-#[derive(Deserialize, Debug)]
+#[derive(Debug)]
 pub struct RedditPost {
     pub date: String,
     pub upvotes: u32,
@@ -198,18 +298,24 @@ pub struct RedditPost {
     pub hours: f64,
 }
 
-/**
- * Estructura para datos de posts de Twitter
- * Contempla las métricas características de la plataforma
- */
-#[derive(Deserialize, Debug)]
-pub struct TwitterPost {
-    pub date: String,
-    pub likes: u32,
-    pub retweets: u32,
-    pub comments: u32,
-    pub views: u32,
-    pub followers: u32,
+impl From<SocialPost> for RedditPost {
+    /**
+     * Convierte un post scrapeado de Reddit, calculando las horas desde su publicación
+     * a partir de su fecha en UTC; sin fecha las horas quedan en 0
+     */
+    fn from(post: SocialPost) -> Self {
+        let hours = DateTime::parse_from_rfc3339(&post.time)
+            .map(|time| (Utc::now() - time.with_timezone(&Utc)).num_seconds().max(0) as f64 / 3600.0)
+            .unwrap_or_default();
+
+        RedditPost {
+            date: post.time,
+            upvotes: post.likes,
+            comments: post.comments,
+            subscribers: post.followers,
+            hours,
+        }
+    }
 }
 
 /**
@@ -224,14 +330,9 @@ pub struct HashtagData {
 
 /**
  * Estructura para organizar datos de tendencias por plataforma
- * Agrupa todos los hashtags de cada red social para análisis conjunto
+ * Agrupa todos los hashtags de cada red social, con el nombre de la plataforma como clave
  */
-#[derive(Deserialize, Debug)]
-pub struct TrendsData {
-    pub instagram: Vec<HashtagData>,
-    pub reddit: Vec<HashtagData>,
-    pub twitter: Vec<HashtagData>,
-}
+pub type TrendsData = BTreeMap<String, Vec<HashtagData>>;
 
 /**
  * Estructura de solicitud completa para análisis de analytics
@@ -251,12 +352,9 @@ pub struct AnalyticsRequest {
 #[derive(Serialize, Debug)]
 pub struct HashtagMetrics {
     pub name: String,
-    pub instagram_interaction: f64,
-    pub instagram_virality: f64,
-    pub reddit_interaction: f64,
-    pub reddit_virality: f64,
-    pub twitter_interaction: f64,
-    pub twitter_virality: f64,
+    /// Tasas con la clave `plataforma_interaction` o `plataforma_virality`
+    #[serde(flatten)]
+    pub rates: BTreeMap<String, f64>,
 }
 
 /**
@@ -275,7 +373,7 @@ pub struct AnalyticsResponse {
  * Función de procesamiento para hashtags de Instagram
  * Calcula promedios de interacción y viralidad con sanitización de datos
  */
-pub fn process_instagram_hashtag(posts: &[InstagramPost]) -> (f64, f64) {
+pub fn process_instagram_hashtag(posts: &[SocialPost]) -> (f64, f64) {
     if posts.is_empty() {
         return (0.0, 0.0);
     }
@@ -311,16 +409,17 @@ pub fn process_instagram_hashtag(posts: &[InstagramPost]) -> (f64, f64) {
  * Función de procesamiento para hashtags de Reddit
  * Calcula métricas específicas de la plataforma con normalización temporal
  */
-pub fn process_reddit_hashtag(posts: &[RedditPost]) -> (f64, f64) {
+pub fn process_reddit_hashtag(posts: &[SocialPost]) -> (f64, f64) {
     if posts.is_empty() {
         return (0.0, 0.0);
     }
+    let posts: Vec<RedditPost> = posts.iter().cloned().map(RedditPost::from).collect();
 
     let mut total_interaction = 0.0;
     let mut total_virality = 0.0;
     let mut count = 0;
 
-    for post in posts {
+    for post in &posts {
         let interaction = reddit_hourly_ratio(post.upvotes, post.comments, post.hours);
         let virality = reddit_viral_rate(post.upvotes, post.comments, post.subscribers);
         
@@ -349,7 +448,7 @@ pub fn process_reddit_hashtag(posts: &[RedditPost]) -> (f64, f64) {
  * Función de procesamiento para hashtags de Twitter
  * Implementa las métricas específicas de la plataforma X/Twitter
  */
-pub fn process_twitter_hashtag(posts: &[TwitterPost]) -> (f64, f64) {
+pub fn process_twitter_hashtag(posts: &[SocialPost]) -> (f64, f64) {
     if posts.is_empty() {
         return (0.0, 0.0);
     }
@@ -359,8 +458,8 @@ pub fn process_twitter_hashtag(posts: &[TwitterPost]) -> (f64, f64) {
     let mut count = 0;
 
     for post in posts {
-        let interaction = x_interaction_rate(post.retweets, post.likes, post.comments, post.views);
-        let virality = x_viral_rate(post.retweets, post.likes, post.comments, post.followers);
+        let interaction = x_interaction_rate(post.shares, post.likes, post.comments, post.views);
+        let virality = x_viral_rate(post.shares, post.likes, post.comments, post.followers);
         
         let sanitized_interaction = sanitize_percentage(interaction, "Twitter Interaction");
         let sanitized_virality = sanitize_percentage(virality, "Twitter Virality");
@@ -384,24 +483,111 @@ pub fn process_twitter_hashtag(posts: &[TwitterPost]) -> (f64, f64) {
 }
 
 /**
- * Funciones de parsing para convertir JSON a estructuras tipadas
- * Manejan la deserialización segura de datos de cada plataforma
+ * Función de procesamiento para hashtags de TikTok
+ * Usa las reproducciones de cada video, que TikTok muestra a diferencia de otras plataformas
  */
-fn parse_instagram_posts(posts: &[serde_json::Value]) -> Vec<InstagramPost> {
-    posts.iter().filter_map(|post| {
-        serde_json::from_value::<InstagramPost>(post.clone()).ok()
-    }).collect()
+pub fn process_tiktok_hashtag(posts: &[SocialPost]) -> (f64, f64) {
+    if posts.is_empty() {
+        return (0.0, 0.0);
+    }
+
+    let mut total_interaction = 0.0;
+    let mut total_virality = 0.0;
+
+    for post in posts {
+        let interaction = tiktok_interaction_rate(post.likes, post.comments, post.shares, post.views);
+        let virality = tiktok_viral_rate(post.views, post.shares, post.followers);
+
+        total_interaction += sanitize_percentage(interaction, "TikTok Interaction");
+        total_virality += sanitize_percentage(virality, "TikTok Virality");
+    }
+
+    let count = posts.len() as f64;
+    (
+        apply_percentage_cap(total_interaction / count),
+        apply_percentage_cap(total_virality / count)
+    )
 }
 
-fn parse_reddit_posts(posts: &[serde_json::Value]) -> Vec<RedditPost> {
-    posts.iter().filter_map(|post| {
-        serde_json::from_value::<RedditPost>(post.clone()).ok()
-    }).collect()
+/**
+ * Función de procesamiento para hashtags de YouTube
+ * Usa las reproducciones de cada video y los suscriptores de su canal como alcance
+ */
+pub fn process_youtube_hashtag(posts: &[SocialPost]) -> (f64, f64) {
+    if posts.is_empty() {
+        return (0.0, 0.0);
+    }
+
+    let mut total_interaction = 0.0;
+    let mut total_virality = 0.0;
+
+    for post in posts {
+        let interaction = youtube_interaction_rate(post.likes, post.comments, post.views);
+        let virality = youtube_viral_rate(post.views, post.followers);
+
+        total_interaction += sanitize_percentage(interaction, "YouTube Interaction");
+        total_virality += sanitize_percentage(virality, "YouTube Virality");
+    }
+
+    let count = posts.len() as f64;
+    (
+        apply_percentage_cap(total_interaction / count),
+        apply_percentage_cap(total_virality / count)
+    )
+}
+
+/**
+ * Función de procesamiento para hashtags de plataformas sin fórmulas propias
+ * Usa el modelo común de publicaciones: la interacción relaciona todo el engagement
+ * con el alcance del autor y la viralidad solo las acciones de propagación
+ */
+pub fn process_social_hashtag(posts: &[SocialPost]) -> (f64, f64) {
+    if posts.is_empty() {
+        return (0.0, 0.0);
+    }
+
+    let mut total_interaction = 0.0;
+    let mut total_virality = 0.0;
+
+    for post in posts {
+        let interaction = social_interaction_rate(post.likes, post.comments, post.shares, post.followers);
+        let virality = social_viral_rate(post.comments, post.shares, post.followers);
+
+        total_interaction += sanitize_percentage(interaction, "Social Interaction");
+        total_virality += sanitize_percentage(virality, "Social Virality");
+    }
+
+    let count = posts.len() as f64;
+    (
+        apply_percentage_cap(total_interaction / count),
+        apply_percentage_cap(total_virality / count)
+    )
+}
+
+/**
+ * Calcula la interacción y viralidad de los posts de una plataforma
+ * Los posts de todas las plataformas llegan con el modelo común de publicaciones;
+ * las plataformas sin fórmulas propias usan las del modelo común
+ */
+fn process_platform_hashtag(platform: &str, posts: &[serde_json::Value]) -> (f64, f64) {
+    let posts = parse_social_posts(posts);
+    match platform {
+        "instagram" => process_instagram_hashtag(&posts),
+        "reddit" => process_reddit_hashtag(&posts),
+        "twitter" => process_twitter_hashtag(&posts),
+        "tiktok" => process_tiktok_hashtag(&posts),
+        "youtube" => process_youtube_hashtag(&posts),
+        _ => process_social_hashtag(&posts),
+    }
 }
 
-fn parse_twitter_posts(posts: &[serde_json::Value]) -> Vec<TwitterPost> {
+/**
+ * Función de parsing para convertir JSON a publicaciones del modelo común
+ * Los posts que no tienen su forma se descartan
+ */
+fn parse_social_posts(posts: &[serde_json::Value]) -> Vec<SocialPost> {
     posts.iter().filter_map(|post| {
-        serde_json::from_value::<TwitterPost>(post.clone()).ok()
+        serde_json::from_value::<SocialPost>(post.clone()).ok()
     }).collect()
 }
 
@@ -410,38 +596,34 @@ fn parse_twitter_posts(posts: &[serde_json::Value]) -> Vec<TwitterPost> {
  * Coordina el análisis de múltiples hashtags across todas las plataformas
  */
 pub fn process_all_hashtags(request: &AnalyticsRequest) -> Vec<HashtagMetrics> {
+    // Todas las plataformas registradas tienen tasas, aunque la solicitud no traiga sus datos
+    let mut platforms: Vec<&str> = SOURCES.iter().map(|source| source.name()).collect();
+    for platform in request.trends.keys() {
+        if !platforms.contains(&platform.as_str()) {
+            platforms.push(platform);
+        }
+    }
+
     let mut results = Vec::new();
     for hashtag_name in &request.hashtags {
         info!("🧮 Procesando hashtag con caps: {}", hashtag_name);
-        let instagram_data = request.trends.instagram.iter()
-            .find(|h| h.keyword == *hashtag_name)
-            .map(|h| parse_instagram_posts(&h.posts))
-            .unwrap_or_default();
+        let mut rates = BTreeMap::new();
 
-        let reddit_data = request.trends.reddit.iter()
-            .find(|h| h.keyword == *hashtag_name)
-            .map(|h| parse_reddit_posts(&h.posts))
-            .unwrap_or_default();
+        for platform in &platforms {
+            let posts = request.trends.get(*platform)
+                .and_then(|data| data.iter().find(|h| h.keyword == *hashtag_name))
+                .map(|h| h.posts.as_slice())
+                .unwrap_or_default();
 
-        let twitter_data = request.trends.twitter.iter()
-            .find(|h| h.keyword == *hashtag_name)
-            .map(|h| parse_twitter_posts(&h.posts))
-            .unwrap_or_default();
+            let (interaction, virality) = process_platform_hashtag(platform, posts);
+            rates.insert(format!("{}_interaction", platform), interaction);
+            rates.insert(format!("{}_virality", platform), virality);
+        }
 
-        let (instagram_interaction, instagram_virality) = process_instagram_hashtag(&instagram_data);
-        let (reddit_interaction, reddit_virality) = process_reddit_hashtag(&reddit_data);
-        let (twitter_interaction, twitter_virality) = process_twitter_hashtag(&twitter_data);
-        let metrics = HashtagMetrics {
+        results.push(HashtagMetrics {
             name: hashtag_name.clone(),
-            instagram_interaction,
-            instagram_virality,
-            reddit_interaction,
-            reddit_virality,
-            twitter_interaction,
-            twitter_virality,
-        };
-
-        results.push(metrics);
+            rates,
+        });
     }
 
     results
@@ -475,25 +657,28 @@ async fn process_analytics(req: web::Json<AnalyticsRequest>) -> Result<impl Resp
 async fn test_analytics() -> Result<impl Responder> {
     let test_data = AnalyticsRequest {
         hashtags: vec!["RockMusic".to_string(), "ElectricGuitar".to_string()],
-        trends: TrendsData {
-            instagram: vec![
+        trends: TrendsData::from([
+            ("instagram".to_string(), vec![
                 HashtagData {
                     keyword: "RockMusic".to_string(),
                     posts: vec![
                         serde_json::json!({
-                            "date": "01/01/25 - 31/01/25",
+                            "text": "Noche de #RockMusic en vivo",
+                            "link": "https://www.instagram.com/p/DFa1rOcKm2s/",
+                            "time": "2025-01-15T03:12:00.000Z",
+                            "views": 28000,
                             "likes": 2100,
                             "comments": 145,
-                            "views": 28000,
+                            "shares": 120,
                             "followers": 78000,
-                            "shares": 120
+                            "community": null
                         })
                     ]
                 }
-            ],
-            reddit: vec![],
-            twitter: vec![]
-        },
+            ]),
+            ("reddit".to_string(), vec![]),
+            ("twitter".to_string(), vec![])
+        ]),
         sales: vec![]
     };
 
@@ -505,7 +690,10 @@ async fn test_analytics() -> Result<impl Responder> {
         "formulas_used": [
             "insta_ratio() [capped]", "insta_viral_rate() [log]",
             "reddit_hourly_ratio() [log]", "reddit_viral_rate() [log]",
-            "x_interaction_rate() [capped]", "x_viral_rate() [log]"
+            "x_interaction_rate() [capped]", "x_viral_rate() [log]",
+            "tiktok_interaction_rate() [capped]", "tiktok_viral_rate() [log]",
+            "youtube_interaction_rate() [capped]", "youtube_viral_rate() [log]",
+            "social_interaction_rate() [capped]", "social_viral_rate() [log]"
         ]
     })))
 }
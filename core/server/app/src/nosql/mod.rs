@@ -15,6 +15,7 @@ use aws_config::BehaviorVersion;
 use std::collections::HashMap;
 use std::env;
 use serde::{Deserialize, Serialize};
+use crate::scraping::source::SocialPost;
 pub mod controllers;
 
 /// Estructura que representa un post extraído de redes sociales
@@ -29,6 +30,47 @@ pub struct ScrapedPost {
     pub subreddit: Option<String>,
     pub title: Option<String>,
     pub vote: Option<i32>,
+    pub shares: Option<i32>,
+    pub views: Option<i32>,
+}
+
+// Convierte una publicación de cualquier plataforma al formato guardado en DynamoDB
+impl From<&SocialPost> for ScrapedPost {
+    fn from(post: &SocialPost) -> Self {
+        ScrapedPost {
+            comments: post.comments as i32,
+            followers: Some(post.followers as i32),
+            likes: post.likes as i32,
+            link: post.link.clone(),
+            time: post.time.clone(),
+            members: None,
+            subreddit: post.community.clone(),
+            title: Some(post.text.clone()),
+            vote: None,
+            shares: Some(post.shares as i32),
+            views: Some(post.views as i32),
+        }
+    }
+}
+
+// Recupera una publicación guardada, incluidas las de Reddit guardadas con votos y miembros
+impl From<ScrapedPost> for SocialPost {
+    fn from(post: ScrapedPost) -> Self {
+        SocialPost {
+            text: post.title.unwrap_or_default(),
+            link: post.link,
+            time: post.time,
+            likes: match post.likes {
+                0 => post.vote.unwrap_or(0) as u32,
+                likes => likes as u32,
+            },
+            comments: post.comments as u32,
+            shares: post.shares.unwrap_or(0) as u32,
+            views: post.views.unwrap_or(0) as u32,
+            followers: post.followers.or(post.members).unwrap_or(0) as u32,
+            community: post.subreddit,
+        }
+    }
 }
 
 // Contenedor para datos de hashtag scraped con metadatos
@@ -380,6 +422,8 @@ async fn test_save_scraped(body: web::Json<serde_json::Value>) -> Result<impl Re
             subreddit: None,
             title: None,
             vote: None,
+            shares: None,
+            views: None,
         },
         ScrapedPost {
             comments: 30,
@@ -391,6 +435,8 @@ async fn test_save_scraped(body: web::Json<serde_json::Value>) -> Result<impl Re
             subreddit: None,
             title: None,
             vote: None,
+            shares: None,
+            views: None,
         }
     ];
     
@@ -0,0 +1,89 @@
+/**
+ * Peticiones que los scrapers de navegador no necesitan cargar.
+ *
+ * Además de los tipos de recurso bloqueados en todo el scraper (imágenes, hojas
+ * de estilo, fuentes y multimedia), cada plataforma descarga analítica, anuncios
+ * y video que no aportan datos a las tendencias y hacen más lenta la carga. Las
+ * reglas se aplican en cada tarea antes de navegar y dejan pasar los scripts que
+ * las páginas necesitan para mostrar las publicaciones.
+ */
+
+use zbrowser::{BlockRule, Context};
+
+/// Dominios de analítica y publicidad bloqueados en todas las plataformas
+const TRACKER_DOMAINS: &[&str] = &[
+    "google-analytics.com",
+    "googletagmanager.com",
+    "googlesyndication.com",
+    "googleadservices.com",
+    "doubleclick.net",
+];
+
+/**
+ * Reglas de bloqueo propias de una plataforma.
+ */
+struct PlatformRules {
+    /// Plataforma de las tareas (ej. `instagram`)
+    platform: &'static str,
+    /// Dominios bloqueados junto con sus subdominios
+    domains: &'static [&'static str],
+    /// Expresiones regulares sobre la URL completa de la petición
+    patterns: &'static [&'static str],
+}
+
+/// Reglas de cada plataforma: CDNs de video y endpoints de telemetría y anuncios
+static PLATFORMS: &[PlatformRules] = &[
+    PlatformRules {
+        platform: "instagram",
+        domains: &["connect.facebook.net"],
+        patterns: &[r"\.mp4(\?|$)", r"/logging_client_events", r"/ajax/bz"],
+    },
+    PlatformRules {
+        platform: "twitter",
+        domains: &["video.twimg.com", "ads-twitter.com", "ads-api.x.com"],
+        patterns: &[r"/1\.1/jot/", r"/i/api/1\.1/promoted_content/"],
+    },
+    PlatformRules {
+        platform: "reddit",
+        domains: &["v.redd.it", "alb.reddit.com", "w3-reporting.reddit.com"],
+        patterns: &[r"/svc/shreddit/events"],
+    },
+];
+
+/**
+ * Listas de bloqueo de los scrapers de navegador.
+ */
+pub struct Blocklist;
+
+impl Blocklist {
+    /**
+     * Devuelve las reglas de bloqueo de una plataforma.
+     *
+     * Las plataformas sin reglas propias solo bloquean los dominios de analítica
+     * y publicidad comunes.
+     *
+     * @param platform Plataforma de la tarea (ej. `instagram`).
+     * @return Reglas a aplicar en el contexto de la tarea.
+     */
+    pub fn rules(platform: &str) -> Vec<BlockRule> {
+        let mut rules: Vec<BlockRule> = TRACKER_DOMAINS.iter().map(|domain| BlockRule::domain(domain)).collect();
+
+        if let Some(own) = PLATFORMS.iter().find(|rules| rules.platform == platform) {
+            rules.extend(own.domains.iter().map(|domain| BlockRule::domain(domain)));
+            rules.extend(own.patterns.iter().filter_map(|pattern| BlockRule::regex(pattern).ok()));
+        }
+
+        rules
+    }
+
+    /**
+     * Aplica las reglas de una plataforma a un contexto del navegador; debe
+     * llamarse antes de navegar. Las reglas duran hasta que termina la tarea.
+     *
+     * @param context Contexto del navegador.
+     * @param platform Plataforma de la tarea.
+     */
+    pub fn apply(context: &Context, platform: &str) -> zbrowser::Result<()> {
+        context.block_urls(&Self::rules(platform))
+    }
+}
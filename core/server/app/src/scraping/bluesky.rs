@@ -0,0 +1,280 @@
+/**
+ * Cliente de Bluesky para obtener las publicaciones de una palabra clave o hashtag.
+ *
+ * Usa la API pública del AppView de Bluesky (AT Protocol), que no requiere iniciar
+ * sesión ni un navegador: `app.bsky.feed.searchPosts` devuelve las publicaciones
+ * más recientes con sus likes, reposts y respuestas, y `app.bsky.actor.getProfiles`
+ * los seguidores de sus autores.
+ */
+
+use crate::{
+    config::Config,
+    scraping::{
+        PROXIES, Utils,
+        region::Region,
+        source::{SocialPost, SocialSource},
+    },
+};
+use chrono::{DateTime, SecondsFormat, Utc};
+use futures::future::{BoxFuture, join_all};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tracing::warn;
+use url::Url;
+
+/// Nombre de la plataforma, usado para la asignación de proxies.
+const PLATFORM: &str = "bluesky";
+
+/// Método de búsqueda de publicaciones.
+const SEARCH_POSTS: &str = "xrpc/app.bsky.feed.searchPosts";
+/// Método que devuelve los perfiles completos de varios autores.
+const GET_PROFILES: &str = "xrpc/app.bsky.actor.getProfiles";
+
+/// Máximo de publicaciones por página que acepta `searchPosts`.
+const PAGE_SIZE: usize = 100;
+/// Máximo de autores por petición que acepta `getProfiles`.
+const PROFILES_PER_REQUEST: usize = 25;
+/// Máximo de páginas de búsqueda que se leen.
+const MAX_PAGES: usize = 5;
+
+/// Número de publicaciones que se recolectan por búsqueda si no se indica otro.
+pub const DEFAULT_POSTS: usize = 30;
+
+/// Respuesta de `app.bsky.feed.searchPosts`.
+#[derive(Debug, Deserialize)]
+struct SearchPosts {
+    #[serde(default)]
+    posts: Vec<PostView>,
+    cursor: Option<String>,
+}
+
+/// Publicación tal como la devuelve Bluesky, solo con los campos usados.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PostView {
+    uri: String,
+    author: Author,
+    record: Record,
+    #[serde(default)]
+    like_count: u32,
+    #[serde(default)]
+    repost_count: u32,
+    #[serde(default)]
+    quote_count: u32,
+    #[serde(default)]
+    reply_count: u32,
+    #[serde(default)]
+    indexed_at: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct Author {
+    did: String,
+    handle: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Record {
+    #[serde(default)]
+    text: String,
+    #[serde(default)]
+    created_at: String,
+}
+
+/// Respuesta de `app.bsky.actor.getProfiles`.
+#[derive(Debug, Deserialize)]
+struct Profiles {
+    #[serde(default)]
+    profiles: Vec<Profile>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Profile {
+    did: String,
+    #[serde(default)]
+    followers_count: u32,
+}
+
+/// Publicación de Bluesky con sus métricas de engagement y los seguidores de su autor.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BlueskyPost {
+    pub author: String,
+    pub text: String,
+    pub link: String,
+    pub time: String,
+    pub likes: u32,
+    pub reposts: u32,
+    pub replies: u32,
+    pub followers: u32,
+}
+
+impl BlueskyPost {
+    /**
+     * Construye la publicación a partir de la respuesta de Bluesky.
+     *
+     * Las citas también propagan la publicación, por lo que cuentan como reposts.
+     *
+     * @param post Publicación devuelta por `searchPosts`.
+     * @param followers Seguidores del autor, 0 si no se pudieron obtener.
+     * @return Publicación con su enlace en bsky.app y su fecha en UTC.
+     */
+    fn from_view(post: PostView, followers: u32) -> Self {
+        // El enlace usa la última parte del URI `at://did/app.bsky.feed.post/rkey`
+        let rkey = post.uri.rsplit('/').next().unwrap_or_default();
+        let time = DateTime::parse_from_rfc3339(&post.record.created_at)
+            .or_else(|_| DateTime::parse_from_rfc3339(&post.indexed_at))
+            .map(|time| time.with_timezone(&Utc).to_rfc3339_opts(SecondsFormat::Millis, true))
+            .unwrap_or_default();
+
+        BlueskyPost {
+            link: format!("https://bsky.app/profile/{}/post/{}", post.author.handle, rkey),
+            author: post.author.handle,
+            text: Utils::clean_text(&post.record.text),
+            time,
+            likes: post.like_count,
+            reposts: post.repost_count.saturating_add(post.quote_count),
+            replies: post.reply_count,
+            followers,
+        }
+    }
+}
+
+/// Cliente de búsquedas de Bluesky.
+pub struct BlueskyScraper;
+
+impl BlueskyScraper {
+    /**
+     * Construye la URL de un método de la API.
+     *
+     * @param api URL base de la API (ej. `https://public.api.bsky.app`).
+     * @param method Método con su prefijo `xrpc/`.
+     * @param params Parámetros de la consulta.
+     * @return URL del método.
+     */
+    fn method_url(api: &str, method: &str, params: &[(&str, &str)]) -> anyhow::Result<Url> {
+        let base = Url::parse(&format!("{}/", api.trim_end_matches('/')))?;
+        let mut url = base.join(method)?;
+        url.query_pairs_mut().extend_pairs(params);
+        Ok(url)
+    }
+
+    /**
+     * Busca las publicaciones más recientes de una consulta, leyendo páginas hasta reunir `limit`.
+     *
+     * @param api URL base de la API.
+     * @param query Palabra clave o hashtag.
+     * @param limit Número máximo de publicaciones.
+     * @return Publicaciones encontradas, en el orden de la búsqueda.
+     */
+    async fn search_posts(api: &str, query: &str, limit: usize) -> anyhow::Result<Vec<PostView>> {
+        let mut posts = Vec::new();
+        let mut cursor: Option<String> = None;
+        let page_size = limit.min(PAGE_SIZE).to_string();
+
+        for _ in 0..MAX_PAGES {
+            let mut params = vec![("q", query), ("sort", "latest"), ("limit", page_size.as_str())];
+            if let Some(cursor) = &cursor {
+                params.push(("cursor", cursor));
+            }
+
+            let url = Self::method_url(api, SEARCH_POSTS, &params)?;
+            let page: SearchPosts = PROXIES.fetch(PLATFORM, url).await?.error_for_status()?.json().await?;
+            let empty = page.posts.is_empty();
+            posts.extend(page.posts);
+
+            cursor = page.cursor;
+            if posts.len() >= limit || empty || cursor.is_none() {
+                break;
+            }
+        }
+
+        posts.truncate(limit);
+        Ok(posts)
+    }
+
+    /**
+     * Obtiene los seguidores de varios autores, pidiendo sus perfiles en paralelo.
+     *
+     * Los grupos de perfiles que fallan se omiten, por lo que sus autores quedan sin seguidores.
+     *
+     * @param api URL base de la API.
+     * @param dids Identificadores de los autores.
+     * @return Seguidores de cada autor por su identificador.
+     */
+    async fn get_followers(api: &str, dids: Vec<String>) -> HashMap<String, u32> {
+        let futures = dids.chunks(PROFILES_PER_REQUEST).map(|chunk| async move {
+            let params: Vec<(&str, &str)> = chunk.iter().map(|did| ("actors", did.as_str())).collect();
+            let url = Self::method_url(api, GET_PROFILES, &params)?;
+            let profiles: Profiles = PROXIES.fetch(PLATFORM, url).await?.error_for_status()?.json().await?;
+            anyhow::Ok(profiles.profiles)
+        });
+
+        let mut followers = HashMap::new();
+        for result in join_all(futures).await {
+            match result {
+                Ok(profiles) => followers.extend(
+                    profiles.into_iter().map(|profile| (profile.did, profile.followers_count)),
+                ),
+                Err(e) => warn!("Failed to load Bluesky profiles: {}", e),
+            }
+        }
+        followers
+    }
+
+    /**
+     * Obtiene publicaciones de una búsqueda con la API dada.
+     *
+     * @param api URL base de la API, configurable para usar otra instancia o un servidor de pruebas.
+     * @param query Palabra clave o hashtag sin el símbolo `#`.
+     * @param limit Número máximo de publicaciones a recolectar.
+     * @return Publicaciones encontradas con los seguidores de su autor.
+     */
+    pub async fn get_posts_with(api: &str, query: String, limit: usize) -> anyhow::Result<Vec<BlueskyPost>> {
+        let posts = Self::search_posts(api, &query, limit).await?;
+
+        let mut dids: Vec<String> = posts.iter().map(|post| post.author.did.clone()).collect();
+        dids.sort();
+        dids.dedup();
+        let followers = Self::get_followers(api, dids).await;
+
+        Ok(posts
+            .into_iter()
+            .map(|post| {
+                let author_followers = followers.get(&post.author.did).copied().unwrap_or_default();
+                BlueskyPost::from_view(post, author_followers)
+            })
+            .collect())
+    }
+}
+
+impl From<BlueskyPost> for SocialPost {
+    fn from(post: BlueskyPost) -> Self {
+        SocialPost {
+            text: post.text,
+            link: post.link,
+            time: post.time,
+            views: 0,
+            likes: post.likes,
+            comments: post.replies,
+            shares: post.reposts,
+            followers: post.followers,
+            community: None,
+        }
+    }
+}
+
+impl SocialSource for BlueskyScraper {
+    fn name(&self) -> &'static str {
+        PLATFORM
+    }
+
+    /// La búsqueda de Bluesky no depende de la ubicación, por lo que se ignora la región
+    fn search(&self, query: String, _region: Option<&'static Region>) -> BoxFuture<'static, anyhow::Result<Vec<SocialPost>>> {
+        Box::pin(async move {
+            let posts = Self::get_posts_with(Config::get_bluesky_api(), query, DEFAULT_POSTS).await?;
+            Ok(posts.into_iter().map(SocialPost::from).collect())
+        })
+    }
+}
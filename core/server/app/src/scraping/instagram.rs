@@ -11,27 +11,36 @@
 
 use crate::{
     config::Config,
-    scraping::{SCRAPER, Utils},
+    scraping::{
+        PROXIES, SCRAPER, SNAPSHOTS, Utils,
+        blocklist::Blocklist,
+        region::Region,
+        session::Sessions,
+        source::{SocialPost, SocialSource},
+    },
 };
-use futures_util::future::join_all;
-use once_cell::sync::OnceCell;
+use futures_util::future::{BoxFuture, join_all};
 use serde::{Deserialize, Serialize};
-use tracing::info;
+use std::collections::HashSet;
+use zbrowser::{Context, ElementHandle, Error, Priority, TaskOptions};
 
 // Variables globales y constantes
 
+/// Nombre de la plataforma, usado para las sesiones y la asignación de proxies.
+const PLATFORM: &str = "instagram";
+
 /// User Agent usado para simular un navegador real y evitar bloqueos por parte de Instagram.
 static USER_AGENT: &str = "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36";
 
-/// Código JavaScript externo para forzar efectos de hover sobre publicaciones.
-static JS_HOVER: &str = include_str!("hover.js");
-
-/// Almacenamiento global de cookies de sesión para evitar múltiples logins.
-static COOKIES: OnceCell<String> = OnceCell::new();
+/// Número de publicaciones que se recolectan por hashtag si no se indica otro.
+pub const DEFAULT_POSTS: usize = 24;
 
 /// URL de login de Instagram.
 const INSTAGRAM_LOGIN_URL: &str = "https://www.instagram.com/accounts/login/";
 
+/// Ruta a la que Instagram redirige cuando la sesión no es válida.
+const INSTAGRAM_LOGIN_PATH: &str = "/accounts/login";
+
 /// URL base para explorar hashtags.
 const INSTAGRAM_POST_URL: &str = "https://www.instagram.com/explore/tags";
 
@@ -42,9 +51,28 @@ const LOGIN_BUTTON_SELECTOR: &str = "button[type='submit']";
 
 /// Selectores CSS para extraer información de las publicaciones y perfiles.
 const POST_SELECTOR: &str = "main > div > div:nth-of-type(2) > div > div > div";
+const METRICS_SELECTOR: &str = "span > span";
 const TIME_SELECTOR: &str = "a span time";
 const FOLLOWERS_SELECTOR: &str = "section a span span";
 
+/// Tiempos de espera en milisegundos para la carga de páginas y el login.
+const NETWORK_IDLE_MS: i64 = 1_000;
+const PAGE_TIMEOUT_MS: i64 = 15_000;
+const LOGIN_TIMEOUT_MS: i64 = 30_000;
+
+/// Tiempo máximo en milisegundos que se espera a que aparezcan las métricas de una publicación tras el hover.
+const HOVER_TIMEOUT_MS: i64 = 1_000;
+
+/// Indica si la publicación con el enlace dado ya muestra sus likes y comentarios.
+const HOVER_METRICS: &str = "(selector, metrics, link) => Array.from(document.querySelectorAll(selector))
+    .some(post => {
+        const a = post.querySelector('a');
+        return a && a.href === link && post.querySelectorAll(metrics).length >= 2;
+    })";
+
+/// Profundidad máxima de scroll al recolectar publicaciones.
+const MAX_SCROLLS: usize = 30;
+
 /// Estructura que contiene likes, comentarios y el enlace de un post
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct InstagramPostPrimary {
@@ -74,53 +102,211 @@ pub struct InstagramPost {
 pub struct InstagramScraper;
 
 impl InstagramScraper {
+    /**
+     * Nombre de la sesión persistente de la cuenta configurada.
+     *
+     * @return Nombre con el formato `instagram:cuenta`.
+     */
+    pub fn session_name() -> String {
+        format!("{}:{}", PLATFORM, Config::get_instagram_username())
+    }
+
     /**
      * Realiza el login en Instagram utilizando credenciales configuradas.
      *
-     * Se navega al formulario de login, se rellenan los campos y se guarda la cookie resultante.
-     * Este método no debe ser llamado directamente, a menos que se requiera regenerar cookies.
+     * Se navega al formulario de login, se rellenan los campos y se guarda la sesión resultante.
+     * Este método no debe ser llamado directamente, a menos que se requiera regenerar la sesión.
      *
      * @return Cookie de sesión en formato string.
      */
     pub async fn login() -> anyhow::Result<String> {
-        SCRAPER
-            .execute(move |context| {
-                context.navigate(INSTAGRAM_LOGIN_URL);
-                context.write_input(USERNAME_SELECTOR, Config::get_instagram_username());
-                context.write_input(PASSWORD_SELECTOR, Config::get_instagram_password());
-                context.click_element(LOGIN_BUTTON_SELECTOR);
-                std::thread::sleep(std::time::Duration::from_secs(15));
-                let cookies = context.string_cookies();
-                cookies
+        let session = Self::session_name();
+        PROXIES
+            .execute(&SCRAPER, PLATFORM, move |context| {
+                Blocklist::apply(&context, PLATFORM)?;
+                context.navigate(INSTAGRAM_LOGIN_URL)?;
+                context.write_input(USERNAME_SELECTOR, Config::get_instagram_username())?;
+                context.write_input(PASSWORD_SELECTOR, Config::get_instagram_password())?;
+                context.click_element(LOGIN_BUTTON_SELECTOR)?;
+                Utils::ignore_timeout(context.wait_for_navigation(LOGIN_TIMEOUT_MS))?;
+
+                // Si seguimos en el formulario, las credenciales fueron rechazadas
+                if Self::is_login_wall(&context)? {
+                    return Err(Error::Failed("Instagram rejected the login".to_string()));
+                }
+
+                context.save_session(&session)?;
+                context.string_cookies()
             })
             .await
+            .map_err(Into::into)
     }
 
     /**
-     * Aplica las cookies almacenadas o realiza login si no se encuentran cookies previas.
+     * Indica si Instagram redirigió la página actual al formulario de login.
      *
-     * Se verifica la existencia de un archivo `cookies.json`, en cuyo caso se cargan. 
-     * De lo contrario, se realiza login y se genera el archivo con las cookies.
+     * @param context Contexto del navegador después de navegar.
+     * @return `true` si se muestra la pantalla de login.
      */
-    pub async fn apply_login() -> anyhow::Result<()> {
-        if COOKIES.get().is_some() {
-            return Ok(());
-        }
+    fn is_login_wall(context: &Context) -> zbrowser::Result<bool> {
+        let path = context.evaluate("location.pathname")?;
+        Ok(path.starts_with(INSTAGRAM_LOGIN_PATH))
+    }
+
+    /**
+     * Navega a una URL de Instagram con la sesión aplicada.
+     *
+     * @param context Contexto del navegador con la sesión aplicada.
+     * @param url URL a visitar.
+     * @return `Error::SessionInvalid` si Instagram pide iniciar sesión de nuevo.
+     */
+    fn open(context: &Context, url: &str) -> zbrowser::Result<()> {
+        context.set_user_agent(USER_AGENT)?;
+        context.navigate(url)?;
+        Utils::ignore_timeout(context.wait_for_network_idle(NETWORK_IDLE_MS, PAGE_TIMEOUT_MS))?;
 
-        if std::path::Path::new("cookies.json").exists() {
-            info!("Loading cookies from file");
-            let cookies = std::fs::read_to_string("cookies.json")?;
-            let _ = COOKIES.set(cookies);
-        } else {
-            info!("No cookies found, logging in");
-            let cookies = InstagramScraper::login().await?;
-            std::fs::write("cookies.json", cookies.clone())?;
-            let _ = COOKIES.set(cookies);
+        if Self::is_login_wall(context)? {
+            return Err(Error::SessionInvalid(Self::session_name()));
         }
 
         Ok(())
     }
 
+    /**
+     * Recolecta likes, comentarios y enlace de las publicaciones de la página de un hashtag.
+     *
+     * Se desplaza por la página hasta reunir el número de publicaciones pedido, hasta que
+     * Instagram deje de cargar publicaciones nuevas o hasta la profundidad máxima de scroll.
+     * Instagram solo muestra likes y comentarios al pasar el mouse sobre una publicación,
+     * por lo que cada publicación nueva recibe un hover real antes de leer sus métricas.
+     *
+     * @param context Contexto del navegador en la página del hashtag.
+     * @param limit Número máximo de publicaciones a recolectar.
+     * @return Publicaciones encontradas, sin repetidos.
+     */
+    pub fn collect_posts(context: &Context, limit: usize) -> zbrowser::Result<Vec<InstagramPostPrimary>> {
+        let mut posts = Vec::new();
+        let mut seen = HashSet::new();
+
+        for _ in 0..MAX_SCROLLS {
+            let mut fresh = false;
+
+            for element in context.query_selector_all(POST_SELECTOR)? {
+                let Some(link) = Self::post_link(&element)? else {
+                    continue;
+                };
+                if !seen.insert(link.clone()) {
+                    continue;
+                }
+
+                fresh = true;
+                posts.push(Self::read_post(context, &element, link)?);
+                if posts.len() >= limit {
+                    return Ok(posts);
+                }
+            }
+
+            // Sin publicaciones nuevas tras el último scroll, Instagram ya no tiene más
+            if !fresh {
+                break;
+            }
+            context.scroll()?;
+            Utils::ignore_timeout(context.wait_for_network_idle(NETWORK_IDLE_MS, PAGE_TIMEOUT_MS))?;
+        }
+
+        Ok(posts)
+    }
+
+    /**
+     * Obtiene el enlace absoluto de una publicación de la cuadrícula.
+     *
+     * @param element Publicación de la cuadrícula del hashtag.
+     * @return Enlace de la publicación, o `None` si no tiene.
+     */
+    fn post_link(element: &ElementHandle) -> zbrowser::Result<Option<String>> {
+        let Some(anchor) = element.query_selector("a")? else {
+            return Ok(None);
+        };
+
+        Ok(anchor.attr("href")?.filter(|href| !href.is_empty()).map(|href| {
+            if href.starts_with('/') {
+                format!("https://www.instagram.com{}", href)
+            } else {
+                href
+            }
+        }))
+    }
+
+    /**
+     * Pasa el mouse sobre una publicación y lee los likes y comentarios que Instagram muestra.
+     *
+     * @param context Contexto del navegador en la página del hashtag.
+     * @param element Publicación de la cuadrícula del hashtag.
+     * @param link Enlace de la publicación.
+     * @return Publicación con sus métricas, en 0 si Instagram no las muestra a tiempo.
+     */
+    fn read_post(context: &Context, element: &ElementHandle, link: String) -> zbrowser::Result<InstagramPostPrimary> {
+        element.hover()?;
+        Utils::ignore_timeout(context.wait_for_function(
+            HOVER_METRICS,
+            (POST_SELECTOR, METRICS_SELECTOR, &link),
+            HOVER_TIMEOUT_MS,
+        ))?;
+
+        let metrics = element.query_selector_all(METRICS_SELECTOR)?;
+
+        let count = |index: usize| match metrics.get(index) {
+            Some(metric) => metric.text().map(|text| Utils::parse_human_number(&text)),
+            None => Ok(0),
+        };
+
+        Ok(InstagramPostPrimary {
+            likes: count(0)?,
+            comments: count(1)?,
+            link,
+        })
+    }
+
+    /**
+     * Extrae el timestamp (`datetime`) y link de la publicación abierta en el contexto.
+     *
+     * Si la publicación no muestra su fecha se usa la fecha actual.
+     *
+     * @param context Contexto del navegador en la página de la publicación.
+     * @return Estructura con el tiempo y enlace de la publicación.
+     */
+    pub fn extract_time_and_link(context: &Context) -> zbrowser::Result<InstagramPostSecondary> {
+        context.evaluate_json(
+            "(selector) => {
+                let t = document.querySelector(selector);
+                let time = t ? t.getAttribute('datetime') || '' : '';
+
+                if (time === '') {
+                    time = new Date().toISOString();
+                }
+
+                let a = document.querySelector('a');
+                let link = a ? a.href : '';
+
+                return { time, link };
+            }",
+            (TIME_SELECTOR,),
+        )
+    }
+
+    /**
+     * Extrae el número de seguidores del perfil abierto en el contexto.
+     *
+     * @param context Contexto del navegador en la página del perfil.
+     * @return Cantidad de seguidores tal como la muestra Instagram.
+     */
+    pub fn extract_followers(context: &Context) -> zbrowser::Result<String> {
+        context.evaluate_json(
+            "(selector) => document.querySelector(selector).textContent",
+            (FOLLOWERS_SELECTOR,),
+        )
+    }
+
     /**
      * Extrae el timestamp (`datetime`) y link de una publicación individual de Instagram.
      *
@@ -130,45 +316,11 @@ impl InstagramScraper {
      * @return Estructura con el tiempo y enlace de la publicación.
      */
     pub async fn get_time_and_link(link: String) -> anyhow::Result<InstagramPostSecondary> {
-        InstagramScraper::apply_login().await?;
-        if let Some(cookies) = COOKIES.get() {
-            let result = SCRAPER
-                .execute(move |context| {
-                    context.set_user_agent(USER_AGENT);
-                    context.set_string_cookies(cookies.clone());
-                    context.navigate(link.clone());
-                    std::thread::sleep(std::time::Duration::from_secs(5));
-                    context.evaluate(format!(
-                    "(() => {{
-                        let t = document.querySelector('{TIME_SELECTOR}');
-                        let time = '';
-
-                        if (t) {{
-                            time = (t || {{ attributes: {{ datetime: {{ value: '' }} }}}}).attributes.datetime.value;
-                        }}
-
-                        if (time === '') {{
-                            time = new Date().toISOString();
-                        }}
-
-                        let a = document.querySelector('a');
-                        let link = '';
-
-                        if (a) {{
-                            link = (a || {{ href: '' }}).href;
-                        }}
-
-                        return JSON.stringify({{ time, link }});
-                    }})()"
-                    ))
-                })
-                .await?;
-
-            let result: InstagramPostSecondary = serde_json::from_str(&result)?;
-            return Ok(result);
-        }
-
-        Err(anyhow::anyhow!("No cookies found"))
+        Sessions::execute(&Self::session_name(), InstagramScraper::login, move |context| {
+            Self::open(context, &link)?;
+            Self::extract_time_and_link(context)
+        })
+        .await
     }
 
     /**
@@ -181,147 +333,135 @@ impl InstagramScraper {
      * @return Cantidad de seguidores como string.
      */
     pub async fn get_followers(link: String) -> anyhow::Result<String> {
-        InstagramScraper::apply_login().await?;
-        if let Some(cookies) = COOKIES.get() {
-            return SCRAPER
-                .execute(move |context| {
-                    context.set_user_agent(USER_AGENT);
-                    context.set_string_cookies(cookies.clone());
-                    context.navigate(link.clone());
-                    std::thread::sleep(std::time::Duration::from_secs(5));
-                    let result = context.evaluate(format!(
-                        "(() => {{
-                        let followers = document.querySelector('{FOLLOWERS_SELECTOR}').textContent;
-                        return followers;
-                    }})()"
-                    ));
-                    result
-                })
-                .await;
-        }
+        Sessions::execute(&Self::session_name(), InstagramScraper::login, move |context| {
+            Self::open(context, &link)?;
+            Self::extract_followers(context)
+        })
+        .await
+    }
 
-        Err(anyhow::anyhow!("No cookies found"))
+    /**
+     * Realiza scraping de las primeras publicaciones asociadas a un hashtag.
+     *
+     * @param hashtag Hashtag sin el símbolo `#` (ej. sustainability).
+     * @return Vector con estructuras completas de cada post.
+     */
+    pub async fn get_posts(hashtag: String) -> anyhow::Result<Vec<InstagramPost>> {
+        Self::get_posts_with(hashtag, DEFAULT_POSTS, None).await
     }
 
     /**
      * Realiza scraping de publicaciones asociadas a un hashtag.
      *
      * Esta función tiene 3 fases:
-     * 1. Extraer likes, comentarios y enlaces de los posts.
+     * 1. Extraer likes, comentarios y enlaces de los posts, desplazándose por la página.
      * 2. Obtener la fecha y link real de cada post.
      * 3. Acceder al perfil del autor y obtener su número de seguidores.
      *
      * @param hashtag Hashtag sin el símbolo `#` (ej. sustainability).
+     * @param limit Número máximo de publicaciones a analizar.
+     * @param region Región del usuario con la que se busca, o `None` para la del navegador.
      * @return Vector con estructuras completas de cada post.
      */
-    pub async fn get_posts(hashtag: String) -> anyhow::Result<Vec<InstagramPost>> {
-        InstagramScraper::apply_login().await?;
-        if let Some(cookies) = COOKIES.get() {
-            // Fase 1: Obtener likes, comments y link de cada publicación
-            let posts = SCRAPER
-                .execute(move |context| {
-                    context.set_user_agent(USER_AGENT);
-                    context.set_string_cookies(cookies.clone());
-                    context.navigate(format!("{}/{}", INSTAGRAM_POST_URL, hashtag));
-                    std::thread::sleep(std::time::Duration::from_secs(5));
-
-                    context.evaluate(format!(
-                        "(() => {{
-                        {JS_HOVER};
-                        let posts = Array.from(document.querySelectorAll('{POST_SELECTOR}'));
-                        posts.forEach((p) => forceHoverPermanent(p));
-                        return '';
-                    }})()"
-                    ));
-                    std::thread::sleep(std::time::Duration::from_secs(5));
-
-                    let result = context.async_evaluate(format!(
-                        "(() => {{
-                        let posts = Array.from(document.querySelectorAll('{POST_SELECTOR}'));
-                        let results = [];
-
-                        for (let i = 0; i < posts.length; i++) {{
-                            try {{
-                                let p = posts[i];
-                                let metrics = p.querySelectorAll('span > span');
-                                let a = p.querySelector('a');
-
-                                let likes = (metrics[0] || {{ textContent: '' }}).textContent;
-                                let comments = (metrics[1] || {{ textContent: '' }}).textContent;
-                                let link = (a || {{ href: '' }}).href;
-
-                                if (!a || !link) {{
-                                    continue;
-                                }}
-
-                                results.push({{
-                                    likes: likes === '' ? 0 : parseInt(likes.trim()),
-                                    comments: comments === '' ? 0 : parseInt(comments.trim()),
-                                    link
-                                }});
-                            }} catch (error) {{
-                            }}
-                        }}
-
-                        return JSON.stringify(results);
-                    }})()"
-                    ));
-                    std::thread::sleep(std::time::Duration::from_secs(5));
-                    result
-                })
-                .await?;
-
-            let posts: Vec<InstagramPostPrimary> = serde_json::from_str(&posts)?;
-            let mut futures = Vec::new();
-
-            // Fase 2: Obtener fecha y link real de cada post en paralelo
-            for post in posts.clone() {
-                futures.push(async move {
-                    match InstagramScraper::get_time_and_link(post.link).await {
-                        Ok(result) => Some(result),
-                        Err(_) => Some(InstagramPostSecondary {
-                            time: String::new(),
-                            link: String::new(),
-                        }),
-                    }
-                });
-            }
+    pub async fn get_posts_with(
+        hashtag: String,
+        limit: usize,
+        region: Option<&'static Region>,
+    ) -> anyhow::Result<Vec<InstagramPost>> {
+        // Fase 1: Obtener likes, comments y link de cada publicación
+        // Si falla o no hay publicaciones se guarda una captura de la página
+        let posts = Sessions::execute(&Self::session_name(), InstagramScraper::login, move |context| {
+            SNAPSHOTS.watch(PLATFORM, context, || {
+                if let Some(region) = region {
+                    region.apply(context)?;
+                }
+                Self::open(context, &format!("{}/{}", INSTAGRAM_POST_URL, hashtag))?;
+
+                Utils::ignore_timeout(context.wait_for_selector_count(POST_SELECTOR, 1, PAGE_TIMEOUT_MS))?;
+                Self::collect_posts(context, limit)
+            })
+        })
+        .await?;
+
+        let mut futures = Vec::new();
+
+        // Fase 2: Obtener fecha y link real de cada post en paralelo
+        for post in posts.clone() {
+            futures.push(async move {
+                match InstagramScraper::get_time_and_link(post.link).await {
+                    Ok(result) => Some(result),
+                    Err(_) => Some(InstagramPostSecondary {
+                        time: String::new(),
+                        link: String::new(),
+                    }),
+                }
+            });
+        }
 
-            let results = join_all(futures).await;
-            let times_and_links: Vec<InstagramPostSecondary> =
-                results.into_iter().filter_map(|result| result).collect();
-            let mut futures = Vec::new();
-
-            // Fase 3: Obtener seguidores del autor de cada post en paralelo
-            for post in times_and_links.clone() {
-                futures.push(async move {
-                    match InstagramScraper::get_followers(post.link).await {
-                        Ok(result) => Some(result),
-                        Err(_) => Some(String::new()),
-                    }
-                });
-            }
+        let results = join_all(futures).await;
+        let times_and_links: Vec<InstagramPostSecondary> =
+            results.into_iter().filter_map(|result| result).collect();
+        let mut futures = Vec::new();
+
+        // Fase 3: Obtener seguidores del autor de cada post en paralelo
+        // Con prioridad baja, para no retrasar las búsquedas de otros usuarios
+        let lookup = TaskOptions::current().with_priority(Priority::Low);
+        for post in times_and_links.clone() {
+            futures.push(lookup.clone().scope(async move {
+                match InstagramScraper::get_followers(post.link).await {
+                    Ok(result) => Some(result),
+                    Err(_) => Some(String::new()),
+                }
+            }));
+        }
 
-            let results = join_all(futures).await;
-            let followers: Vec<String> = results.into_iter().filter_map(|result| result).collect();
-
-            // Ensamblar todos los datos en la estructura final
-            let posts: Vec<InstagramPost> = posts
-                .into_iter()
-                .zip(followers)
-                .zip(times_and_links)
-                .map(|((post, follower), time_and_link)| InstagramPost {
-                    likes: post.likes,
-                    comments: post.comments,
-                    link: post.link,
-                    time: time_and_link.time,
-                    followers: Utils::parse_human_number(&follower),
-                })
-                .collect();
-
-            return Ok(posts);
+        let results = join_all(futures).await;
+        let followers: Vec<String> = results.into_iter().filter_map(|result| result).collect();
+
+        // Ensamblar todos los datos en la estructura final
+        let posts: Vec<InstagramPost> = posts
+            .into_iter()
+            .zip(followers)
+            .zip(times_and_links)
+            .map(|((post, follower), time_and_link)| InstagramPost {
+                likes: post.likes,
+                comments: post.comments,
+                link: post.link,
+                time: time_and_link.time,
+                followers: Utils::parse_human_number(&follower),
+            })
+            .collect();
+
+        Ok(posts)
+    }
+}
+
+impl From<InstagramPost> for SocialPost {
+    /// Instagram no muestra el texto en la cuadrícula del hashtag ni cuántas veces se compartió
+    fn from(post: InstagramPost) -> Self {
+        SocialPost {
+            text: String::new(),
+            link: post.link,
+            time: post.time,
+            views: 0,
+            likes: post.likes,
+            comments: post.comments,
+            shares: 0,
+            followers: post.followers,
+            community: None,
         }
+    }
+}
+
+impl SocialSource for InstagramScraper {
+    fn name(&self) -> &'static str {
+        PLATFORM
+    }
 
-        Err(anyhow::anyhow!("No cookies found"))
+    fn search(&self, query: String, region: Option<&'static Region>) -> BoxFuture<'static, anyhow::Result<Vec<SocialPost>>> {
+        Box::pin(async move {
+            let posts = Self::get_posts_with(query, DEFAULT_POSTS, region).await?;
+            Ok(posts.into_iter().map(SocialPost::from).collect())
+        })
     }
 }
@@ -0,0 +1,220 @@
+/**
+ * Cliente de Mastodon para obtener las publicaciones de un hashtag.
+ *
+ * Usa la línea de tiempo pública del hashtag (`/api/v1/timelines/tag/:hashtag`)
+ * de la instancia configurada, que no requiere iniciar sesión ni un navegador y
+ * devuelve cada publicación con sus favoritos, impulsos, respuestas y los
+ * seguidores de su autor. La instancia incluye las publicaciones federadas que
+ * conoce de otras instancias.
+ */
+
+use crate::{
+    config::Config,
+    scraping::{
+        PROXIES, Utils,
+        region::Region,
+        source::{SocialPost, SocialSource},
+    },
+};
+use chrono::{DateTime, SecondsFormat, Utc};
+use futures::future::BoxFuture;
+use scraper::Html;
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+/// Nombre de la plataforma, usado para la asignación de proxies.
+const PLATFORM: &str = "mastodon";
+
+/// Ruta de las líneas de tiempo de hashtags.
+const TAG_TIMELINE: &str = "api/v1/timelines/tag";
+
+/// Máximo de publicaciones por página que acepta la línea de tiempo.
+const PAGE_SIZE: usize = 40;
+/// Máximo de páginas que se leen.
+const MAX_PAGES: usize = 5;
+
+/// Número de publicaciones que se recolectan por hashtag si no se indica otro.
+pub const DEFAULT_POSTS: usize = 30;
+
+/// Publicación tal como la devuelve Mastodon, solo con los campos usados.
+#[derive(Debug, Deserialize)]
+struct Status {
+    id: String,
+    created_at: String,
+    uri: String,
+    url: Option<String>,
+    #[serde(default)]
+    content: String,
+    #[serde(default)]
+    replies_count: u32,
+    #[serde(default)]
+    reblogs_count: u32,
+    #[serde(default)]
+    favourites_count: u32,
+    account: Account,
+}
+
+#[derive(Debug, Deserialize)]
+struct Account {
+    acct: String,
+    #[serde(default)]
+    followers_count: u32,
+}
+
+/// Publicación de Mastodon con sus métricas de engagement y los seguidores de su autor.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MastodonPost {
+    pub author: String,
+    pub text: String,
+    pub link: String,
+    pub time: String,
+    pub favourites: u32,
+    pub reblogs: u32,
+    pub replies: u32,
+    pub followers: u32,
+}
+
+impl From<Status> for MastodonPost {
+    fn from(status: Status) -> Self {
+        let time = DateTime::parse_from_rfc3339(&status.created_at)
+            .map(|time| time.with_timezone(&Utc).to_rfc3339_opts(SecondsFormat::Millis, true))
+            .unwrap_or_default();
+
+        MastodonPost {
+            author: status.account.acct,
+            text: MastodonScraper::plain_text(&status.content),
+            // Las publicaciones federadas enlazan a su instancia de origen
+            link: status.url.unwrap_or(status.uri),
+            time,
+            favourites: status.favourites_count,
+            reblogs: status.reblogs_count,
+            replies: status.replies_count,
+            followers: status.account.followers_count,
+        }
+    }
+}
+
+/// Cliente de hashtags de Mastodon.
+pub struct MastodonScraper;
+
+impl MastodonScraper {
+    /**
+     * Convierte una consulta en un hashtag de Mastodon.
+     *
+     * Los hashtags solo admiten letras, números y guiones bajos, por lo que
+     * `machine learning` se busca como `machinelearning`.
+     *
+     * @param query Palabra clave o hashtag, con o sin el símbolo `#`.
+     * @return Hashtag sin el símbolo `#`, vacío si la consulta no tiene caracteres válidos.
+     */
+    pub fn hashtag(query: &str) -> String {
+        query.chars().filter(|c| c.is_alphanumeric() || *c == '_').collect()
+    }
+
+    /**
+     * Extrae el texto de una publicación, que Mastodon entrega como HTML.
+     *
+     * @param content HTML de la publicación.
+     * @return Texto sin etiquetas, con los párrafos y saltos de línea como espacios.
+     */
+    pub fn plain_text(content: &str) -> String {
+        let content = content
+            .replace("</p>", "</p> ")
+            .replace("<br>", " ")
+            .replace("<br/>", " ")
+            .replace("<br />", " ");
+        let fragment = Html::parse_fragment(&content);
+        Utils::clean_text(&fragment.root_element().text().collect::<String>())
+    }
+
+    /**
+     * Construye la URL de una página de la línea de tiempo de un hashtag.
+     *
+     * @param instance URL de la instancia (ej. `https://mastodon.social`).
+     * @param hashtag Hashtag sin el símbolo `#`.
+     * @param max_id Identificador desde el que se piden publicaciones más antiguas.
+     * @return URL de la página.
+     */
+    fn timeline_url(instance: &str, hashtag: &str, max_id: Option<&str>) -> anyhow::Result<Url> {
+        let base = Url::parse(&format!("{}/", instance.trim_end_matches('/')))?;
+        let mut url = base.join(TAG_TIMELINE)?;
+        url.path_segments_mut()
+            .map_err(|_| anyhow::anyhow!("Invalid Mastodon instance {}", instance))?
+            .push(hashtag);
+        url.query_pairs_mut().append_pair("limit", &PAGE_SIZE.to_string());
+        if let Some(max_id) = max_id {
+            url.query_pairs_mut().append_pair("max_id", max_id);
+        }
+        Ok(url)
+    }
+
+    /**
+     * Obtiene publicaciones de un hashtag con la instancia dada.
+     *
+     * Se leen páginas de la línea de tiempo, de la más reciente a la más antigua,
+     * hasta reunir `limit` o hasta que la instancia no tenga más publicaciones.
+     *
+     * @param instance URL de la instancia, configurable para usar otra o un servidor de pruebas.
+     * @param query Palabra clave o hashtag.
+     * @param limit Número máximo de publicaciones a recolectar.
+     * @return Publicaciones encontradas, de la más reciente a la más antigua.
+     */
+    pub async fn get_posts_with(instance: &str, query: String, limit: usize) -> anyhow::Result<Vec<MastodonPost>> {
+        let hashtag = Self::hashtag(&query);
+        if hashtag.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut posts = Vec::new();
+        let mut max_id: Option<String> = None;
+
+        for _ in 0..MAX_PAGES {
+            let url = Self::timeline_url(instance, &hashtag, max_id.as_deref())?;
+            let page: Vec<Status> = PROXIES.fetch(PLATFORM, url).await?.error_for_status()?.json().await?;
+            let Some(last) = page.last() else {
+                break;
+            };
+
+            max_id = Some(last.id.clone());
+            let full = page.len() >= PAGE_SIZE;
+            posts.extend(page.into_iter().map(MastodonPost::from));
+            if posts.len() >= limit || !full {
+                break;
+            }
+        }
+
+        posts.truncate(limit);
+        Ok(posts)
+    }
+}
+
+impl From<MastodonPost> for SocialPost {
+    /// Los favoritos cuentan como likes y los impulsos como veces compartido
+    fn from(post: MastodonPost) -> Self {
+        SocialPost {
+            text: post.text,
+            link: post.link,
+            time: post.time,
+            views: 0,
+            likes: post.favourites,
+            comments: post.replies,
+            shares: post.reblogs,
+            followers: post.followers,
+            community: None,
+        }
+    }
+}
+
+impl SocialSource for MastodonScraper {
+    fn name(&self) -> &'static str {
+        PLATFORM
+    }
+
+    /// Las líneas de tiempo de Mastodon no dependen de la ubicación, por lo que se ignora la región
+    fn search(&self, query: String, _region: Option<&'static Region>) -> BoxFuture<'static, anyhow::Result<Vec<SocialPost>>> {
+        Box::pin(async move {
+            let posts = Self::get_posts_with(Config::get_mastodon_instance(), query, DEFAULT_POSTS).await?;
+            Ok(posts.into_iter().map(SocialPost::from).collect())
+        })
+    }
+}
@@ -10,15 +10,27 @@
 
 use crate::config::Config;
 use lazy_static::lazy_static;
+use proxy::ProxyPool;
 use regex::Regex;
-use std::sync::Arc;
-use zbrowser::{BlockResource, Scraper};
+use snapshots::FailureSnapshots;
+use std::{sync::Arc, time::Duration};
+use zbrowser::{BlockResource, ResourceLimits, SchedulerConfig, Scraper, SessionStore};
 
+pub mod blocklist;
+pub mod bluesky;
 pub mod instagram;
+pub mod mastodon;
 pub mod notices;
+pub mod proxy;
 pub mod reddit;
+pub mod region;
+pub mod session;
+pub mod snapshots;
+pub mod source;
+pub mod tiktok;
 pub mod trends;
 pub mod twitter;
+pub mod youtube;
 
 /**
  * Definición de expresiones regulares globales y configuración del scraper.
@@ -28,6 +40,9 @@ pub mod twitter;
  * Las regex incluyen patrones para normalización de espacios en blanco y
  * parsing de números en formato humano con sufijos.
  */
+/// Directorio donde se guardan las sesiones de login de los scrapers
+const SESSIONS_DIR: &str = "sessions";
+
 lazy_static! {
     // Regex to match multiple whitespace characters and replace with single space
     static ref WHITESPACE_REGEX: Regex = Regex::new(r"\s+").unwrap();
@@ -37,10 +52,12 @@ lazy_static! {
     static ref HUMAN_NUMBER_REGEX: Regex =
         Regex::new(r"^([\d,.]+)\s*([kKmM]il|mill[oó]n|mills?|[kKMGTP])?$").unwrap();
 
-    // Global scraper instance configured with browserless WebSocket and resource blocking
-    pub static ref SCRAPER: Arc<Scraper> = Arc::new(Scraper::new::<&str>(
-        None,
-        //Config::get_browserless_ws(),
+    // Global scraper instance configured with browserless WebSocket, resource blocking,
+    // a bounded task queue, memory and time limits per task and the persistent login
+    // sessions of Instagram and X
+    // Without BROWSERLESS_WS a local Chrome is launched instead
+    pub static ref SCRAPER: Arc<Scraper> = Arc::new(Scraper::new(
+        Config::get_browserless_ws(),
         Config::get_workers_scraper(),
         // Block unnecessary resources to improve scraping performance
         vec![
@@ -49,7 +66,40 @@ lazy_static! {
             BlockResource::Font,
             BlockResource::Media,
         ],
-    ));
+    )
+    .expect("Failed to create the scraper")
+    .with_scheduler(
+        SchedulerConfig::new(Config::get_workers_scraper().max(1) as usize)
+            .with_max_queue(Config::get_max_queue_scraper()),
+    )
+    .with_limits(resource_limits())
+    .with_sessions(SessionStore::new(SESSIONS_DIR)));
+
+    // Proxies shared by the browser and HTTP scrapers, assigned per platform
+    pub static ref PROXIES: ProxyPool = ProxyPool::from_urls(Config::get_proxies());
+
+    // Screenshots and HTML of the pages where a scrape failed or found nothing
+    pub static ref SNAPSHOTS: FailureSnapshots = FailureSnapshots::new(Config::get_snapshots_dir());
+}
+
+/**
+ * Límites de recursos de las tareas del scraper.
+ * 
+ * Las pestañas cuyas páginas superan la memoria configurada o cuyas tareas
+ * superan el tiempo máximo se cierran y se reemplazan por pestañas nuevas.
+ * Un valor de 0 en la configuración desactiva el límite correspondiente.
+ * 
+ * @return Límites de memoria y tiempo de las tareas
+ */
+fn resource_limits() -> ResourceLimits {
+    let mut limits = ResourceLimits::new();
+    if Config::get_max_heap_mb_scraper() > 0 {
+        limits = limits.with_max_heap_mb(Config::get_max_heap_mb_scraper());
+    }
+    if Config::get_max_task_secs_scraper() > 0 {
+        limits = limits.with_max_task_time(Duration::from_secs(Config::get_max_task_secs_scraper()));
+    }
+    limits
 }
 
 /**
@@ -73,6 +123,23 @@ impl Utils {
         text.trim().to_string()
     }
 
+    /**
+     * Trata el timeout de una espera como éxito para las esperas opcionales.
+     *
+     * Se usa cuando la página puede no llegar nunca al estado esperado (por ejemplo,
+     * una búsqueda sin resultados o un sitio con peticiones constantes) y aun así
+     * conviene continuar con el contenido disponible.
+     *
+     * @param result - Resultado de la espera
+     * @return zbrowser::Result<()> - Ok si la espera terminó o expiró, el error en otro caso
+     */
+    pub fn ignore_timeout<T>(result: zbrowser::Result<T>) -> zbrowser::Result<()> {
+        match result {
+            Ok(_) | Err(zbrowser::Error::Timeout) => Ok(()),
+            Err(e) => Err(e),
+        }
+    }
+
     /**
      * Convierte números en formato humano con sufijos a valores numéricos u32.
      * 
@@ -8,9 +8,9 @@
  * Autor: Carlos Alberto Zamudio Velázquez
  */
 
+use crate::scraping::PROXIES;
 use futures::future::join_all;
 use lazy_static::lazy_static;
-use reqwest::Client;
 use scraper::{Html, Selector};
 use serde::{Deserialize, Serialize};
 use std::time::Duration;
@@ -25,6 +25,9 @@ lazy_static! {
         Selector::parse("meta[name='description']").unwrap();
 }
 
+// Platform name used to assign proxies
+const PLATFORM: &str = "notices";
+
 // GDELT API configuration constants
 const BASE_URL: &str = "https://api.gdeltproject.org/api/v2/doc/doc";
 const MODE: &str = "artlist";
@@ -114,86 +117,61 @@ impl NoticesScraper {
             .append_pair("format", params.format)
             .append_pair("language", &params.language);
 
-        let client = Client::new();
-        let response = client.get(url).send().await?;
+        let response = PROXIES.fetch(PLATFORM, url).await?;
         let body = response.text().await?;
 
+        Ok(Self::parse_articles(&body))
+    }
+
+    // Parse the body of a GDELT API response into its articles
+    pub fn parse_articles(body: &str) -> Vec<Articles> {
         if body.trim().is_empty() {
-            return Ok(Vec::new());
+            return Vec::new();
         }
 
         // Try to parse response as ApiResponse first, then as direct Vec<Articles>
-        let articles = match serde_json::from_str::<ApiResponse>(&body) {
+        match serde_json::from_str::<ApiResponse>(body) {
             Ok(response) => response.articles,
-            Err(_) => match serde_json::from_str::<Vec<Articles>>(&body) {
+            Err(_) => match serde_json::from_str::<Vec<Articles>>(body) {
                 Ok(articles) => articles,
                 Err(_) => Vec::new(),
             },
-        };
-
-        Ok(articles)
+        }
     }
 
-    // Get detailed information by scraping individual article pages
-    pub async fn get_details(params: Params) -> anyhow::Result<Details> {
-        let articles = Self::get_articles(params).await?;
-        let client = Client::new();
-
-        // Create concurrent futures for scraping each article
-        let futures = articles.into_iter().map(|article| {
-            let client = client.clone();
-            let article_url = article.url.clone();
-
-            async move {
-                let request_future = client.get(&article_url).send();
-                
-                // Apply timeout to prevent hanging requests
-                match timeout(Duration::from_secs(MAX_TIMEOUT), request_future).await {
-                    Ok(Ok(response)) => match response.text().await {
-                        Ok(body) => {
-                            let document = Html::parse_document(&body);
-
-                            // Extract title from h1 tag
-                            if let Some(element) = document.select(&TITLE_SELECTOR).next() {
-                                let title = element.text().collect::<Vec<_>>().join(" ");
-
-                                // Extract keywords from meta tag
-                                if let Some(element) = document.select(&KEYWORD_SELECTOR).next() {
-                                    let keywords_str = element.attr("content").unwrap_or_default();
-
-                                    // Extract description from meta tag
-                                    if let Some(element) =
-                                        document.select(&DESCRIPTION_SELECTOR).next()
-                                    {
-                                        let description =
-                                            element.attr("content").unwrap_or_default().to_string();
-
-                                        return Some(Info {
-                                            title,
-                                            url: article_url,
-                                            description,
-                                            keywords: keywords_str
-                                                .split(',')
-                                                .map(|s| s.trim().to_string())
-                                                .collect(),
-                                        });
-                                    }
-                                }
-                            }
-                        }
-                        Err(_) => {}
-                    },
-                    Ok(Err(_)) => {},
-                    Err(_) => {} // Timeout occurred
-                }
-                None
-            }
-        });
+    // Extract title, description and keywords from the HTML of an article page
+    // Returns None when the page lacks any of them
+    pub fn parse_info(url: String, body: &str) -> Option<Info> {
+        let document = Html::parse_document(body);
+
+        // Extract title from h1 tag
+        let title = document.select(&TITLE_SELECTOR).next()?.text().collect::<Vec<_>>().join(" ");
+
+        // Extract keywords from meta tag
+        let keywords_str = document
+            .select(&KEYWORD_SELECTOR)
+            .next()?
+            .attr("content")
+            .unwrap_or_default();
+
+        // Extract description from meta tag
+        let description = document
+            .select(&DESCRIPTION_SELECTOR)
+            .next()?
+            .attr("content")
+            .unwrap_or_default()
+            .to_string();
+
+        Some(Info {
+            title,
+            url,
+            description,
+            keywords: keywords_str.split(',').map(|s| s.trim().to_string()).collect(),
+        })
+    }
 
-        // Wait for all scraping operations to complete
-        let results = join_all(futures).await;
-        let mut details: Vec<Info> = results.into_iter().filter_map(|result| result).collect();
-        
+    // Keep the articles with the most keywords and turn their keywords into hashtags
+    pub fn select_details(mut details: Details) -> Details {
         // Sort by number of keywords (descending) and limit results
         details.sort_by(|a, b| b.keywords.len().cmp(&a.keywords.len()));
         details = details.into_iter().take(MAX_ARTICLES).collect();
@@ -217,6 +195,54 @@ impl NoticesScraper {
             }).collect();
         }
 
-        Ok(details)
+        details
+    }
+
+    // Get detailed information by scraping individual article pages
+    pub async fn get_details(params: Params) -> anyhow::Result<Details> {
+        let articles = Self::get_articles(params).await?;
+        let (client, proxy) = PROXIES.client(PLATFORM)?;
+
+        // Create concurrent futures for scraping each article
+        let futures = articles.into_iter().map(|article| {
+            let client = client.clone();
+            let proxy = proxy.clone();
+            let article_url = article.url.clone();
+
+            async move {
+                let request_future = client.get(&article_url).send();
+
+                // Apply timeout to prevent hanging requests
+                let result = match timeout(Duration::from_secs(MAX_TIMEOUT), request_future).await {
+                    Ok(result) => result,
+                    Err(_) => {
+                        // Timeout occurred
+                        if let Some(proxy) = &proxy {
+                            PROXIES.mark_failed(PLATFORM, proxy);
+                        }
+                        return None;
+                    }
+                };
+
+                // Report the request so a blocked proxy rotates like in `execute`
+                if let Some(proxy) = &proxy {
+                    PROXIES.report_request(PLATFORM, proxy, &result);
+                }
+
+                match result {
+                    Ok(response) => match response.text().await {
+                        Ok(body) => Self::parse_info(article_url, &body),
+                        Err(_) => None,
+                    },
+                    Err(_) => None,
+                }
+            }
+        });
+
+        // Wait for all scraping operations to complete
+        let results = join_all(futures).await;
+        let details: Vec<Info> = results.into_iter().filter_map(|result| result).collect();
+
+        Ok(Self::select_details(details))
     }
 }
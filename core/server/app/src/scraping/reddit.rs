@@ -4,35 +4,120 @@
  * Este módulo proporciona funcionalidades para extraer posts de Reddit mediante
  * búsquedas por palabras clave. Incluye extracción de información básica de posts
  * como títulos, votos, comentarios, subreddit y opcionalmente el número de miembros
- * de cada subreddit. Utiliza técnicas de scraping concurrente y evita detección
- * mediante user agents aleatorios.
+ * de cada subreddit. Las búsquedas usan los endpoints JSON de Reddit (`search.json`
+ * y `about.json`), con los miembros de cada subreddit en caché; si Reddit los
+ * rechaza, se recurre al navegador con scraping concurrente y user agents aleatorios.
  * 
  * Autor: Carlos Alberto Zamudio Velázquez
  */
 
-use crate::scraping::{SCRAPER, Utils};
+use crate::{
+    cache::MembersCache,
+    config::Config,
+    scraping::{
+        PROXIES, SCRAPER, SNAPSHOTS, Utils,
+        blocklist::Blocklist,
+        region::Region,
+        source::{SocialPost, SocialSource},
+    },
+};
+use chrono::{DateTime, Duration, SecondsFormat, Utc};
 use fake::{Fake, faker::internet::en::UserAgent};
-use futures::future::join_all;
+use futures::future::{BoxFuture, join_all};
 use lazy_static::lazy_static;
 use scraper::{ElementRef, Html, Selector};
 use serde::{Deserialize, Serialize};
-use std::sync::Arc;
+use std::collections::HashMap;
 use tracing::warn;
+use url::Url;
+use zbrowser::{Context, Scraper};
+
+// Platform name used to assign proxies
+const PLATFORM: &str = "reddit";
 
 // CSS selectors for Reddit elements
 const SUBREDDIT_SELECTOR_STR: &str = "faceplate-hovercard a";
 const MEMBERS_SELECTOR_STR: &str = "#subscribers faceplate-number";
+const POST_CONSUME_SELECTOR_STR: &str = "[consume-events]";
+
+// Maximum time in milliseconds to wait for the content of a page
+const PAGE_TIMEOUT_MS: i64 = 10_000;
+// Maximum time in milliseconds to wait for more posts after scrolling
+const STALL_TIMEOUT_MS: i64 = 5_000;
+// Maximum number of scroll steps on a search page
+const MAX_SCROLLS: usize = 10;
+// Number of posts scraped per search when no limit is given
+pub const DEFAULT_POSTS: usize = 25;
+
+// Base URL of the public links of posts and subreddits
+const REDDIT_URL: &str = "https://www.reddit.com";
+// Search page used by the browser fallback
+const REDDIT_SEARCH_URL: &str = "https://www.reddit.com/search";
+// Maximum number of posts per page accepted by search.json
+const PAGE_SIZE: usize = 100;
+// Maximum number of search.json pages read per search
+const MAX_PAGES: usize = 3;
+// Hours a subreddit member count stays cached
+const MEMBERS_TTL_HOURS: i64 = 6;
 
 lazy_static! {
     // Selectors for extracting post data
     static ref TIME_SELECTOR: Selector = Selector::parse("time").unwrap();
     static ref NUMBER_SELECTOR: Selector = Selector::parse("faceplate-number").unwrap();
-    static ref POST_CONSUME_SELECTOR: Selector = Selector::parse("[consume-events]").unwrap();
+    static ref POST_CONSUME_SELECTOR: Selector = Selector::parse(POST_CONSUME_SELECTOR_STR).unwrap();
     static ref POST_TITLE_SELECTOR: Selector = Selector::parse("[data-testid='post-title-text']").unwrap();
     static ref SUBREDDIT_SELECTOR: Selector = Selector::parse(SUBREDDIT_SELECTOR_STR).unwrap();
 
-    // Selector for subreddit member count
-    static ref MEMBERS_SELECTOR: Selector = Selector::parse(MEMBERS_SELECTOR_STR).unwrap();
+    // Member counts of the subreddits already looked up, shared between searches
+    pub(crate) static ref MEMBERS: MembersCache = MembersCache::new();
+}
+
+/// Listing returned by the JSON endpoints of Reddit
+#[derive(Debug, Deserialize)]
+struct Listing {
+    data: ListingData,
+}
+
+#[derive(Debug, Deserialize)]
+struct ListingData {
+    #[serde(default)]
+    children: Vec<Thing>,
+    after: Option<String>,
+}
+
+/// Item of a listing, posts have the `t3` kind
+#[derive(Debug, Deserialize)]
+struct Thing {
+    kind: String,
+    data: serde_json::Value,
+}
+
+/// Post of a search.json listing with the fields used
+#[derive(Debug, Deserialize)]
+struct ListingPost {
+    #[serde(default)]
+    title: String,
+    permalink: String,
+    subreddit: String,
+    #[serde(default)]
+    score: i64,
+    #[serde(default)]
+    num_comments: u32,
+    #[serde(default)]
+    created_utc: f64,
+    subreddit_subscribers: Option<u32>,
+}
+
+/// Subreddit information returned by about.json
+#[derive(Debug, Deserialize)]
+struct About {
+    data: AboutData,
+}
+
+#[derive(Debug, Deserialize)]
+struct AboutData {
+    #[serde(default)]
+    subscribers: u32,
 }
 
 /// Represents a simple Reddit post with basic information
@@ -40,6 +125,7 @@ lazy_static! {
 pub struct SimplePost {
     time: String,
     pub title: String,
+    pub link: String,
     pub vote: u32,
     pub comments: u32,
     pub subreddit: String,
@@ -50,6 +136,7 @@ pub struct SimplePost {
 pub struct SimplePostWithMembers {
     time: String,
     pub title: String,
+    pub link: String,
     pub vote: u32,
     pub comments: u32,
     pub subreddit: String,
@@ -82,20 +169,16 @@ impl RedditScraper {
 
             if let Some(subreddit_element) = element.select(&SUBREDDIT_SELECTOR).next() {
                 let subreddit = subreddit_element.attr("href").unwrap_or_default();
+                // The title links to the post page
+                let link = title_element.attr("href").unwrap_or_default();
 
                 return Ok(SimplePost {
                     time: time.to_string(),
                     title: Utils::clean_text(&title),
+                    link: Self::absolute_url(link),
                     vote: Utils::parse_human_number(&vote),
                     comments: Utils::parse_human_number(&comments),
-                    subreddit: {
-                        // Convert relative URLs to absolute URLs
-                        if subreddit.to_string().starts_with("/r/") {
-                            format!("https://www.reddit.com{}", subreddit.to_string())
-                        } else {
-                            subreddit.to_string()
-                        }
-                    },
+                    subreddit: Self::absolute_url(subreddit),
                 });
             }
         }
@@ -103,21 +186,19 @@ impl RedditScraper {
         Err(anyhow::anyhow!("Not found elements"))
     }
 
-    /// Scrapes Reddit posts by keyword search
-    pub async fn get_simple_posts_by_keyword(keyword: String) -> Vec<SimplePost> {
-        let content = SCRAPER
-            .execute(move |context| {
-                // Set random user agent to avoid detection
-                let user_agent: String = UserAgent().fake();
-                context.set_user_agent(&user_agent);
-                std::thread::sleep(std::time::Duration::from_secs(3));
-                context.navigate(&format!("https://www.reddit.com/search?q={}", keyword));
-                context.get_html()
-            })
-            .await
-            .unwrap_or_default();
+    /// Converts relative URLs to absolute URLs
+    fn absolute_url(href: &str) -> String {
+        if href.starts_with("/r/") {
+            format!("https://www.reddit.com{}", href)
+        } else {
+            href.to_string()
+        }
+    }
 
-        let document = Html::parse_document(&content);
+    /// Extracts every post of a Reddit search results page
+    /// Posts missing any of their fields are skipped
+    pub fn parse_simple_posts(html: &str) -> Vec<SimplePost> {
+        let document = Html::parse_document(html);
         let mut posts = Vec::new();
 
         // Extract posts from search results
@@ -133,46 +214,144 @@ impl RedditScraper {
         posts
     }
 
+    /// Reads the member count of the subreddit page open in the context
+    /// The `faceplate-number` component keeps the exact count in its `number` attribute,
+    /// its text is the abbreviated count shown to users
+    pub fn extract_members(context: &Context) -> zbrowser::Result<Option<u32>> {
+        let Some(element) = context.query_selector(MEMBERS_SELECTOR_STR)? else {
+            return Ok(None);
+        };
+
+        let members = match element.attr("number")? {
+            Some(number) => number,
+            None => element.text()?,
+        };
+        Ok(Some(Utils::parse_human_number(&members)))
+    }
+
+    /// Scrapes Reddit posts by keyword search
+    /// Uses the JSON endpoints and the browser when they fail
+    /// Fails when the search page could not be loaded either
+    pub async fn get_simple_posts_by_keyword(keyword: String) -> anyhow::Result<Vec<SimplePost>> {
+        match Self::search_json(Config::get_reddit_api(), &keyword, DEFAULT_POSTS).await {
+            Ok(posts) => Ok(posts),
+            Err(e) => {
+                warn!("Reddit JSON search failed, using the browser: {}", e);
+                Self::get_simple_posts_by_keyword_with(&SCRAPER, keyword, DEFAULT_POSTS, None).await
+            }
+        }
+    }
+
+    /// Builds the URL of the Reddit search page for a keyword, with the keyword percent-encoded
+    pub fn search_url(keyword: &str) -> String {
+        Url::parse_with_params(REDDIT_SEARCH_URL, &[("q", keyword)])
+            .map(String::from)
+            .unwrap_or_default()
+    }
+
+    /// Scrapes up to `limit` Reddit posts by keyword search using the given scraper
+    /// This is the browser fallback of `search_json`
+    /// The search page is scrolled until enough posts are loaded or no more appear
+    /// With a region the search is made with its language, time zone and location
+    /// A snapshot of the search page is saved when it fails or shows no posts
+    pub async fn get_simple_posts_by_keyword_with(
+        scraper: &Scraper,
+        keyword: String,
+        limit: usize,
+        region: Option<&'static Region>,
+    ) -> anyhow::Result<Vec<SimplePost>> {
+        let mut posts = PROXIES
+            .execute(scraper, PLATFORM, move |context| {
+                SNAPSHOTS.watch(PLATFORM, &context, || {
+                    if let Some(region) = region {
+                        region.apply(&context)?;
+                    }
+                    // Set random user agent to avoid detection
+                    let user_agent: String = UserAgent().fake();
+                    context.set_user_agent(&user_agent)?;
+                    Blocklist::apply(&context, PLATFORM)?;
+                    context.navigate(Self::search_url(&keyword))?;
+
+                    // Searches without results never show a post
+                    Utils::ignore_timeout(context.wait_for_element(POST_CONSUME_SELECTOR_STR, PAGE_TIMEOUT_MS))?;
+
+                    // Reddit loads more results while scrolling
+                    let mut loaded = 0;
+                    for _ in 0..MAX_SCROLLS {
+                        match context.wait_for_selector_count(POST_CONSUME_SELECTOR_STR, loaded + 1, STALL_TIMEOUT_MS) {
+                            Ok(count) if count >= limit => break,
+                            Ok(count) => loaded = count,
+                            Err(zbrowser::Error::Timeout) => break,
+                            Err(e) => return Err(e),
+                        }
+                        context.scroll()?;
+                    }
+
+                    Ok(Self::parse_simple_posts(&context.get_html()?))
+                })
+            })
+            .await?;
+
+        posts.truncate(limit);
+        Ok(posts)
+    }
+
     /// Scrapes Reddit posts with additional member count for each subreddit
-    pub async fn get_simple_posts_with_members(keyword: String) -> Vec<SimplePostWithMembers> {
-        let simple_posts = Self::get_simple_posts_by_keyword(keyword).await;
+    /// Posts whose subreddit page could not be loaded are skipped
+    pub async fn get_simple_posts_with_members(
+        keyword: String,
+    ) -> anyhow::Result<Vec<SimplePostWithMembers>> {
+        Self::search_with(Config::get_reddit_api(), &SCRAPER, keyword, DEFAULT_POSTS, None).await
+    }
+
+    /// Scrapes Reddit posts with the member count of their subreddit using the given scraper
+    /// This is the browser fallback of `get_simple_posts_with_members_json`
+    pub async fn get_simple_posts_with_members_with(
+        scraper: &Scraper,
+        keyword: String,
+        limit: usize,
+        region: Option<&'static Region>,
+    ) -> anyhow::Result<Vec<SimplePostWithMembers>> {
+        let simple_posts = Self::get_simple_posts_by_keyword_with(scraper, keyword, limit, region).await?;
         let mut futures = Vec::new();
-        let scraper = Arc::clone(&SCRAPER);
 
         // Create async tasks to fetch member count for each subreddit
         for post in simple_posts {
             let subreddit = post.subreddit.clone();
-            let scraper_clone = Arc::clone(&scraper);
 
             let future = async move {
-                let content = scraper_clone
-                    .execute(move |context| {
+                let url = post.subreddit.clone();
+                let members = PROXIES
+                    .execute(scraper, PLATFORM, move |context| {
+                        if let Some(region) = region {
+                            region.apply(&context)?;
+                        }
                         let user_agent: String = UserAgent().fake();
-                        context.set_user_agent(&user_agent);
-                        std::thread::sleep(std::time::Duration::from_secs(3));
-                        context.navigate(&post.subreddit);
-                        context.get_html()
+                        context.set_user_agent(&user_agent)?;
+                        Blocklist::apply(&context, PLATFORM)?;
+                        context.navigate(&url)?;
+                        Utils::ignore_timeout(context.wait_for_element(MEMBERS_SELECTOR_STR, PAGE_TIMEOUT_MS))?;
+                        Self::extract_members(&context)
                     })
-                    .await
-                    .unwrap_or_default();
-
-                let document = Html::parse_document(&content);
-                let members_element = document.select(&MEMBERS_SELECTOR).next();
-
-                if let Some(members_element) = members_element {
-                    let members = members_element.text().collect::<Vec<_>>().join(" ");
-
-                    Some(SimplePostWithMembers {
-                        time: post.time,
-                        title: post.title,
-                        vote: post.vote,
-                        comments: post.comments,
-                        subreddit,
-                        members: Utils::parse_human_number(&members),
-                    })
-                } else {
-                    None
-                }
+                    .await;
+
+                let members = match members {
+                    Ok(members) => members,
+                    Err(e) => {
+                        warn!("Failed to load subreddit {}: {}", subreddit, e);
+                        return None;
+                    }
+                };
+
+                members.map(|members| SimplePostWithMembers {
+                    time: post.time,
+                    title: post.title,
+                    link: post.link,
+                    vote: post.vote,
+                    comments: post.comments,
+                    subreddit,
+                    members,
+                })
             };
 
             futures.push(future);
@@ -180,6 +359,188 @@ impl RedditScraper {
 
         // Execute all futures concurrently and filter successful results
         let results = join_all(futures).await;
-        results.into_iter().filter_map(|result| result).collect()
+        Ok(results.into_iter().filter_map(|result| result).collect())
+    }
+
+    /// Builds the URL of a JSON endpoint of Reddit under the given API base URL
+    fn json_url(api: &str, path: &str) -> anyhow::Result<Url> {
+        let base = Url::parse(&format!("{}/", api.trim_end_matches('/')))?;
+        Ok(base.join(path)?)
+    }
+
+    /// Converts a post of a search.json listing into a simple post
+    /// The time is the real creation time of the post in UTC instead of the relative one shown by the page
+    fn listing_post(post: &ListingPost) -> SimplePost {
+        let time = DateTime::from_timestamp(post.created_utc as i64, 0)
+            .map(|time| time.to_rfc3339_opts(SecondsFormat::Millis, true))
+            .unwrap_or_default();
+
+        SimplePost {
+            time,
+            title: Utils::clean_text(&post.title),
+            link: Self::absolute_url(&post.permalink),
+            vote: post.score.max(0) as u32,
+            comments: post.num_comments,
+            subreddit: format!("{}/r/{}/", REDDIT_URL, post.subreddit),
+        }
+    }
+
+    /// Scrapes up to `limit` Reddit posts by keyword through the search.json endpoint
+    /// Pages are read with the `after` cursor until enough posts are found or no more remain
+    /// The member counts included in the listing are cached for `get_members_json`
+    pub async fn search_json(api: &str, keyword: &str, limit: usize) -> anyhow::Result<Vec<SimplePost>> {
+        let mut posts = Vec::new();
+        let mut after: Option<String> = None;
+        let page_size = limit.min(PAGE_SIZE).to_string();
+
+        for _ in 0..MAX_PAGES {
+            let mut url = Self::json_url(api, "search.json")?;
+            url.query_pairs_mut()
+                .append_pair("q", keyword)
+                .append_pair("type", "link")
+                .append_pair("limit", &page_size)
+                .append_pair("raw_json", "1");
+            if let Some(after) = &after {
+                url.query_pairs_mut().append_pair("after", after);
+            }
+
+            let listing: Listing = PROXIES.fetch(PLATFORM, url).await?.error_for_status()?.json().await?;
+            let empty = listing.data.children.is_empty();
+
+            for thing in listing.data.children.into_iter().filter(|thing| thing.kind == "t3") {
+                match serde_json::from_value::<ListingPost>(thing.data) {
+                    Ok(post) => {
+                        if let Some(members) = post.subreddit_subscribers {
+                            Self::cache_members(&post.subreddit, members);
+                        }
+                        posts.push(Self::listing_post(&post));
+                    }
+                    Err(e) => warn!("Error: {}", e),
+                }
+            }
+
+            after = listing.data.after;
+            if posts.len() >= limit || empty || after.is_none() {
+                break;
+            }
+        }
+
+        posts.truncate(limit);
+        Ok(posts)
+    }
+
+    /// Stores the member count of a subreddit for `MEMBERS_TTL_HOURS`
+    /// Expired counts are evicted on every insert so the cache only keeps live subreddits
+    fn cache_members(subreddit: &str, members: u32) {
+        let now = Utc::now();
+        MEMBERS.retain(|_, (_, expires)| *expires > now);
+        MEMBERS.insert(subreddit.to_lowercase(), (members, now + Duration::hours(MEMBERS_TTL_HOURS)));
+    }
+
+    /// Reads the member count of a subreddit through its about.json endpoint
+    /// Counts are served from the cache while they have not expired
+    pub async fn get_members_json(api: &str, subreddit: &str) -> anyhow::Result<u32> {
+        let key = subreddit.to_lowercase();
+        MEMBERS.remove_if(&key, |_, (_, expires)| *expires <= Utc::now());
+        if let Some(entry) = MEMBERS.get(&key) {
+            return Ok(entry.0);
+        }
+
+        let url = Self::json_url(api, &format!("r/{}/about.json", subreddit))?;
+        let about: About = PROXIES.fetch(PLATFORM, url).await?.error_for_status()?.json().await?;
+        Self::cache_members(subreddit, about.data.subscribers);
+        Ok(about.data.subscribers)
+    }
+
+    /// Scrapes Reddit posts with the member count of their subreddit through the JSON endpoints
+    /// Each subreddit is looked up once; posts whose subreddit could not be loaded are skipped
+    pub async fn get_simple_posts_with_members_json(
+        api: &str,
+        keyword: &str,
+        limit: usize,
+    ) -> anyhow::Result<Vec<SimplePostWithMembers>> {
+        let simple_posts = Self::search_json(api, keyword, limit).await?;
+
+        let mut subreddits: Vec<String> = simple_posts.iter().map(|post| post.subreddit.clone()).collect();
+        subreddits.sort();
+        subreddits.dedup();
+
+        // Fetch the member count of every subreddit concurrently
+        let futures = subreddits.into_iter().map(|subreddit| async move {
+            let name = subreddit.trim_end_matches('/').rsplit('/').next().unwrap_or_default().to_string();
+            match Self::get_members_json(api, &name).await {
+                Ok(members) => Some((subreddit, members)),
+                Err(e) => {
+                    warn!("Failed to load subreddit {}: {}", subreddit, e);
+                    None
+                }
+            }
+        });
+        let members: HashMap<String, u32> = join_all(futures).await.into_iter().flatten().collect();
+
+        Ok(simple_posts
+            .into_iter()
+            .filter_map(|post| {
+                let members = *members.get(&post.subreddit)?;
+                Some(SimplePostWithMembers {
+                    time: post.time,
+                    title: post.title,
+                    link: post.link,
+                    vote: post.vote,
+                    comments: post.comments,
+                    subreddit: post.subreddit,
+                    members,
+                })
+            })
+            .collect())
+    }
+
+    /// Scrapes Reddit posts with members through the JSON endpoints, falling back to the browser
+    /// when Reddit rejects the requests (e.g. rate limits or blocked IPs)
+    /// The region only applies to the browser, since the JSON endpoints do not depend on it
+    pub async fn search_with(
+        api: &str,
+        scraper: &Scraper,
+        keyword: String,
+        limit: usize,
+        region: Option<&'static Region>,
+    ) -> anyhow::Result<Vec<SimplePostWithMembers>> {
+        match Self::get_simple_posts_with_members_json(api, &keyword, limit).await {
+            Ok(posts) => Ok(posts),
+            Err(e) => {
+                warn!("Reddit JSON search failed, using the browser: {}", e);
+                Self::get_simple_posts_with_members_with(scraper, keyword, limit, region).await
+            }
+        }
+    }
+}
+
+impl From<SimplePostWithMembers> for SocialPost {
+    /// The votes count as likes and the members of the subreddit as the reach of the post
+    fn from(post: SimplePostWithMembers) -> Self {
+        SocialPost {
+            text: post.title,
+            link: post.link,
+            time: post.time,
+            views: 0,
+            likes: post.vote,
+            comments: post.comments,
+            shares: 0,
+            followers: post.members,
+            community: Some(post.subreddit),
+        }
+    }
+}
+
+impl SocialSource for RedditScraper {
+    fn name(&self) -> &'static str {
+        PLATFORM
+    }
+
+    fn search(&self, query: String, region: Option<&'static Region>) -> BoxFuture<'static, anyhow::Result<Vec<SocialPost>>> {
+        Box::pin(async move {
+            let posts = Self::search_with(Config::get_reddit_api(), &SCRAPER, query, DEFAULT_POSTS, region).await?;
+            Ok(posts.into_iter().map(SocialPost::from).collect())
+        })
     }
 }
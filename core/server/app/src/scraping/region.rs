@@ -0,0 +1,138 @@
+/**
+ * Regiones usadas para que los scrapers vean las plataformas como un usuario local.
+ *
+ * Las plataformas ordenan y filtran los resultados según el idioma, la zona
+ * horaria y la ubicación del visitante. A partir de las ubicaciones que el
+ * usuario registró (`locations`, ej. "Austin, Dallas, Houston") se elige una
+ * región conocida y se emulan su idioma, zona horaria y coordenadas en el
+ * navegador antes de navegar.
+ */
+
+use zbrowser::{Context, Emulation};
+
+/**
+ * Región con la configuración regional que se emula en el navegador.
+ */
+#[derive(Debug, PartialEq)]
+pub struct Region {
+    /// Nombre de la región
+    pub name: &'static str,
+    /// Palabras que identifican la región en las ubicaciones del usuario, sin acentos y en minúsculas
+    pub keywords: &'static [&'static str],
+    /// Locale ICU (ej. `es-MX`)
+    pub locale: &'static str,
+    /// Zona horaria IANA (ej. `America/Mexico_City`)
+    pub timezone: &'static str,
+    /// Latitud en grados
+    pub latitude: f64,
+    /// Longitud en grados
+    pub longitude: f64,
+}
+
+/// Regiones conocidas; las ciudades van antes que su país para que tengan prioridad
+static REGIONS: &[Region] = &[
+    Region { name: "Ciudad de México", keywords: &["cdmx", "ciudad de mexico", "mexico city", "df"], locale: "es-MX", timezone: "America/Mexico_City", latitude: 19.4326, longitude: -99.1332 },
+    Region { name: "Guadalajara", keywords: &["guadalajara", "jalisco"], locale: "es-MX", timezone: "America/Mexico_City", latitude: 20.6597, longitude: -103.3496 },
+    Region { name: "Monterrey", keywords: &["monterrey", "nuevo leon"], locale: "es-MX", timezone: "America/Monterrey", latitude: 25.6866, longitude: -100.3161 },
+    Region { name: "Tijuana", keywords: &["tijuana", "baja california"], locale: "es-MX", timezone: "America/Tijuana", latitude: 32.5149, longitude: -117.0382 },
+    Region { name: "Querétaro", keywords: &["queretaro"], locale: "es-MX", timezone: "America/Mexico_City", latitude: 20.5888, longitude: -100.3899 },
+    Region { name: "México", keywords: &["mexico", "mx"], locale: "es-MX", timezone: "America/Mexico_City", latitude: 19.4326, longitude: -99.1332 },
+    Region { name: "Madrid", keywords: &["madrid"], locale: "es-ES", timezone: "Europe/Madrid", latitude: 40.4168, longitude: -3.7038 },
+    Region { name: "Barcelona", keywords: &["barcelona"], locale: "es-ES", timezone: "Europe/Madrid", latitude: 41.3874, longitude: 2.1686 },
+    Region { name: "España", keywords: &["espana", "spain"], locale: "es-ES", timezone: "Europe/Madrid", latitude: 40.4168, longitude: -3.7038 },
+    Region { name: "Colombia", keywords: &["bogota", "medellin", "colombia"], locale: "es-CO", timezone: "America/Bogota", latitude: 4.7110, longitude: -74.0721 },
+    Region { name: "Argentina", keywords: &["buenos aires", "argentina"], locale: "es-AR", timezone: "America/Argentina/Buenos_Aires", latitude: -34.6037, longitude: -58.3816 },
+    Region { name: "Chile", keywords: &["santiago", "chile"], locale: "es-CL", timezone: "America/Santiago", latitude: -33.4489, longitude: -70.6693 },
+    Region { name: "Perú", keywords: &["lima", "peru"], locale: "es-PE", timezone: "America/Lima", latitude: -12.0464, longitude: -77.0428 },
+    Region { name: "Texas", keywords: &["austin", "dallas", "houston", "san antonio", "texas"], locale: "en-US", timezone: "America/Chicago", latitude: 30.2672, longitude: -97.7431 },
+    Region { name: "Nueva York", keywords: &["new york", "nueva york", "nyc"], locale: "en-US", timezone: "America/New_York", latitude: 40.7128, longitude: -74.0060 },
+    Region { name: "Los Ángeles", keywords: &["los angeles", "california"], locale: "en-US", timezone: "America/Los_Angeles", latitude: 34.0522, longitude: -118.2437 },
+    Region { name: "Estados Unidos", keywords: &["usa", "united states", "estados unidos", "eeuu", "us"], locale: "en-US", timezone: "America/Chicago", latitude: 39.8283, longitude: -98.5795 },
+];
+
+impl Region {
+    /**
+     * Busca la región de las ubicaciones registradas por un usuario.
+     *
+     * Las ubicaciones se separan por comas o punto y coma y se revisan en el
+     * orden en que el usuario las escribió; gana la primera que coincida con
+     * alguna región conocida. No distingue mayúsculas ni acentos.
+     *
+     * @param locations Ubicaciones del usuario (ej. "Austin, Dallas, Houston").
+     * @return Región encontrada, o `None` si ninguna ubicación es conocida.
+     */
+    pub fn from_locations(locations: &str) -> Option<&'static Region> {
+        locations
+            .split([',', ';'])
+            .map(Self::normalize)
+            .filter(|location| !location.is_empty())
+            .find_map(|location| {
+                REGIONS.iter().find(|region| {
+                    region
+                        .keywords
+                        .iter()
+                        .any(|keyword| Self::contains_words(&location, keyword))
+                })
+            })
+    }
+
+    /**
+     * Valor del encabezado `Accept-Language` de la región (ej. `es-MX,es;q=0.9`).
+     */
+    pub fn accept_language(&self) -> String {
+        let language = self.locale.split('-').next().unwrap_or(self.locale);
+        format!("{},{};q=0.9", self.locale, language)
+    }
+
+    /**
+     * Emulación del navegador correspondiente a la región.
+     *
+     * @return Idioma, zona horaria y coordenadas de la región.
+     */
+    pub fn emulation(&self) -> Emulation {
+        Emulation::new()
+            .accept_language(self.accept_language())
+            .locale(self.locale)
+            .timezone(self.timezone)
+            .geolocation(self.latitude, self.longitude)
+    }
+
+    /**
+     * Aplica la región a un contexto del navegador; debe llamarse antes de navegar.
+     *
+     * @param context Contexto del navegador.
+     */
+    pub fn apply(&self, context: &Context) -> zbrowser::Result<()> {
+        context.emulate(&self.emulation())
+    }
+
+    /**
+     * Pasa una ubicación a minúsculas, sin acentos y con palabras separadas por un espacio.
+     */
+    fn normalize(location: &str) -> String {
+        let location: String = location
+            .to_lowercase()
+            .chars()
+            .map(|c| match c {
+                'á' | 'à' | 'ä' => 'a',
+                'é' | 'è' | 'ë' => 'e',
+                'í' | 'ì' | 'ï' => 'i',
+                'ó' | 'ò' | 'ö' => 'o',
+                'ú' | 'ù' | 'ü' => 'u',
+                'ñ' => 'n',
+                c if c.is_alphanumeric() => c,
+                _ => ' ',
+            })
+            .collect();
+
+        location.split_whitespace().collect::<Vec<_>>().join(" ")
+    }
+
+    /**
+     * Indica si `keyword` aparece en `location` como palabras completas,
+     * para que "us" no coincida con "austin".
+     */
+    fn contains_words(location: &str, keyword: &str) -> bool {
+        format!(" {} ", location).contains(&format!(" {} ", keyword))
+    }
+}
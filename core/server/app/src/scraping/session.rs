@@ -0,0 +1,162 @@
+/**
+ * Manejo de sesiones persistentes para los scrapers que requieren login.
+ *
+ * Las sesiones se guardan en el `SessionStore` del scraper global con nombres
+ * del tipo `plataforma:cuenta`, por lo que sobreviven a reinicios y permiten
+ * varias cuentas por plataforma. Cuando no existe una sesión válida, o el sitio
+ * la rechaza mostrando su pantalla de login, se inicia sesión de nuevo
+ * automáticamente y se reintenta la tarea una vez.
+ */
+
+use crate::scraping::{PROXIES, SCRAPER, blocklist::Blocklist};
+use dashmap::DashMap;
+use lazy_static::lazy_static;
+use std::{future::Future, sync::Arc};
+use tokio::sync::Mutex;
+use tracing::{info, warn};
+use zbrowser::{Context, Error, Session, SessionStore};
+
+lazy_static! {
+    // Evita que varias tareas concurrentes inicien sesión con la misma cuenta al mismo
+    // tiempo; las sesiones de otras cuentas o plataformas no se esperan entre sí
+    static ref LOGIN_LOCKS: DashMap<String, Arc<Mutex<()>>> = DashMap::new();
+}
+
+/**
+ * Utilidades para ejecutar tareas de scraping con una sesión persistente.
+ */
+pub struct Sessions;
+
+impl Sessions {
+    /**
+     * Devuelve el candado de login de una sesión, creándolo si no existe.
+     *
+     * @param name Nombre de la sesión.
+     * @return Candado compartido por las tareas de esa sesión.
+     */
+    fn login_lock(name: &str) -> Arc<Mutex<()>> {
+        Arc::clone(LOGIN_LOCKS.entry(name.to_string()).or_default().value())
+    }
+
+    /**
+     * Devuelve el almacén de sesiones del scraper global.
+     */
+    fn store() -> anyhow::Result<&'static SessionStore> {
+        SCRAPER
+            .sessions()
+            .ok_or_else(|| anyhow::anyhow!("No session store configured"))
+    }
+
+    /**
+     * Garantiza que exista una sesión válida con el nombre dado.
+     *
+     * Si la sesión no existe o expiró se ejecuta `login`, que debe guardar la
+     * sesión con `Context::save_session`. Los logins de una misma sesión se
+     * serializan para que las tareas concurrentes reutilicen la sesión creada
+     * por la primera.
+     *
+     * @param name Nombre de la sesión (ej. `instagram:cuenta`).
+     * @param login Función que inicia sesión y guarda las cookies.
+     * @return Sesión válida, la existente o la recién creada.
+     */
+    pub async fn ensure<L, Fut, T>(name: &str, login: L) -> anyhow::Result<Session>
+    where
+        L: Fn() -> Fut,
+        Fut: Future<Output = anyhow::Result<T>>,
+    {
+        let lock = Self::login_lock(name);
+        let _guard = lock.lock().await;
+        let store = Self::store()?;
+
+        if let Some(session) = store.get(name) {
+            return Ok(session);
+        }
+
+        info!("No valid session for {}, logging in", name);
+        login().await?;
+
+        store
+            .get(name)
+            .ok_or_else(|| anyhow::anyhow!("Login did not create session {}", name))
+    }
+
+    /**
+     * Descarta una sesión rechazada por el sitio.
+     *
+     * Otra tarea pudo iniciar sesión de nuevo mientras esta se ejecutaba, por lo
+     * que la sesión solo se borra si sigue siendo la que usó la tarea.
+     *
+     * @param name Nombre de la sesión.
+     * @param used Sesión con la que se ejecutó la tarea.
+     */
+    async fn discard(name: &str, used: &Session) -> anyhow::Result<()> {
+        let lock = Self::login_lock(name);
+        let _guard = lock.lock().await;
+        let store = Self::store()?;
+
+        let unchanged = store
+            .get(name)
+            .is_some_and(|current| current.saved_at == used.saved_at && current.cookies == used.cookies);
+        if unchanged {
+            store.remove(name);
+        }
+        Ok(())
+    }
+
+    /**
+     * Ejecuta una tarea en el navegador con las cookies de la sesión indicada.
+     *
+     * La tarea debe devolver `Error::SessionInvalid` cuando detecte que el sitio
+     * pide iniciar sesión. En ese caso la sesión se descarta, se inicia sesión de
+     * nuevo y la tarea se reintenta una sola vez. La tarea usa el proxy de la
+     * plataforma de la sesión.
+     *
+     * Las tareas de una misma sesión reutilizan un contexto del navegador que ya
+     * tiene la sesión iniciada, en lugar de restaurar las cookies en uno nuevo.
+     * Los contextos de las tareas fallidas se descartan.
+     *
+     * @param name Nombre de la sesión (ej. `instagram:cuenta`).
+     * @param login Función que inicia sesión y guarda las cookies.
+     * @param task Tarea a ejecutar con la sesión aplicada.
+     * @return Resultado de la tarea.
+     */
+    pub async fn execute<L, Fut, T, F, R>(name: &str, login: L, task: F) -> anyhow::Result<R>
+    where
+        L: Fn() -> Fut,
+        Fut: Future<Output = anyhow::Result<T>>,
+        F: Fn(&Context) -> zbrowser::Result<R> + Send + Sync + 'static,
+        R: Send + Sync + 'static,
+    {
+        let task = Arc::new(task);
+        let platform = name.split(':').next().unwrap_or(name);
+
+        for attempt in 0..2 {
+            let used = Self::ensure(name, &login).await?;
+
+            let session = name.to_string();
+            let blocked = platform.to_string();
+            let task = Arc::clone(&task);
+            let result = PROXIES
+                .execute_pooled(&SCRAPER, platform, name, move |context| {
+                    // Los contextos reutilizados conservan las cookies de la tarea anterior
+                    if !context.is_warm() && !context.use_session(&session)? {
+                        return Err(Error::SessionInvalid(session.clone()));
+                    }
+                    // Las reglas de bloqueo duran una sola tarea, incluso en contextos reutilizados
+                    Blocklist::apply(&context, &blocked)?;
+                    task(&context)
+                })
+                .await;
+
+            match result {
+                Err(Error::SessionInvalid(_)) if attempt == 0 => {
+                    warn!("Session {} was rejected, logging in again", name);
+                    Self::discard(name, &used).await?;
+                }
+                result => return result.map_err(Into::into),
+            }
+        }
+
+        Err(Error::SessionInvalid(name.to_string()).into())
+    }
+}
@@ -0,0 +1,218 @@
+/**
+ * Capturas de las páginas donde el scraping falla o no encuentra resultados.
+ *
+ * Cuando una plataforma muestra un muro de login, un captcha o cambia su diseño,
+ * los scrapers solo ven un error o una lista vacía. Con esta política se guarda
+ * una captura de pantalla completa, el HTML, la URL, los nombres de las cookies
+ * y los diagnósticos del navegador de la página en ese momento, para revisarlos
+ * después. Los valores de las cookies no se guardan, ya que contienen las sesiones
+ * de las cuentas usadas para el scraping.
+ *
+ * Las capturas de cada ejecución del servidor se agrupan en su propia carpeta:
+ * `<dir>/<run_id>/<plataforma>-<contexto>/`. Solo se conservan las carpetas de
+ * las ejecuciones más recientes y un número máximo de capturas por ejecución.
+ */
+
+use serde::{Deserialize, Serialize};
+use std::{fs, path::PathBuf, time::SystemTime};
+use tracing::{info, warn};
+use zbrowser::{Context, Diagnostics, Error, ScreenshotOptions};
+
+/// Ejecuciones cuyas capturas se conservan por omisión, incluida la actual
+const MAX_RUNS: usize = 10;
+/// Capturas que se guardan por omisión en cada ejecución
+const MAX_SNAPSHOTS_PER_RUN: usize = 100;
+
+/**
+ * Cookie de la página sin su valor.
+ */
+#[derive(Debug, Serialize, Deserialize)]
+struct CookieInfo {
+    name: String,
+    #[serde(default)]
+    domain: String,
+}
+
+/**
+ * Información de la página guardada junto a la captura y el HTML.
+ */
+#[derive(Debug, Serialize)]
+struct FailureReport<'a> {
+    platform: &'a str,
+    reason: &'a str,
+    captured_at: String,
+    url: String,
+    title: String,
+    cookies: Vec<CookieInfo>,
+    diagnostics: Diagnostics,
+}
+
+/**
+ * Política que guarda capturas de las tareas fallidas o sin resultados.
+ */
+pub struct FailureSnapshots {
+    dir: PathBuf,
+    run_id: String,
+    max_runs: usize,
+    max_snapshots: usize,
+}
+
+impl FailureSnapshots {
+    /**
+     * Crea la política para una nueva ejecución; el identificador de la ejecución
+     * es la fecha y hora de inicio más un sufijo aleatorio.
+     *
+     * @param dir Directorio donde se guardan las capturas.
+     */
+    pub fn new<P: Into<PathBuf>>(dir: P) -> Self {
+        let suffix = uuid::Uuid::new_v4().simple().to_string();
+        let run_id = format!("{}-{}", chrono::Utc::now().format("%Y%m%dT%H%M%SZ"), &suffix[..8]);
+        Self::with_run_id(dir, run_id)
+    }
+
+    /**
+     * Crea la política con un identificador de ejecución conocido.
+     *
+     * @param dir Directorio donde se guardan las capturas.
+     * @param run_id Identificador de la ejecución.
+     */
+    pub fn with_run_id<P: Into<PathBuf>, S: Into<String>>(dir: P, run_id: S) -> Self {
+        Self {
+            dir: dir.into(),
+            run_id: run_id.into(),
+            max_runs: MAX_RUNS,
+            max_snapshots: MAX_SNAPSHOTS_PER_RUN,
+        }
+    }
+
+    /**
+     * Cambia cuántas capturas se conservan.
+     *
+     * @param max_runs Ejecuciones cuyas capturas se conservan, incluida la actual.
+     * @param max_snapshots Capturas que se guardan en cada ejecución.
+     */
+    pub fn with_retention(mut self, max_runs: usize, max_snapshots: usize) -> Self {
+        self.max_runs = max_runs.max(1);
+        self.max_snapshots = max_snapshots;
+        self
+    }
+
+    /**
+     * Carpeta donde se guardan las capturas de esta ejecución.
+     */
+    pub fn run_dir(&self) -> PathBuf {
+        self.dir.join(&self.run_id)
+    }
+
+    /**
+     * Borra las carpetas de las ejecuciones más antiguas, conservando las
+     * `max_runs` más recientes por fecha de modificación; la actual nunca se borra.
+     */
+    fn prune(&self) {
+        let Ok(entries) = fs::read_dir(&self.dir) else {
+            return;
+        };
+
+        let mut runs: Vec<(SystemTime, PathBuf)> = entries
+            .flatten()
+            .filter(|entry| entry.file_name() != self.run_id.as_str())
+            .filter(|entry| entry.file_type().is_ok_and(|kind| kind.is_dir()))
+            .map(|entry| {
+                let modified = entry.metadata().and_then(|metadata| metadata.modified());
+                (modified.unwrap_or(SystemTime::UNIX_EPOCH), entry.path())
+            })
+            .collect();
+
+        // La más reciente primero; la ejecución actual ocupa uno de los lugares
+        runs.sort_by(|a, b| b.0.cmp(&a.0));
+        for (_, path) in runs.into_iter().skip(self.max_runs - 1) {
+            if let Err(e) = fs::remove_dir_all(&path) {
+                warn!("Failed to remove old snapshots {}: {}", path.display(), e);
+            }
+        }
+    }
+
+    /**
+     * Ejecuta la parte de una tarea que extrae resultados y guarda una captura
+     * de la página si devuelve un error o ninguna publicación.
+     *
+     * Los errores de un contexto cerrado, desconectado, con la sesión rechazada,
+     * cuyo navegador falló o que superó sus límites de recursos no se capturan,
+     * ya que la página no está disponible o la tarea se reintenta.
+     * El resultado se devuelve sin cambios aunque la captura falle.
+     *
+     * @param platform Plataforma de la tarea (ej. `instagram`).
+     * @param context Contexto del navegador de la tarea.
+     * @param task Extracción de los resultados.
+     * @return Resultado de la extracción.
+     */
+    pub fn watch<T, F>(&self, platform: &str, context: &Context, task: F) -> zbrowser::Result<Vec<T>>
+    where
+        F: FnOnce() -> zbrowser::Result<Vec<T>>,
+    {
+        let result = task();
+
+        let reason = match &result {
+            Ok(items) if items.is_empty() => "no results".to_string(),
+            Ok(_) => return result,
+            Err(
+                Error::ContextClosed
+                | Error::Disconnected(_)
+                | Error::SessionInvalid(_)
+                | Error::BrowserCrashed(_)
+                | Error::LimitExceeded(_),
+            ) => return result,
+            Err(e) => e.to_string(),
+        };
+
+        match self.save(platform, context, &reason) {
+            Ok(path) => info!("Saved {} failure snapshot ({}) to {}", platform, reason, path.display()),
+            Err(e) => warn!("Failed to save {} failure snapshot: {}", platform, e),
+        }
+        result
+    }
+
+    /**
+     * Guarda la captura de pantalla, el HTML y el reporte de la página actual.
+     *
+     * @param platform Plataforma de la tarea.
+     * @param context Contexto del navegador de la tarea.
+     * @param reason Motivo de la captura (error o falta de resultados).
+     * @return Carpeta donde se guardó la captura, o un error si la ejecución
+     *         ya tiene el máximo de capturas.
+     */
+    pub fn save(&self, platform: &str, context: &Context, reason: &str) -> anyhow::Result<PathBuf> {
+        let saved = fs::read_dir(self.run_dir()).map(|entries| entries.count()).unwrap_or(0);
+        if saved >= self.max_snapshots {
+            anyhow::bail!("the run already has {} snapshots", saved);
+        }
+
+        let path = self.run_dir().join(format!("{}-{}", platform, context.id()));
+        fs::create_dir_all(&path)?;
+        if saved == 0 {
+            self.prune();
+        }
+
+        // La captura de pantalla es opcional, el HTML suele bastar para ver qué pasó
+        match context.screenshot(&ScreenshotOptions::full_page()) {
+            Ok(image) => fs::write(path.join("screenshot.png"), image)?,
+            Err(e) => warn!("Failed to take {} screenshot: {}", platform, e),
+        }
+
+        let snapshot = context.snapshot()?;
+        fs::write(path.join("page.html"), &snapshot.html)?;
+
+        let report = FailureReport {
+            platform,
+            reason,
+            captured_at: chrono::Utc::now().to_rfc3339(),
+            url: snapshot.url,
+            title: snapshot.title,
+            cookies: serde_json::from_str(&snapshot.cookies).unwrap_or_default(),
+            diagnostics: context.diagnostics(),
+        };
+        fs::write(path.join("report.json"), serde_json::to_string_pretty(&report)?)?;
+
+        Ok(path)
+    }
+}
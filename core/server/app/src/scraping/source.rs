@@ -0,0 +1,74 @@
+/**
+ * Fuentes sociales de las que se obtienen las métricas de tendencias.
+ *
+ * Cada plataforma implementa `SocialSource` para buscar publicaciones de una
+ * palabra clave o hashtag y devolverlas con el modelo común `SocialPost`. El
+ * scraper de tendencias, el guardado en DynamoDB y el cálculo de analytics
+ * recorren el registro `SOURCES`, por lo que agregar una plataforma consiste
+ * en implementar el trait y registrarla aquí.
+ */
+
+use crate::scraping::{
+    bluesky::BlueskyScraper, instagram::InstagramScraper, mastodon::MastodonScraper,
+    reddit::RedditScraper, region::Region, tiktok::TikTokScraper, twitter::TwitterScraper,
+    youtube::YouTubeScraper,
+};
+use futures::future::BoxFuture;
+use serde::{Deserialize, Serialize};
+
+/**
+ * Publicación de cualquier plataforma con las métricas que se comparan entre ellas.
+ */
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SocialPost {
+    /// Texto o título de la publicación, vacío si la plataforma no lo muestra
+    pub text: String,
+    /// Enlace a la publicación
+    pub link: String,
+    /// Fecha de publicación en formato ISO 8601, vacía si no se pudo obtener
+    pub time: String,
+    /// Reproducciones, 0 si la plataforma no las muestra
+    pub views: u32,
+    /// Likes, votos positivos o reacciones
+    pub likes: u32,
+    /// Comentarios o respuestas
+    pub comments: u32,
+    /// Veces que se compartió o republicó
+    pub shares: u32,
+    /// Alcance del autor: seguidores de la cuenta o miembros de la comunidad
+    pub followers: u32,
+    /// Comunidad donde se publicó (ej. el subreddit), si la plataforma las tiene
+    pub community: Option<String>,
+}
+
+/**
+ * Plataforma de la que se obtienen publicaciones.
+ */
+pub trait SocialSource: Sync {
+    /**
+     * Nombre de la plataforma, usado como clave en las tendencias y en DynamoDB.
+     *
+     * @return Nombre en minúsculas (ej. `instagram`).
+     */
+    fn name(&self) -> &'static str;
+
+    /**
+     * Busca las publicaciones recientes de una palabra clave o hashtag.
+     *
+     * @param query Palabra clave o hashtag sin el símbolo `#`.
+     * @param region Región del usuario con la que se busca, o `None` para la del navegador.
+     * @return Publicaciones encontradas con el modelo común.
+     */
+    fn search(&self, query: String, region: Option<&'static Region>) -> BoxFuture<'static, anyhow::Result<Vec<SocialPost>>>;
+}
+
+/// Plataformas de las que se obtienen las tendencias, en el orden en que se muestran
+pub static SOURCES: &[&dyn SocialSource] = &[
+    &RedditScraper,
+    &InstagramScraper,
+    &TwitterScraper,
+    &TikTokScraper,
+    &YouTubeScraper,
+    &BlueskyScraper,
+    &MastodonScraper,
+];
@@ -0,0 +1,242 @@
+/**
+ * Scraper de TikTok para obtener las publicaciones de un hashtag.
+ *
+ * La página de un hashtag carga sus videos desde el endpoint interno
+ * `/api/challenge/item_list/`, que devuelve por cada video sus reproducciones,
+ * likes, comentarios, veces compartido, fecha de publicación y los seguidores
+ * de su autor. En lugar de leer el DOM, el scraper captura esas respuestas
+ * mientras se desplaza por la página, por lo que no necesita iniciar sesión
+ * ni visitar el perfil de cada autor.
+ */
+
+use crate::scraping::{
+    PROXIES, SCRAPER, SNAPSHOTS, Utils,
+    blocklist::Blocklist,
+    region::Region,
+    source::{SocialPost, SocialSource},
+};
+use chrono::{DateTime, SecondsFormat};
+use futures::future::BoxFuture;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use zbrowser::{Context, Error, Scraper};
+
+/// Nombre de la plataforma, usado para la asignación de proxies y las listas de bloqueo.
+const PLATFORM: &str = "tiktok";
+
+/// URL base de las páginas de hashtags.
+const TIKTOK_TAG_URL: &str = "https://www.tiktok.com/tag";
+
+/// Expresión regular de las respuestas con los videos de un hashtag.
+const ITEM_LIST_PATTERN: &str = r"/api/challenge/item_list/";
+
+/// Tiempo máximo en milisegundos que se espera la primera página de videos.
+const PAGE_TIMEOUT_MS: i64 = 15_000;
+/// Tiempo máximo en milisegundos que se esperan más videos después de un scroll.
+const STALL_TIMEOUT_MS: i64 = 5_000;
+/// Profundidad máxima de scroll al recolectar videos.
+const MAX_SCROLLS: usize = 15;
+
+/// Número de videos que se recolectan por hashtag si no se indica otro.
+pub const DEFAULT_POSTS: usize = 30;
+
+/// Página de videos devuelta por `/api/challenge/item_list/`.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ItemList {
+    #[serde(default)]
+    item_list: Vec<Item>,
+    #[serde(default)]
+    has_more: bool,
+}
+
+/// Video tal como lo devuelve TikTok, solo con los campos usados.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Item {
+    id: String,
+    #[serde(default)]
+    desc: String,
+    create_time: i64,
+    author: Author,
+    stats: Stats,
+    #[serde(default)]
+    author_stats: AuthorStats,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Author {
+    unique_id: String,
+}
+
+/// Contadores del video; las reproducciones pueden superar `u32`.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Stats {
+    #[serde(default)]
+    play_count: u64,
+    #[serde(default)]
+    digg_count: u64,
+    #[serde(default)]
+    comment_count: u64,
+    #[serde(default)]
+    share_count: u64,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AuthorStats {
+    #[serde(default)]
+    follower_count: u64,
+}
+
+/// Video de TikTok con sus métricas de engagement y el alcance de su autor.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TikTokPost {
+    pub author: String,
+    pub text: String,
+    pub link: String,
+    pub time: String,
+    pub views: u32,
+    pub likes: u32,
+    pub comments: u32,
+    pub shares: u32,
+    pub followers: u32,
+}
+
+impl From<Item> for TikTokPost {
+    fn from(item: Item) -> Self {
+        let count = |value: u64| value.min(u32::MAX as u64) as u32;
+        let time = DateTime::from_timestamp(item.create_time, 0)
+            .map(|time| time.to_rfc3339_opts(SecondsFormat::Millis, true))
+            .unwrap_or_default();
+
+        TikTokPost {
+            link: format!("https://www.tiktok.com/@{}/video/{}", item.author.unique_id, item.id),
+            author: item.author.unique_id,
+            text: Utils::clean_text(&item.desc),
+            time,
+            views: count(item.stats.play_count),
+            likes: count(item.stats.digg_count),
+            comments: count(item.stats.comment_count),
+            shares: count(item.stats.share_count),
+            followers: count(item.author_stats.follower_count),
+        }
+    }
+}
+
+/// Scraper de hashtags de TikTok.
+pub struct TikTokScraper;
+
+impl TikTokScraper {
+    /**
+     * Obtiene videos de un hashtag con el scraper dado, desplazándose por la página
+     * hasta reunir `limit`.
+     *
+     * Si falla o no hay videos se guarda una captura de la página.
+     *
+     * @param scraper Scraper que ejecuta la tarea.
+     * @param hashtag Hashtag sin el símbolo `#`.
+     * @param limit Número máximo de videos a recolectar.
+     * @param region Región del usuario con la que se busca, o `None` para la del navegador.
+     * @return Videos encontrados, sin repetidos.
+     */
+    pub async fn get_posts_with(
+        scraper: &Scraper,
+        hashtag: String,
+        limit: usize,
+        region: Option<&'static Region>,
+    ) -> anyhow::Result<Vec<TikTokPost>> {
+        let url = format!("{}/{}", TIKTOK_TAG_URL, hashtag);
+
+        let posts = PROXIES
+            .execute(scraper, PLATFORM, move |context| {
+                SNAPSHOTS.watch(PLATFORM, &context, || {
+                    if let Some(region) = region {
+                        region.apply(&context)?;
+                    }
+                    Blocklist::apply(&context, PLATFORM)?;
+                    // Se registra antes de navegar para no perder la primera página de videos
+                    context.capture_responses(ITEM_LIST_PATTERN)?;
+                    context.navigate(&url)?;
+                    Self::collect_posts(&context, limit)
+                })
+            })
+            .await?;
+
+        Ok(posts)
+    }
+
+    /**
+     * Recolecta los videos de la página del hashtag abierta en el contexto.
+     *
+     * Cada scroll hace que TikTok pida la siguiente página de videos; se lee cada
+     * respuesta hasta reunir `limit`, hasta que TikTok indique que no hay más o
+     * deje de responder. Las respuestas deben capturarse con `capture_responses`
+     * antes de navegar.
+     *
+     * @param context Contexto del navegador en la página del hashtag.
+     * @param limit Número máximo de videos a recolectar.
+     * @return Videos encontrados, sin repetidos.
+     */
+    pub fn collect_posts(context: &Context, limit: usize) -> zbrowser::Result<Vec<TikTokPost>> {
+        let mut posts = Vec::new();
+        let mut seen = HashSet::new();
+        let mut timeout = PAGE_TIMEOUT_MS;
+
+        for _ in 0..MAX_SCROLLS {
+            let response = match context.wait_for_response(ITEM_LIST_PATTERN, timeout) {
+                Ok(response) => response,
+                // Hashtags sin videos o sin más páginas
+                Err(Error::Timeout) => break,
+                Err(e) => return Err(e),
+            };
+
+            let page: ItemList = response.json()?;
+            for item in page.item_list {
+                if seen.insert(item.id.clone()) {
+                    posts.push(TikTokPost::from(item));
+                }
+            }
+
+            if posts.len() >= limit || !page.has_more {
+                break;
+            }
+            context.scroll()?;
+            timeout = STALL_TIMEOUT_MS;
+        }
+
+        posts.truncate(limit);
+        Ok(posts)
+    }
+}
+
+impl From<TikTokPost> for SocialPost {
+    fn from(post: TikTokPost) -> Self {
+        SocialPost {
+            text: post.text,
+            link: post.link,
+            time: post.time,
+            views: post.views,
+            likes: post.likes,
+            comments: post.comments,
+            shares: post.shares,
+            followers: post.followers,
+            community: None,
+        }
+    }
+}
+
+impl SocialSource for TikTokScraper {
+    fn name(&self) -> &'static str {
+        PLATFORM
+    }
+
+    fn search(&self, query: String, region: Option<&'static Region>) -> BoxFuture<'static, anyhow::Result<Vec<SocialPost>>> {
+        Box::pin(async move {
+            let posts = Self::get_posts_with(&SCRAPER, query, DEFAULT_POSTS, region).await?;
+            Ok(posts.into_iter().map(SocialPost::from).collect())
+        })
+    }
+}
@@ -1,62 +1,40 @@
 /**
  * Módulo para análisis de tendencias y métricas de redes sociales.
  * 
- * Este módulo orquesta la recolección de métricas de engagement de múltiples
- * plataformas sociales (Reddit, Instagram, Twitter) basándose en palabras clave
- * extraídas de noticias y hashtags específicos. Proporciona análisis cruzado
- * de tendencias y consolidación de datos de diferentes fuentes para generar
- * informes completos de engagement social.
+ * Este módulo orquesta la recolección de métricas de engagement de las
+ * plataformas sociales registradas en `SOURCES` (Reddit, Instagram, Twitter,
+ * TikTok, YouTube, Bluesky, Mastodon) basándose en palabras clave extraídas de noticias y hashtags específicos.
+ * Proporciona análisis cruzado de tendencias y consolidación de datos de
+ * diferentes fuentes para generar informes completos de engagement social.
  * 
  * Autor: Carlos Alberto Zamudio Velázquez 
  * Contribuyentes: Renato García Morán
  */
 
 use crate::scraping::{
-    instagram::{InstagramPost, InstagramScraper},
     notices::{Details, NoticesScraper, Params},
-    reddit::{RedditScraper, SimplePostWithMembers},
-    twitter::{TweetData, TwitterScraper},
+    region::Region,
+    source::{SOURCES, SocialPost, SocialSource},
 };
 use futures::future::join_all;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use tracing::warn;
 
 /**
- * Estructuras de datos para métricas de engagement por plataforma.
- * 
- * Define los modelos de datos que encapsulan las métricas de cada red social,
- * organizando los posts por palabra clave para facilitar el análisis comparativo
- * entre plataformas.
+ * Publicaciones de una plataforma encontradas para una palabra clave o hashtag.
  */
 #[derive(Deserialize, Serialize, Debug)]
-pub struct RedditMetrics {
-    pub keyword: String,
-    pub posts: Vec<SimplePostWithMembers>,
-}
-
-#[derive(Deserialize, Serialize, Debug)]
-pub struct InstagramMetrics {
-    pub keyword: String,
-    pub posts: Vec<InstagramPost>,
-}
-
-#[derive(Deserialize, Serialize, Debug)]
-pub struct TwitterMetrics {
+pub struct Metrics {
     pub keyword: String,
-    pub posts: Vec<TweetData>,
+    pub posts: Vec<SocialPost>,
 }
 
 /**
- * Estructura consolidada de datos de todas las plataformas sociales.
- * 
- * Agrupa las métricas de Reddit, Instagram y Twitter para proporcionar
- * una vista unificada del engagement social por plataforma.
+ * Métricas de todas las plataformas sociales, agrupadas por el nombre de cada
+ * plataforma registrada en `SOURCES` (ej. `reddit`, `instagram`, `twitter`).
  */
-#[derive(Deserialize, Serialize, Debug)]
-pub struct Data {
-    pub reddit: Vec<RedditMetrics>,
-    pub instagram: Vec<InstagramMetrics>,
-    pub twitter: Vec<TwitterMetrics>,
-}
+pub type Data = BTreeMap<String, Vec<Metrics>>;
 
 /**
  * Estructura principal de tendencias con metadatos y datos de engagement.
@@ -79,211 +57,91 @@ pub struct Trends {
 pub struct TrendsScraper;
 
 impl TrendsScraper {
-    pub async fn get_reddit_metrics(details: &Details) -> Vec<RedditMetrics> {
-        let mut futures = Vec::new();
-
-        for detail in details {
-            for keyword in &detail.keywords {
-                let future = async move {
-                    let posts = RedditScraper::get_simple_posts_with_members(keyword.clone()).await;
-                    RedditMetrics {
-                        keyword: keyword.clone(),
-                        posts,
-                    }
-                };
-
-                futures.push(future);
-            }
-        }
-
-        let results = join_all(futures).await;
-        results.into_iter().collect()
+    /**
+     * Devuelve las métricas vacías de todas las plataformas registradas.
+     *
+     * @return Una entrada sin métricas por plataforma.
+     */
+    pub fn empty_data() -> Data {
+        SOURCES.iter().map(|source| (source.name().to_string(), Vec::new())).collect()
     }
 
-    pub async fn get_instagram_metrics(details: &Details) -> Vec<InstagramMetrics> {
-        let mut futures = Vec::new();
-
-        for detail in details {
-            for keyword in &detail.keywords {
-                let future = async move {
-                    match InstagramScraper::get_posts(keyword.clone()).await {
-                        Ok(posts) => InstagramMetrics {
-                            keyword: keyword.clone(),
-                            posts,
-                        },
-                        Err(_) => InstagramMetrics {
-                            keyword: keyword.clone(),
-                            posts: Vec::new(),
-                        },
-                    }
-                };
-
-                futures.push(future);
+    /**
+     * Busca en una plataforma las publicaciones de cada palabra clave o hashtag.
+     *
+     * Las búsquedas se hacen en paralelo; las que fallan se registran y quedan sin
+     * publicaciones para no perder las métricas de las demás.
+     *
+     * @param source Plataforma en la que se busca.
+     * @param queries Palabras clave o hashtags a buscar.
+     * @param region Región del usuario con la que se busca, o `None` para la del navegador.
+     * @return Métricas de cada búsqueda, en el orden de `queries`.
+     */
+    pub async fn get_metrics(
+        source: &dyn SocialSource,
+        queries: &[String],
+        region: Option<&'static Region>,
+    ) -> Vec<Metrics> {
+        let futures = queries.iter().map(|query| async move {
+            let posts = source.search(query.clone(), region).await.unwrap_or_else(|e| {
+                warn!("{} scraping failed for '{}': {}", source.name(), query, e);
+                Vec::new()
+            });
+            Metrics {
+                keyword: query.clone(),
+                posts,
             }
-        }
+        });
 
-        let results = join_all(futures).await;
-        results.into_iter().collect()
+        join_all(futures).await
     }
 
-    pub async fn get_reddit_metrics_from_hashtags(hashtags: &[String]) -> Vec<RedditMetrics> {
-        let mut futures = Vec::new();
-        for hashtag in hashtags {
-            let hashtag_clone = hashtag.clone();
-            let future = async move {
-                let posts = RedditScraper::get_simple_posts_with_members(hashtag_clone.clone()).await;
-                RedditMetrics {
-                    keyword: hashtag_clone,
-                    posts,
-                }
-            };
-            futures.push(future);
-        }
-        let results = join_all(futures).await;
-        results.into_iter().collect()
+    /**
+     * Busca en todas las plataformas registradas a la vez.
+     *
+     * @param queries Palabras clave o hashtags a buscar.
+     * @param region Región del usuario con la que se busca, o `None` para la del navegador.
+     * @return Métricas de cada plataforma.
+     */
+    pub async fn get_all_metrics(queries: &[String], region: Option<&'static Region>) -> Data {
+        let futures = SOURCES.iter().map(|source| async move {
+            (source.name().to_string(), Self::get_metrics(*source, queries, region).await)
+        });
+
+        join_all(futures).await.into_iter().collect()
     }
-    pub async fn get_instagram_metrics_from_hashtags(hashtags: &[String]) -> Vec<InstagramMetrics> {
-        let mut futures = Vec::new();
-        for hashtag in hashtags {
-            let hashtag_clone = hashtag.clone();
-            let future = async move {
-                match InstagramScraper::get_posts(hashtag_clone.clone()).await {
-                    Ok(posts) => InstagramMetrics {
-                        keyword: hashtag_clone,
-                        posts,
-                    },
-                    Err(_) => InstagramMetrics {
-                        keyword: hashtag_clone,
-                        posts: Vec::new(),
-                    },
-                }
-            };
-            futures.push(future);
-        }
-        let results = join_all(futures).await;
-        results.into_iter().collect()
-    }
-
-    pub async fn get_twitter_metrics(details: &Details) -> Vec<TwitterMetrics> {
-        let mut futures = Vec::new();
-
-        for detail in details {
-            for keyword in &detail.keywords {
-                let future = async move {
-                    match TwitterScraper::get_posts(keyword.clone()).await {
-                        Ok(posts) => TwitterMetrics {
-                            keyword: keyword.clone(),
-                            posts,
-                        },
-                        Err(_) => TwitterMetrics {
-                            keyword: keyword.clone(),
-                            posts: Vec::new(),
-                        },
-                    }
-                };
-
-                futures.push(future);
-            }
-        }
 
-        let results = join_all(futures).await;
-        results.into_iter().collect()
-    }
-
-    pub async fn get_twitter_metrics_from_hashtags(hashtags: &[String]) -> Vec<TwitterMetrics> {
-        let mut futures = Vec::new();
-        for hashtag in hashtags {
-            let hashtag_clone = hashtag.clone();
-            let future = async move {
-                match TwitterScraper::get_posts(hashtag_clone.clone()).await {
-                    Ok(posts) => TwitterMetrics {
-                        keyword: hashtag_clone,
-                        posts,
-                    },
-                    Err(_) => TwitterMetrics {
-                        keyword: hashtag_clone,
-                        posts: Vec::new(),
-                    },
-                }
-            };
-            futures.push(future);
-        }
-        let results = join_all(futures).await;
-        results.into_iter().collect()
+    pub async fn get_trends(params: Params) -> anyhow::Result<Trends> {
+        Self::get_trends_with_hashtags(params, None, None).await
     }
 
-    pub async fn get_trends(params: Params) -> anyhow::Result<Trends> {
+    /**
+     * Obtiene las noticias y las métricas de sus palabras clave y de los hashtags dados.
+     *
+     * @param params Parámetros de búsqueda de noticias.
+     * @param hashtags Hashtags adicionales a buscar en las redes sociales.
+     * @param region Región del usuario con la que se hacen las búsquedas, o `None` para la del navegador.
+     * @return Noticias y métricas de cada plataforma.
+     */
+    pub async fn get_trends_with_hashtags(
+        params: Params,
+        hashtags: Option<Vec<String>>,
+        region: Option<&'static Region>,
+    ) -> anyhow::Result<Trends> {
         let details = NoticesScraper::get_details(params).await?;
 
-        let reddit_future = Self::get_reddit_metrics(&details);
-        let instagram_future = Self::get_instagram_metrics(&details);
-        let twitter_future = Self::get_twitter_metrics(&details);
+        // Primero las palabras clave de las noticias y después los hashtags
+        let mut queries: Vec<String> = details
+            .iter()
+            .flat_map(|detail| detail.keywords.iter().cloned())
+            .collect();
+        queries.extend(hashtags.unwrap_or_default());
 
-        let (reddit, instagram, twitter) = futures::future::join3(reddit_future, instagram_future, twitter_future).await;
+        let data = Self::get_all_metrics(&queries, region).await;
 
         Ok(Trends {
             metadata: details,
-            data: Data {
-                reddit,
-                instagram,
-                twitter,
-            },
+            data,
         })
     }
-    pub async fn get_trends_with_hashtags(params: Params, hashtags: Option<Vec<String>>) -> anyhow::Result<Trends> {
-        let details = NoticesScraper::get_details(params).await?;
-        
-        // Obtener métricas de las palabras clave de las noticias
-        let reddit_keywords_future = Self::get_reddit_metrics(&details);
-        let instagram_keywords_future = Self::get_instagram_metrics(&details);
-        let twitter_keywords_future = Self::get_twitter_metrics(&details);
-        
-        // Si hay hashtags, también obtener métricas de los hashtags
-        let (mut reddit_results, mut instagram_results, mut twitter_results) = if let Some(ref hashtags) = hashtags {
-            let reddit_hashtags_future = Self::get_reddit_metrics_from_hashtags(hashtags);
-            let instagram_hashtags_future = Self::get_instagram_metrics_from_hashtags(hashtags);
-            let twitter_hashtags_future = Self::get_twitter_metrics_from_hashtags(hashtags);
-            
-            let (
-                reddit_keywords, 
-                instagram_keywords, 
-                twitter_keywords,
-                reddit_hashtags, 
-                instagram_hashtags, 
-                twitter_hashtags
-            ) = futures::join!(
-                reddit_keywords_future, 
-                instagram_keywords_future,
-                twitter_keywords_future, 
-                reddit_hashtags_future,
-                instagram_hashtags_future,
-                twitter_hashtags_future
-            );
-            
-            // Combinar resultados de keywords y hashtags
-            let mut combined_reddit = reddit_keywords;
-            combined_reddit.extend(reddit_hashtags);
-            
-            let mut combined_instagram = instagram_keywords;
-            combined_instagram.extend(instagram_hashtags);
-            
-
-            let mut combined_twitter = twitter_keywords;
-            combined_twitter.extend(twitter_hashtags);
-            
-            (combined_reddit, combined_instagram, combined_twitter)
-        } else {
-            // Solo usar las keywords de las noticias
-            futures::future::join3(reddit_keywords_future, instagram_keywords_future, twitter_keywords_future).await
-        };
-
-        Ok(Trends {
-            metadata: details,
-            data: Data {
-                reddit: reddit_results,
-                instagram: instagram_results,
-                twitter: twitter_results,
-            },
-        })
-    }
-}
\ No newline at end of file
+}
@@ -13,12 +13,17 @@
 
 use crate::{
     config::Config,
-    scraping::{SCRAPER, Utils},
+    scraping::{
+        PROXIES, SCRAPER, SNAPSHOTS, Utils,
+        blocklist::Blocklist,
+        region::Region,
+        session::Sessions,
+        source::{SocialPost, SocialSource},
+    },
 };
-use futures_util::future::join_all;
-use once_cell::sync::OnceCell;
+use futures_util::future::{BoxFuture, join_all};
 use serde::{Deserialize, Serialize};
-use tracing::info;
+use zbrowser::{Context, Error, Priority, ScrollOptions, TaskOptions};
 
 /**
  * Constantes de configuración para la autenticación y navegación en Twitter/X.
@@ -27,11 +32,13 @@ use tracing::info;
  * y selectores CSS para los elementos de la interfaz de Twitter.
  */
 static USER_AGENT: &str = "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36";
-static JS_HOVER: &str = include_str!("hover.js");
-static COOKIES: OnceCell<String> = OnceCell::new();
+
+// Platform name used for the session and proxy assignment
+const PLATFORM: &str = "twitter";
 
 const TWITTER_LOGIN_URL: &str = "https://x.com/";
 const TWITTER_POST_URL: &str = "https://x.com/search?q=%23";
+const TWITTER_LOGIN_PATHS: [&str; 2] = ["/i/flow/login", "/login"];
 
 const INIT_LOGIN_BUTTON_SELECTOR: &str = "[data-testid='loginButton']";
 const USERNAME_SELECTOR: &str = "input[name='text']";
@@ -39,10 +46,19 @@ const PASSWORD_SELECTOR: &str = "input[name='password']";
 const LOGIN_BUTTON_SELECTOR: &str = "button[data-testid='LoginForm_Login_Button']";
 
 const POST_SELECTOR: &str = "main > div > div:nth-of-type(2) > div > div > div";
+const TWEET_SELECTOR: &str = "article[data-testid='tweet']";
 const TIME_SELECTOR: &str = "a span time";
 
 const FOLLOWERS_SELECTOR: &str = "section a span span";
 
+/// Tiempos de espera en milisegundos para la carga de páginas y el login.
+const NETWORK_IDLE_MS: i64 = 1_000;
+const PAGE_TIMEOUT_MS: i64 = 15_000;
+const LOGIN_TIMEOUT_MS: i64 = 30_000;
+
+/// Número de tweets que se recolectan por hashtag si no se indica otro.
+pub const DEFAULT_TWEETS: usize = 20;
+
 /**
  * Estructuras de datos para representar tweets y información de perfiles.
  * 
@@ -94,188 +110,251 @@ pub struct TweetData {
 pub struct TwitterScraper;
 
 impl TwitterScraper {
+    /**
+     * Nombre de la sesión persistente de la cuenta configurada.
+     *
+     * @return Nombre con el formato `twitter:cuenta`.
+     */
+    pub fn session_name() -> String {
+        format!("{}:{}", PLATFORM, Config::get_twitter_username())
+    }
 
     pub async fn login() -> anyhow::Result<String> {
-        SCRAPER
-            .execute(move |context| {
-                context.navigate(TWITTER_LOGIN_URL);
-
-                context.click_element(INIT_LOGIN_BUTTON_SELECTOR);
-                std::thread::sleep(std::time::Duration::from_secs(5));
-                
-                context.write_input(USERNAME_SELECTOR, Config::get_twitter_username());
+        let session = Self::session_name();
+        PROXIES
+            .execute(&SCRAPER, PLATFORM, move |context| {
+                Blocklist::apply(&context, PLATFORM)?;
+                context.navigate(TWITTER_LOGIN_URL)?;
+
+                context.click_element(INIT_LOGIN_BUTTON_SELECTOR)?;
+                context.wait_for_element(USERNAME_SELECTOR, PAGE_TIMEOUT_MS)?;
+
+                context.write_input(USERNAME_SELECTOR, Config::get_twitter_username())?;
                 context.evaluate("
                 (() => {
                     document.evaluate(\"//span[contains(text(), 'Next')]\", document, null, XPathResult.FIRST_ORDERED_NODE_TYPE, null).singleNodeValue.click();
                     return '';
-                })()");
-                std::thread::sleep(std::time::Duration::from_secs(5));
-                context.write_input(PASSWORD_SELECTOR, Config::get_twitter_password());
-                context.click_element(LOGIN_BUTTON_SELECTOR);
-                std::thread::sleep(std::time::Duration::from_secs(15));
-                let cookies = context.string_cookies();
-                cookies
+                })()")?;
+                context.wait_for_element(PASSWORD_SELECTOR, PAGE_TIMEOUT_MS)?;
+                context.write_input(PASSWORD_SELECTOR, Config::get_twitter_password())?;
+                context.click_element(LOGIN_BUTTON_SELECTOR)?;
+                Utils::ignore_timeout(context.wait_for_navigation(LOGIN_TIMEOUT_MS))?;
+
+                // X mantiene el flujo de login abierto cuando las credenciales son rechazadas
+                if Self::is_login_wall(&context)? {
+                    return Err(Error::Failed("X rejected the login".to_string()));
+                }
+
+                context.save_session(&session)?;
+                context.string_cookies()
             })
             .await
+            .map_err(Into::into)
     }
 
-    pub async fn apply_login() -> anyhow::Result<()> {
-        if COOKIES.get().is_some() {
-            return Ok(());
-        }
+    /**
+     * Indica si X redirigió la página actual a su flujo de login.
+     *
+     * @param context Contexto del navegador después de navegar.
+     * @return `true` si se muestra la pantalla de login.
+     */
+    fn is_login_wall(context: &Context) -> zbrowser::Result<bool> {
+        let path = context.evaluate("location.pathname")?;
+        Ok(TWITTER_LOGIN_PATHS.iter().any(|login| path.starts_with(login)))
+    }
 
-        if std::path::Path::new("x_cookies.json").exists() {
-            info!("Loading cookies from file");
-            let cookies = std::fs::read_to_string("x_cookies.json")?;
-            let _ = COOKIES.set(cookies);
-        } else {
-            info!("No cookies found, logging in");
-            let cookies = TwitterScraper::login().await?;
-            std::fs::write("x_cookies.json", cookies.clone())?;
-            let _ = COOKIES.set(cookies);
+    /**
+     * Navega a una URL de X con la sesión aplicada.
+     *
+     * @param context Contexto del navegador con la sesión aplicada.
+     * Espera a que la red quede inactiva para que cargue el contenido dinámico.
+     *
+     * @param url URL a visitar.
+     * @return `Error::SessionInvalid` si X pide iniciar sesión de nuevo.
+     */
+    fn open(context: &Context, url: &str) -> zbrowser::Result<()> {
+        context.set_user_agent(USER_AGENT)?;
+        context.navigate(url)?;
+        Utils::ignore_timeout(context.wait_for_network_idle(NETWORK_IDLE_MS, PAGE_TIMEOUT_MS))?;
+
+        if Self::is_login_wall(context)? {
+            return Err(Error::SessionInvalid(Self::session_name()));
         }
 
         Ok(())
     }
 
+    /**
+     * Obtiene los primeros tweets de un hashtag con el número de seguidores de su autor.
+     *
+     * @param hashtag Hashtag sin el símbolo `#`.
+     * @return Tweets encontrados, vacío si no hay credenciales configuradas.
+     */
     pub async fn get_posts(hashtag: String) -> anyhow::Result<Vec<TweetData>> {
+        Self::get_posts_with(hashtag, DEFAULT_TWEETS, None).await
+    }
+
+    /**
+     * Obtiene tweets de un hashtag, desplazándose por la búsqueda hasta reunir `limit`.
+     *
+     * @param hashtag Hashtag sin el símbolo `#`.
+     * @param limit Número máximo de tweets a analizar.
+     * @param region Región del usuario con la que se busca, o `None` para la del navegador.
+     * @return Tweets encontrados, vacío si no hay credenciales configuradas.
+     */
+    pub async fn get_posts_with(
+        hashtag: String,
+        limit: usize,
+        region: Option<&'static Region>,
+    ) -> anyhow::Result<Vec<TweetData>> {
         if Config::get_twitter_username().is_empty() || Config::get_twitter_password().is_empty() {
             return Ok(Vec::new());
         }
         
-        TwitterScraper::apply_login().await?;
-    
-        if let Some(cookies) = COOKIES.get() {
-            let url = format!("https://x.com/search?q=%23{}", hashtag);
-    
-            let json = SCRAPER
-                .execute(move |context| {
-                    context.set_user_agent(USER_AGENT);
-                    context.set_string_cookies(cookies.clone());
-                    context.navigate(url.clone());
-                    std::thread::sleep(std::time::Duration::from_secs(5));
-    
-                    for _ in 0..30 {
-                        context.evaluate(
-                            r#"
-                            (() => {
-                                window.scrollBy(0, window.innerHeight);
-                            })()
-                            "#
-                        );
-                        std::thread::sleep(std::time::Duration::from_secs(1));
+        let url = format!("https://x.com/search?q=%23{}", hashtag);
+
+        // Si falla o no hay tweets se guarda una captura de la página
+        let tweets = Sessions::execute(&Self::session_name(), TwitterScraper::login, move |context| {
+            SNAPSHOTS.watch(PLATFORM, context, || {
+                if let Some(region) = region {
+                    region.apply(context)?;
+                }
+                Self::open(context, &url)?;
+                Utils::ignore_timeout(context.wait_for_selector_count(TWEET_SELECTOR, 1, PAGE_TIMEOUT_MS))?;
+                Self::collect_tweets(context, limit)
+            })
+        })
+        .await?;
+
+        let mut full_tweets = Vec::new();
+
+        for mut tweet in tweets {
+            let handle = tweet.handle.trim_start_matches('@').to_string();
+            let profile_url = format!("https://x.com/{}", handle);
+
+            // Con prioridad baja, para no retrasar las búsquedas de otros usuarios
+            let lookup = TaskOptions::current().with_priority(Priority::Low);
+            tweet.followers = lookup
+                .scope(Sessions::execute(&Self::session_name(), TwitterScraper::login, move |context| {
+                    if let Some(region) = region {
+                        region.apply(context)?;
                     }
-    
-                    context.evaluate(
-                        "(() => {
-                            const articles = Array.from(document.querySelectorAll('article[data-testid=\\\"tweet\\\"]'));
-                            const tweets = [];
-    
-                            for (const article of articles) {
-                                try {
-                                    const username = article.querySelector('[data-testid=\\\"User-Name\\\"] span')?.innerText || '';
-                                    const handle = Array.from(article.querySelectorAll('a span'))
-                                        .map(span => span.innerText)
-                                        .find(text => text.startsWith('@')) || '';
-                                    const text = article.querySelector('[data-testid=\\\"tweetText\\\"]')?.innerText || '';
-                                    const time = article.querySelector('time')?.getAttribute('datetime') || '';
-                                    const linkPath = article.querySelector('a[href*=\\\"/status/\\\"]')?.getAttribute('href') || '';
-                                    const link = linkPath ? `https://x.com${linkPath}` : '';
-    
-                                    const replies = parseInt(
-                                        article.querySelector('button[data-testid=\\\"reply\\\"] span')?.textContent?.replace(/[^0-9]/g, '') || '0'
-                                    );
-                                    const retweets = parseInt(
-                                        article.querySelector('button[data-testid=\\\"retweet\\\"] span')?.textContent?.replace(/[^0-9]/g, '') || '0'
-                                    );
-                                    const likes = parseInt(
-                                        article.querySelector('button[data-testid=\\\"like\\\"] span')?.textContent?.replace(/[^0-9]/g, '') || '0'
-                                    );
-    
-                                    tweets.push({
-                                        username,
-                                        handle,
-                                        text,
-                                        time,
-                                        link,
-                                        likes,
-                                        retweets,
-                                        replies
-                                    });
-                                } catch (e) {
-                                    continue;
-                                }
-                            }
-    
-                            return JSON.stringify(tweets);
-                        })()"
-                    )
-                })
+                    Self::open(context, &profile_url)?;
+                    Self::extract_followers(context, &handle)
+                }))
                 .await?;
-    
-            #[derive(Deserialize)]
-            struct PartialTweetData {
-                username: String,
-                handle: String,
-                text: String,
-                link: String,
-                time: String,
-                likes: u32,
-                retweets: u32,
-                replies: u32,
-            }
-    
-            let mut partial_tweets: Vec<PartialTweetData> = serde_json::from_str(&json)?;
-            let mut full_tweets = Vec::new();
-    
-            for tweet in partial_tweets.drain(..) {
-                let handle = tweet.handle.clone();
-                let handle_trimmed = handle.trim_start_matches('@').to_string();
-                let profile_url = format!("https://x.com/{}", handle_trimmed);
-            
-                let followers_count_str = SCRAPER
-                    .execute({
-                        let cookies = cookies.clone();
-                        let handle_trimmed = handle_trimmed.clone();
-                        move |context| {
-                            context.set_user_agent(USER_AGENT);
-                            context.set_string_cookies(cookies.clone());
-                            context.navigate(profile_url.clone());
-                            std::thread::sleep(std::time::Duration::from_secs(4));
-            
-                            context.evaluate(&format!(
-                                r#"
-                                (() => {{
-                                    const followersElement = document.querySelector('a[href="/{}/verified_followers"] span span');
-                                    return followersElement?.textContent || '0';
-                                }})()
-                                "#,
-                                handle_trimmed
-                            ))
-                        }
-                    })
-                    .await?;
-            
-                let followers = Self::parse_followers_count(&followers_count_str);
-            
-                full_tweets.push(TweetData {
-                    username: tweet.username,
-                    handle: tweet.handle,
-                    text: tweet.text,
-                    link: tweet.link,
-                    time: tweet.time,
-                    likes: tweet.likes,
-                    retweets: tweet.retweets,
-                    replies: tweet.replies,
-                    followers,
-                });
-            }
-            
-    
-            Ok(full_tweets)
-        } else {
-            Err(anyhow::anyhow!("No cookies found"))
+
+            full_tweets.push(tweet);
         }
+
+        Ok(full_tweets)
+    }
+
+    /**
+     * Recolecta los tweets de la página de resultados abierta en el contexto.
+     *
+     * X recicla los elementos de la lista al desplazarse, por lo que los tweets se
+     * extraen después de cada paso de scroll y se eliminan los repetidos por enlace.
+     * El número de seguidores de cada autor no aparece en la búsqueda, por lo que
+     * se devuelve en 0.
+     *
+     * @param context Contexto del navegador en la página de búsqueda.
+     * @param limit Número máximo de tweets a recolectar.
+     * @return Tweets encontrados, sin repetidos.
+     */
+    pub fn collect_tweets(context: &Context, limit: usize) -> zbrowser::Result<Vec<TweetData>> {
+        #[derive(Deserialize)]
+        struct PartialTweetData {
+            username: String,
+            handle: String,
+            text: String,
+            link: String,
+            time: String,
+            likes: u32,
+            retweets: u32,
+            replies: u32,
+        }
+
+        let tweets: Vec<PartialTweetData> = context.scroll_collect(
+            "(selector) => {
+                const articles = Array.from(document.querySelectorAll(selector));
+                const tweets = [];
+
+                for (const article of articles) {
+                    try {
+                        const username = article.querySelector('[data-testid=\"User-Name\"] span')?.innerText || '';
+                        const handle = Array.from(article.querySelectorAll('a span'))
+                            .map(span => span.innerText)
+                            .find(text => text.startsWith('@')) || '';
+                        const text = article.querySelector('[data-testid=\"tweetText\"]')?.innerText || '';
+                        const time = article.querySelector('time')?.getAttribute('datetime') || '';
+                        const linkPath = article.querySelector('a[href*=\"/status/\"]')?.getAttribute('href') || '';
+                        const link = linkPath ? `https://x.com${linkPath}` : '';
+
+                        if (!link) {
+                            continue;
+                        }
+
+                        const count = (testId) => parseInt(
+                            article.querySelector(`button[data-testid=\"${testId}\"] span`)?.textContent?.replace(/[^0-9]/g, '') || '0'
+                        );
+
+                        tweets.push({
+                            username,
+                            handle,
+                            text,
+                            time,
+                            link,
+                            likes: count('like'),
+                            retweets: count('retweet'),
+                            replies: count('reply')
+                        });
+                    } catch (e) {
+                        // Queda en los diagnósticos del navegador en lugar de perderse
+                        console.error('Failed to parse tweet', e);
+                        continue;
+                    }
+                }
+
+                return tweets;
+            }",
+            (TWEET_SELECTOR,),
+            |tweet: &PartialTweetData| tweet.link.clone(),
+            &ScrollOptions::new(limit),
+        )?;
+
+        Ok(tweets
+            .into_iter()
+            .map(|tweet| TweetData {
+                username: tweet.username,
+                handle: tweet.handle,
+                text: tweet.text,
+                link: tweet.link,
+                time: tweet.time,
+                likes: tweet.likes,
+                retweets: tweet.retweets,
+                replies: tweet.replies,
+                followers: 0,
+            })
+            .collect())
+    }
+
+    /**
+     * Extrae el número de seguidores del perfil abierto en el contexto.
+     *
+     * @param context Contexto del navegador en la página del perfil.
+     * @param handle Usuario del perfil sin el símbolo `@`.
+     * @return Número de seguidores, 0 si el perfil no lo muestra.
+     */
+    pub fn extract_followers(context: &Context, handle: &str) -> zbrowser::Result<u32> {
+        // Los usuarios solo tienen letras, números y guiones bajos, por lo que no alteran el selector
+        let selector = format!("a[href='/{}/verified_followers'] span span", handle);
+        let followers = match context.query_selector(&selector)? {
+            Some(count) => count.text()?,
+            None => return Ok(0),
+        };
+
+        Ok(Self::parse_followers_count(&followers))
     }
 
     fn parse_followers_count(s: &str) -> u32 {
@@ -290,4 +369,33 @@ impl TwitterScraper {
             s.replace(",", "").parse::<u32>().unwrap_or(0)
         }
     }
-}
\ No newline at end of file
+}
+
+impl From<TweetData> for SocialPost {
+    fn from(tweet: TweetData) -> Self {
+        SocialPost {
+            text: tweet.text,
+            link: tweet.link,
+            time: tweet.time,
+            views: 0,
+            likes: tweet.likes,
+            comments: tweet.replies,
+            shares: tweet.retweets,
+            followers: tweet.followers,
+            community: None,
+        }
+    }
+}
+
+impl SocialSource for TwitterScraper {
+    fn name(&self) -> &'static str {
+        PLATFORM
+    }
+
+    fn search(&self, query: String, region: Option<&'static Region>) -> BoxFuture<'static, anyhow::Result<Vec<SocialPost>>> {
+        Box::pin(async move {
+            let tweets = Self::get_posts_with(query, DEFAULT_TWEETS, region).await?;
+            Ok(tweets.into_iter().map(SocialPost::from).collect())
+        })
+    }
+}
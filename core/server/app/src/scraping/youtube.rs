@@ -0,0 +1,327 @@
+/**
+ * Scraper de YouTube para obtener los videos de una palabra clave o hashtag.
+ *
+ * La búsqueda se ordena por fecha de subida y sus resultados se leen de
+ * `ytInitialData`, el objeto con el que YouTube dibuja la página. Después se
+ * visita cada video para leer de `ytInitialPlayerResponse` y `ytInitialData` sus
+ * reproducciones, likes, comentarios, fecha de publicación y los suscriptores
+ * del canal, por lo que no necesita iniciar sesión ni esperar a que carguen los
+ * comentarios.
+ */
+
+use crate::scraping::{
+    PROXIES, SCRAPER, SNAPSHOTS, Utils,
+    blocklist::Blocklist,
+    region::Region,
+    source::{SocialPost, SocialSource},
+};
+use chrono::{DateTime, NaiveDate, SecondsFormat, Utc};
+use futures::future::{BoxFuture, join_all};
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+use url::Url;
+use zbrowser::{Context, Scraper};
+
+/// Nombre de la plataforma, usado para la asignación de proxies y las listas de bloqueo.
+const PLATFORM: &str = "youtube";
+
+/// URL de la búsqueda de videos.
+const YOUTUBE_SEARCH_URL: &str = "https://www.youtube.com/results";
+
+/// URL base de los videos.
+const YOUTUBE_WATCH_URL: &str = "https://www.youtube.com/watch";
+
+/// Filtro de la búsqueda que muestra solo videos ordenados por fecha de subida.
+const UPLOAD_DATE_FILTER: &str = "CAISAhAB";
+
+/// Idioma de la interfaz; en inglés los contadores usan la coma solo como separador de miles.
+const LANGUAGE: &str = "en";
+
+/// Tiempos de espera en milisegundos para la carga de páginas.
+const NETWORK_IDLE_MS: i64 = 1_000;
+const PAGE_TIMEOUT_MS: i64 = 15_000;
+
+/// Número de videos que se recolectan por búsqueda si no se indica otro.
+pub const DEFAULT_POSTS: usize = 15;
+
+/// Lee de `ytInitialData` los identificadores de los videos de la búsqueda, sin repetidos.
+const SEARCH_SCRIPT: &str = "(limit) => {
+    const ids = [];
+    const visit = (node) => {
+        if (ids.length >= limit || !node || typeof node !== 'object') {
+            return;
+        }
+        const id = node.videoRenderer && node.videoRenderer.videoId;
+        if (id && !ids.includes(id)) {
+            ids.push(id);
+        }
+        Object.values(node).forEach(visit);
+    };
+    visit(window.ytInitialData);
+    return ids;
+}";
+
+/// Lee las métricas del video abierto tal como las muestra YouTube.
+const VIDEO_SCRIPT: &str = "() => {
+    const player = window.ytInitialPlayerResponse || {};
+    const data = window.ytInitialData || {};
+    const find = (node, key) => {
+        if (!node || typeof node !== 'object') {
+            return undefined;
+        }
+        if (key in node) {
+            return node[key];
+        }
+        for (const value of Object.values(node)) {
+            const found = find(value, key);
+            if (found !== undefined) {
+                return found;
+            }
+        }
+        return undefined;
+    };
+
+    const details = player.videoDetails || {};
+    const microformat = (player.microformat || {}).playerMicroformatRenderer || {};
+    const panel = (data.engagementPanels || []).find((panel) =>
+        (panel.engagementPanelSectionListRenderer || {}).panelIdentifier === 'engagement-panel-comments-section'
+    );
+    const comments = find(panel, 'contextualInfo');
+    const subscribers = find(data, 'subscriberCountText');
+
+    return {
+        title: details.title || '',
+        channel: details.author || '',
+        views: details.viewCount || '',
+        likes: find(data, 'likeCountIfIndifferentNumber') || '',
+        comments: comments && comments.runs ? comments.runs.map((run) => run.text).join('') : '',
+        subscribers: subscribers ? subscribers.simpleText || '' : '',
+        published: microformat.publishDate || microformat.uploadDate || '',
+    };
+}";
+
+/// Métricas de un video tal como las devuelve `VIDEO_SCRIPT`.
+#[derive(Debug, Deserialize)]
+struct VideoDetails {
+    title: String,
+    channel: String,
+    views: String,
+    likes: String,
+    comments: String,
+    subscribers: String,
+    published: String,
+}
+
+/// Video de YouTube con sus métricas de engagement y los suscriptores de su canal.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct YouTubePost {
+    pub channel: String,
+    pub title: String,
+    pub link: String,
+    pub time: String,
+    pub views: u32,
+    pub likes: u32,
+    pub comments: u32,
+    pub subscribers: u32,
+}
+
+impl YouTubePost {
+    /**
+     * Construye el video a partir de las métricas leídas de su página.
+     *
+     * @param link Enlace del video.
+     * @param details Métricas leídas por `VIDEO_SCRIPT`.
+     * @return Video con sus contadores como números.
+     */
+    fn from_details(link: String, details: VideoDetails) -> Self {
+        // La interfaz está en inglés, por lo que la coma solo separa miles, y los
+        // suscriptores traen la unidad después del número (ej. `1.2M subscribers`)
+        let count = |text: &str| {
+            let number = text.split_whitespace().next().unwrap_or_default();
+            Utils::parse_human_number(&number.replace(',', ""))
+        };
+
+        YouTubePost {
+            channel: details.channel,
+            title: Utils::clean_text(&details.title),
+            link,
+            time: Self::parse_publish_date(&details.published),
+            views: count(&details.views),
+            likes: count(&details.likes),
+            comments: count(&details.comments),
+            subscribers: count(&details.subscribers),
+        }
+    }
+
+    /**
+     * Convierte la fecha de publicación de YouTube al formato ISO 8601 en UTC.
+     *
+     * YouTube da la fecha con su zona horaria o, en videos antiguos, solo el día.
+     *
+     * @param published Fecha tal como la da YouTube.
+     * @return Fecha en UTC con milisegundos, vacía si no se pudo leer.
+     */
+    fn parse_publish_date(published: &str) -> String {
+        let time = DateTime::parse_from_rfc3339(published)
+            .map(|time| time.with_timezone(&Utc))
+            .ok()
+            .or_else(|| {
+                NaiveDate::parse_from_str(published, "%Y-%m-%d")
+                    .ok()
+                    .and_then(|date| date.and_hms_opt(0, 0, 0))
+                    .map(|time| time.and_utc())
+            });
+
+        time.map(|time| time.to_rfc3339_opts(SecondsFormat::Millis, true))
+            .unwrap_or_default()
+    }
+}
+
+/// Scraper de búsquedas de YouTube.
+pub struct YouTubeScraper;
+
+impl YouTubeScraper {
+    /**
+     * Construye la URL de la búsqueda de una palabra clave, ordenada por fecha de subida.
+     *
+     * @param query Palabra clave o hashtag sin el símbolo `#`.
+     * @return URL de la búsqueda.
+     */
+    pub fn search_url(query: &str) -> String {
+        Url::parse_with_params(
+            YOUTUBE_SEARCH_URL,
+            &[("search_query", query), ("sp", UPLOAD_DATE_FILTER), ("hl", LANGUAGE)],
+        )
+        .map(String::from)
+        .unwrap_or_default()
+    }
+
+    /**
+     * Construye el enlace de un video.
+     *
+     * @param id Identificador del video.
+     * @return Enlace del video.
+     */
+    pub fn video_url(id: &str) -> String {
+        format!("{}?v={}", YOUTUBE_WATCH_URL, id)
+    }
+
+    /**
+     * Lee los identificadores de los videos de la página de búsqueda abierta en el contexto.
+     *
+     * @param context Contexto del navegador en la página de búsqueda.
+     * @param limit Número máximo de videos a leer.
+     * @return Identificadores de los videos, sin repetidos.
+     */
+    pub fn extract_video_ids(context: &Context, limit: usize) -> zbrowser::Result<Vec<String>> {
+        context.evaluate_json(SEARCH_SCRIPT, (limit,))
+    }
+
+    /**
+     * Lee las métricas del video abierto en el contexto.
+     *
+     * @param context Contexto del navegador en la página del video.
+     * @param link Enlace del video.
+     * @return Video con sus métricas, en 0 las que YouTube no muestra.
+     */
+    pub fn extract_video(context: &Context, link: String) -> zbrowser::Result<YouTubePost> {
+        let details: VideoDetails = context.evaluate_json(VIDEO_SCRIPT, ())?;
+        Ok(YouTubePost::from_details(link, details))
+    }
+
+    /**
+     * Obtiene videos de una búsqueda con el scraper dado.
+     *
+     * Esta función tiene 2 fases:
+     * 1. Leer los identificadores de los videos más recientes de la búsqueda.
+     * 2. Visitar cada video en paralelo para leer sus métricas.
+     *
+     * Si la búsqueda falla o no tiene videos se guarda una captura de la página. Los
+     * videos cuya página no se pudo cargar se omiten.
+     *
+     * @param scraper Scraper que ejecuta la tarea.
+     * @param query Palabra clave o hashtag sin el símbolo `#`.
+     * @param limit Número máximo de videos a recolectar.
+     * @param region Región del usuario con la que se busca, o `None` para la del navegador.
+     * @return Videos encontrados, en el orden de la búsqueda.
+     */
+    pub async fn get_posts_with(
+        scraper: &Scraper,
+        query: String,
+        limit: usize,
+        region: Option<&'static Region>,
+    ) -> anyhow::Result<Vec<YouTubePost>> {
+        let url = Self::search_url(&query);
+
+        // Fase 1: Obtener los videos de la búsqueda
+        let ids = PROXIES
+            .execute(scraper, PLATFORM, move |context| {
+                SNAPSHOTS.watch(PLATFORM, &context, || {
+                    if let Some(region) = region {
+                        region.apply(&context)?;
+                    }
+                    Blocklist::apply(&context, PLATFORM)?;
+                    context.navigate(&url)?;
+                    Utils::ignore_timeout(context.wait_for_network_idle(NETWORK_IDLE_MS, PAGE_TIMEOUT_MS))?;
+                    Self::extract_video_ids(&context, limit)
+                })
+            })
+            .await?;
+
+        // Fase 2: Obtener las métricas de cada video en paralelo
+        let futures = ids.into_iter().map(|id| async move {
+            let link = Self::video_url(&id);
+            let url = link.clone();
+            let video = PROXIES
+                .execute(scraper, PLATFORM, move |context| {
+                    if let Some(region) = region {
+                        region.apply(&context)?;
+                    }
+                    Blocklist::apply(&context, PLATFORM)?;
+                    context.navigate(format!("{}&hl={}", url, LANGUAGE))?;
+                    Self::extract_video(&context, url.clone())
+                })
+                .await;
+
+            match video {
+                Ok(video) => Some(video),
+                Err(e) => {
+                    warn!("Failed to load YouTube video {}: {}", link, e);
+                    None
+                }
+            }
+        });
+
+        Ok(join_all(futures).await.into_iter().flatten().collect())
+    }
+}
+
+impl From<YouTubePost> for SocialPost {
+    /// Los suscriptores del canal cuentan como el alcance del video
+    fn from(post: YouTubePost) -> Self {
+        SocialPost {
+            text: post.title,
+            link: post.link,
+            time: post.time,
+            views: post.views,
+            likes: post.likes,
+            comments: post.comments,
+            shares: 0,
+            followers: post.subscribers,
+            community: None,
+        }
+    }
+}
+
+impl SocialSource for YouTubeScraper {
+    fn name(&self) -> &'static str {
+        PLATFORM
+    }
+
+    fn search(&self, query: String, region: Option<&'static Region>) -> BoxFuture<'static, anyhow::Result<Vec<SocialPost>>> {
+        Box::pin(async move {
+            let posts = Self::get_posts_with(&SCRAPER, query, DEFAULT_POSTS, region).await?;
+            Ok(posts.into_iter().map(SocialPost::from).collect())
+        })
+    }
+}
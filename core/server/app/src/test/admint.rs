@@ -0,0 +1,53 @@
+/**
+ * Pruebas unitarias para las rutas del módulo admin.rs
+ *
+ * Verifican que el estado del scraper (`/admin/scraper`) solo se entregue a
+ * administradores: una solicitud sin token o con el token de un usuario que no
+ * está registrado como administrador se rechaza.
+ *
+ * Proyecto: WhisperTrend
+ */
+
+use actix_web::{http::StatusCode, test, App};
+use auth::TokenService;
+use crate::{
+    config::{Claims, Config},
+    controllers::admin,
+};
+
+/**
+ * Prueba el endpoint GET `/admin/scraper` sin token.
+ *
+ * @assert La respuesta debe ser 401 Unauthorized.
+ */
+#[actix_rt::test]
+async fn test_scraper_metrics_requires_token() {
+    let app = test::init_service(App::new().service(admin::routes())).await;
+
+    let req = test::TestRequest::get().uri("/admin/scraper").to_request();
+
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+}
+
+/**
+ * Prueba el endpoint GET `/admin/scraper` con un token válido que no pertenece
+ * a ningún administrador.
+ *
+ * @param id Identificador que no corresponde a ningún administrador.
+ * @assert La respuesta debe ser 403 Forbidden.
+ */
+#[actix_rt::test]
+async fn test_scraper_metrics_rejects_non_admin() {
+    let app = test::init_service(App::new().service(admin::routes())).await;
+
+    let token = TokenService::<Claims>::create(&Config::get_secret_key(), Claims::new(i32::MAX))
+        .expect("Failed to create token");
+    let req = test::TestRequest::get()
+        .uri("/admin/scraper")
+        .insert_header(("token", token))
+        .to_request();
+
+    let resp = test::call_service(&app, req).await;
+    assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+}
@@ -44,12 +44,15 @@ async fn test_process_analytics_endpoint() {
                         "keyword": "TestTag",
                         "posts": [
                             {
-                                "date": "01/01/25 - 31/01/25",
+                                "text": "Probando #TestTag",
+                                "link": "https://www.instagram.com/p/DFt3sTtAg01/",
+                                "time": "2025-01-20T15:30:00.000Z",
+                                "views": 15000,
                                 "likes": 1200,
                                 "comments": 100,
-                                "views": 15000,
+                                "shares": 80,
                                 "followers": 50000,
-                                "shares": 80
+                                "community": null
                             }
                         ]
                     }
@@ -65,6 +68,71 @@ async fn test_process_analytics_endpoint() {
     assert_eq!(resp.status(), StatusCode::OK);
 }
 
+/**
+ *
+ * Prueba el endpoint POST `/analytics/process` con publicaciones tal como las guardan los scrapers.
+ *
+ * Los scrapers de todas las plataformas guardan `SocialPost`, por lo que las tasas de Instagram
+ * y Twitter deben calcularse a partir de ese modelo.
+ *
+ * @assert Instagram: (1200 + 100) / 15000 * 100 = 8.67 y ln(181) / ln(50001) * 30 = 14.41.
+ * @assert Twitter: (130 + 860 + 45) / 42000 * 100 = 2.46 y ln(1036) / ln(18001) * 20 = 14.17.
+ * @assert Las plataformas sin publicaciones deben tener tasas en 0.
+ */
+#[actix_rt::test]
+async fn test_process_analytics_with_scraped_posts() {
+    let app = test::init_service(
+        App::new().service(analytics::routes()),
+    )
+    .await;
+
+    let req = test::TestRequest::post()
+        .uri("/analytics/process")
+        .set_json(&serde_json::json!({
+            "hashtags": ["TestTag"],
+            "trends": {
+                "instagram": [{
+                    "keyword": "TestTag",
+                    "posts": [{
+                        "text": "Probando #TestTag",
+                        "link": "https://www.instagram.com/p/DFt3sTtAg01/",
+                        "time": "2025-01-20T15:30:00.000Z",
+                        "views": 15000,
+                        "likes": 1200,
+                        "comments": 100,
+                        "shares": 80,
+                        "followers": 50000,
+                        "community": null
+                    }]
+                }],
+                "twitter": [{
+                    "keyword": "TestTag",
+                    "posts": [{
+                        "text": "Hilo sobre #TestTag",
+                        "link": "https://x.com/pruebas_wt/status/1881385912345678901",
+                        "time": "2025-01-20T16:05:00.000Z",
+                        "views": 42000,
+                        "likes": 860,
+                        "comments": 45,
+                        "shares": 130,
+                        "followers": 18000,
+                        "community": null
+                    }]
+                }]
+            },
+            "sales": []
+        }))
+        .to_request();
+
+    let body: serde_json::Value = test::call_and_read_body_json(&app, req).await;
+    let rates = &body["hashtags"][0];
+    assert_eq!(rates["instagram_interaction"], 8.67);
+    assert_eq!(rates["instagram_virality"], 14.41);
+    assert_eq!(rates["twitter_interaction"], 2.46);
+    assert_eq!(rates["twitter_virality"], 14.17);
+    assert_eq!(rates["reddit_interaction"], 0.0);
+}
+
 /**
  *
  * Prueba el endpoint POST `/analytics/test`.
@@ -0,0 +1,55 @@
+/**
+ * Pruebas unitarias para el módulo scraping::blocklist
+ *
+ * Verifican que cada plataforma bloquee su analítica, anuncios y video sin
+ * bloquear las páginas ni las APIs de las que se extraen las publicaciones.
+ * No requieren navegador: solo se prueban las reglas.
+ *
+ * Proyecto: WhisperTrend
+ */
+
+use crate::scraping::blocklist::Blocklist;
+
+/**
+ * Indica si alguna regla de la plataforma bloquea la URL.
+ */
+fn blocks(platform: &str, url: &str) -> bool {
+    Blocklist::rules(platform).iter().any(|rule| rule.matches(url))
+}
+
+/**
+ * Prueba las reglas comunes a todas las plataformas.
+ *
+ * @assert Los dominios de analítica se bloquean junto con sus subdominios.
+ * @assert Las plataformas sin reglas propias conservan las reglas comunes.
+ */
+#[test]
+fn test_blocks_trackers_everywhere() {
+    assert!(blocks("instagram", "https://www.google-analytics.com/g/collect?v=2"));
+    assert!(blocks("reddit", "https://stats.g.doubleclick.net/j/collect"));
+    assert!(blocks("notices", "https://www.googletagmanager.com/gtm.js?id=GTM-1"));
+    assert!(!blocks("notices", "https://www.google.com/search?q=analytics"));
+}
+
+/**
+ * Prueba las reglas propias de cada plataforma.
+ *
+ * @assert Todas las expresiones regulares de las plataformas son válidas.
+ * @assert El video y la telemetría se bloquean.
+ * @assert Las páginas, scripts y APIs de las que se extraen datos siguen cargando.
+ */
+#[test]
+fn test_keeps_pages_and_apis() {
+    assert!(Blocklist::rules("instagram").len() > Blocklist::rules("notices").len() + 3);
+    assert!(Blocklist::rules("twitter").len() > Blocklist::rules("notices").len() + 4);
+
+    assert!(blocks("instagram", "https://scontent.cdninstagram.com/o1/v/t16/f2/m86/clip.mp4?efg=x"));
+    assert!(blocks("twitter", "https://video.twimg.com/ext_tw_video/1/pu/vid/720x1280/a.mp4"));
+    assert!(blocks("twitter", "https://api.x.com/1.1/jot/client_event.json"));
+    assert!(blocks("reddit", "https://v.redd.it/abc/DASH_720.mp4"));
+
+    assert!(!blocks("instagram", "https://www.instagram.com/explore/tags/viajes/"));
+    assert!(!blocks("instagram", "https://static.cdninstagram.com/rsrc.php/v3/app.js"));
+    assert!(!blocks("twitter", "https://x.com/i/api/graphql/abc/SearchTimeline?variables=%7B%7D"));
+    assert!(!blocks("reddit", "https://www.reddit.com/search?q=viajes"));
+}
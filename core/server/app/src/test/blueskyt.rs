@@ -0,0 +1,84 @@
+/**
+ * Pruebas unitarias para el módulo scraping::bluesky
+ *
+ * Las pruebas usan las respuestas grabadas de `searchPosts` y `getProfiles` en
+ * `fixtures/bluesky`, servidas por un servidor HTTP local, por lo que no requieren red.
+ *
+ * Proyecto: WhisperTrend
+ */
+
+use crate::scraping::{bluesky::BlueskyScraper, source::SocialPost};
+use crate::test::standin;
+
+/// Respuestas de la API de Bluesky, de la más específica a la más general
+static ROUTES: &[(&str, &str)] = &[
+    (
+        "/xrpc/app.bsky.feed.searchPosts?q=skincare&sort=latest&cursor=2",
+        include_str!("../../fixtures/bluesky/search_page2.json"),
+    ),
+    (
+        "/xrpc/app.bsky.feed.searchPosts?q=skincare&sort=latest",
+        include_str!("../../fixtures/bluesky/search.json"),
+    ),
+    (
+        "/xrpc/app.bsky.actor.getProfiles",
+        include_str!("../../fixtures/bluesky/profiles.json"),
+    ),
+];
+
+/**
+ *
+ * Prueba `get_posts_with` sobre las respuestas grabadas.
+ *
+ * @assert Se debe pedir la página siguiente con el cursor hasta reunir el límite.
+ * @assert Las métricas, el enlace y la fecha en UTC deben coincidir con la grabación.
+ * @assert Las citas deben contar como reposts.
+ * @assert Los autores sin perfil deben quedar con 0 seguidores.
+ */
+#[actix_web::test]
+async fn test_get_posts_from_stand_in() {
+    let api = standin::serve(ROUTES);
+    let posts = BlueskyScraper::get_posts_with(&api, "skincare".to_string(), 3)
+        .await
+        .expect("Stand-in search should not fail");
+
+    assert_eq!(posts.len(), 3, "Posts should be limited to the requested number");
+    assert_eq!(posts[0].author, "ines-formulates.bsky.social");
+    assert_eq!(
+        posts[0].text,
+        "Reformulated my niacinamide serum at 4% instead of 10% and the #skincare irritation is gone"
+    );
+    assert_eq!(posts[0].link, "https://bsky.app/profile/ines-formulates.bsky.social/post/3lqt6wmx4kc2s");
+    assert_eq!(posts[0].time, "2025-06-05T08:14:37.512Z");
+    assert_eq!(posts[0].likes, 193);
+    assert_eq!(posts[0].reposts, 33);
+    assert_eq!(posts[0].replies, 12);
+    assert_eq!(posts[0].followers, 4_870);
+    assert_eq!(posts[1].time, "2025-06-04T14:52:19.000Z", "Dates should be converted to UTC");
+    assert_eq!(posts[1].followers, 61_300);
+    assert_eq!(posts[2].text, "Patch test results from the weekend, photos in thread #skincare");
+    assert_eq!(posts[2].followers, 4_870, "Authors should keep their followers on every post");
+
+    let posts: Vec<SocialPost> = BlueskyScraper::get_posts_with(&api, "skincare".to_string(), 30)
+        .await
+        .expect("Stand-in search should not fail")
+        .into_iter()
+        .map(SocialPost::from)
+        .collect();
+    assert_eq!(posts.len(), 4, "Search should stop when there is no cursor");
+    assert_eq!(posts[3].followers, 0);
+    assert_eq!((posts[1].likes, posts[1].shares, posts[1].comments), (1_047, 287, 74));
+}
+
+/**
+ *
+ * Prueba una API que responde con error.
+ *
+ * @assert La búsqueda debe fallar en lugar de devolver publicaciones vacías.
+ */
+#[actix_web::test]
+async fn test_get_posts_fails_on_error_status() {
+    let api = standin::serve(&[]);
+    let result = BlueskyScraper::get_posts_with(&api, "skincare".to_string(), 3).await;
+    assert!(result.is_err(), "A failed search should be reported");
+}
@@ -4,6 +4,7 @@
 // Fecha: 04-06-2025
 
 // Este módulo contiene funciones para calcular métricas de viralidad e interacción en redes sociales como X (Twitter), Reddit e Instagram.
+// Las fórmulas de TikTok, YouTube y del modelo común se prueban directamente desde analytics.rs.
 
 pub fn x_viral_rate(reposts: u32, likes: u32, comments: u32, followers: u32) -> f64 {
     if followers == 0 {
@@ -136,9 +137,99 @@ pub fn insta_viral_rate(comments: u32, shares: u32, followers: u32) -> f64 {
         Resultado: 5.0%
 */
 
+/* 
+    TikTok INTERACTION RATE (fórmula de analytics.rs)
+        -- Prueba #20 (test_tiktok_interaction_rate_normal_case)
+        Cálculo: (900 likes + 60 comments + 40 shares) / 10000 views * 100 = 1000 / 10000 * 100 = 0.1 * 100 = 10.0%
+        Resultado: 10.0%
+        
+        -- Prueba #21 (test_tiktok_interaction_rate_zero_views)
+        Cálculo: División por cero (views = 0), función retorna 0.0 por seguridad
+        Resultado: 0.0%
+        
+        -- Prueba #22 (test_tiktok_interaction_rate_capped)
+        Cálculo: (5000 likes + 300 comments + 200 shares) / 1000 views * 100 = 5500 / 1000 * 100 = 550.0%, se limita a 100
+        Resultado: 100.0%
+
+    TikTok VIRAL RATE (fórmula de analytics.rs)
+        -- Prueba #23 (test_tiktok_viral_rate_normal_case)
+        Cálculo: ln(9499 views + 50 shares * 10 + 1) / ln(99 followers + 1) * 25 = ln(10000) / ln(100) * 25 = 2 * 25 = 50.0%
+        Resultado: 50.0%
+        
+        -- Prueba #24 (test_tiktok_viral_rate_zero_followers)
+        Cálculo: División por cero (followers = 0), función retorna 0.0 por seguridad
+        Resultado: 0.0%
+        
+        -- Prueba #25 (test_tiktok_viral_rate_capped)
+        Cálculo: ln(99999 views + 0 shares * 10 + 1) / ln(9 followers + 1) * 25 = ln(100000) / ln(10) * 25 = 5 * 25 = 125.0%, se limita a 100
+        Resultado: 100.0%
+*/
+
+/* 
+    YouTube INTERACTION RATE (fórmula de analytics.rs)
+        -- Prueba #26 (test_youtube_interaction_rate_normal_case)
+        Cálculo: (400 likes + 50 comments * 2) / 10000 views * 100 = 500 / 10000 * 100 = 0.05 * 100 = 5.0%
+        Resultado: 5.0%
+        
+        -- Prueba #27 (test_youtube_interaction_rate_zero_views)
+        Cálculo: División por cero (views = 0), función retorna 0.0 por seguridad
+        Resultado: 0.0%
+        
+        -- Prueba #28 (test_youtube_interaction_rate_comments_count_double)
+        Cálculo: (0 likes + 250 comments * 2) / 1000 views * 100 = 500 / 1000 * 100 = 0.5 * 100 = 50.0%
+        Resultado: 50.0%
+
+    YouTube VIRAL RATE (fórmula de analytics.rs)
+        -- Prueba #29 (test_youtube_viral_rate_normal_case)
+        Cálculo: ln(999999 views + 1) / ln(999 suscriptores + 1) * 30 = ln(1000000) / ln(1000) * 30 = 2 * 30 = 60.0%
+        Resultado: 60.0%
+        
+        -- Prueba #30 (test_youtube_viral_rate_zero_subscribers)
+        Cálculo: División por cero (suscriptores = 0), función retorna 0.0 por seguridad
+        Resultado: 0.0%
+        
+        -- Prueba #31 (test_youtube_viral_rate_large_channel)
+        Cálculo: ln(999 views + 1) / ln(999999 suscriptores + 1) * 30 = ln(1000) / ln(1000000) * 30 = 0.5 * 30 = 15.0%
+        Resultado: 15.0%
+*/
+
+/* 
+    SOCIAL INTERACTION RATE (plataformas sin fórmulas propias, fórmula de analytics.rs)
+        -- Prueba #32 (test_social_interaction_rate_normal_case)
+        Cálculo: (500 likes + 60 comments + 40 shares) / 12000 followers * 100 = 600 / 12000 * 100 = 0.05 * 100 = 5.0%
+        Resultado: 5.0%
+        
+        -- Prueba #33 (test_social_interaction_rate_zero_followers)
+        Cálculo: División por cero (followers = 0), función retorna 0.0 por seguridad
+        Resultado: 0.0%
+        
+        -- Prueba #34 (test_social_interaction_rate_capped)
+        Cálculo: (300 likes + 90 comments + 60 shares) / 150 followers * 100 = 450 / 150 * 100 = 300.0%, se limita a 100
+        Resultado: 100.0%
+*/
+
+/* 
+    SOCIAL VIRAL RATE (plataformas sin fórmulas propias, fórmula de analytics.rs)
+        -- Prueba #35 (test_social_viral_rate_normal_case)
+        Cálculo: ln(900 comments + 99 shares + 1) / ln(999999 followers + 1) * 30 = ln(1000) / ln(1000000) * 30 = 0.5 * 30 = 15.0%
+        Resultado: 15.0%
+        
+        -- Prueba #36 (test_social_viral_rate_zero_followers)
+        Cálculo: División por cero (followers = 0), función retorna 0.0 por seguridad
+        Resultado: 0.0%
+        
+        -- Prueba #37 (test_social_viral_rate_only_shares)
+        Cálculo: ln(0 comments + 999 shares + 1) / ln(999999 followers + 1) * 30 = ln(1000) / ln(1000000) * 30 = 0.5 * 30 = 15.0%
+        Resultado: 15.0%
+*/
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::nosql::controllers::analytics::{
+        social_interaction_rate, social_viral_rate, tiktok_interaction_rate, tiktok_viral_rate, youtube_interaction_rate,
+        youtube_viral_rate,
+    };
     
     // ===================== X (TWITTER) VIRAL RATE TESTS =====================
     
@@ -284,4 +375,142 @@ mod tests {
         let result = insta_viral_rate(0, 25, 500);
         assert_eq!(result, 5.0);
     }
+
+    // ===================== TIKTOK INTERACTION RATE TESTS =====================
+
+    //Prueba #20
+    #[test]
+    fn test_tiktok_interaction_rate_normal_case() {
+        let result = tiktok_interaction_rate(900, 60, 40, 10000);
+        assert_eq!(result, 10.0);
+    }
+
+    //Prueba #21
+    #[test]
+    fn test_tiktok_interaction_rate_zero_views() {
+        let result = tiktok_interaction_rate(900, 60, 40, 0);
+        assert_eq!(result, 0.0);
+    }
+
+    //Prueba #22
+    #[test]
+    fn test_tiktok_interaction_rate_capped() {
+        let result = tiktok_interaction_rate(5000, 300, 200, 1000);
+        assert_eq!(result, 100.0);
+    }
+
+    // ===================== TIKTOK VIRAL RATE TESTS =====================
+
+    //Prueba #23
+    #[test]
+    fn test_tiktok_viral_rate_normal_case() {
+        let result = tiktok_viral_rate(9499, 50, 99);
+        assert_eq!(result, 50.0);
+    }
+
+    //Prueba #24
+    #[test]
+    fn test_tiktok_viral_rate_zero_followers() {
+        let result = tiktok_viral_rate(9499, 50, 0);
+        assert_eq!(result, 0.0);
+    }
+
+    //Prueba #25
+    #[test]
+    fn test_tiktok_viral_rate_capped() {
+        let result = tiktok_viral_rate(99999, 0, 9);
+        assert_eq!(result, 100.0);
+    }
+
+    // ===================== YOUTUBE INTERACTION RATE TESTS =====================
+
+    //Prueba #26
+    #[test]
+    fn test_youtube_interaction_rate_normal_case() {
+        let result = youtube_interaction_rate(400, 50, 10000);
+        assert_eq!(result, 5.0);
+    }
+
+    //Prueba #27
+    #[test]
+    fn test_youtube_interaction_rate_zero_views() {
+        let result = youtube_interaction_rate(400, 50, 0);
+        assert_eq!(result, 0.0);
+    }
+
+    //Prueba #28
+    #[test]
+    fn test_youtube_interaction_rate_comments_count_double() {
+        let result = youtube_interaction_rate(0, 250, 1000);
+        assert_eq!(result, 50.0);
+    }
+
+    // ===================== YOUTUBE VIRAL RATE TESTS =====================
+
+    //Prueba #29
+    #[test]
+    fn test_youtube_viral_rate_normal_case() {
+        let result = youtube_viral_rate(999999, 999);
+        assert_eq!(result, 60.0);
+    }
+
+    //Prueba #30
+    #[test]
+    fn test_youtube_viral_rate_zero_subscribers() {
+        let result = youtube_viral_rate(999999, 0);
+        assert_eq!(result, 0.0);
+    }
+
+    //Prueba #31
+    #[test]
+    fn test_youtube_viral_rate_large_channel() {
+        let result = youtube_viral_rate(999, 999999);
+        assert_eq!(result, 15.0);
+    }
+
+    // ===================== SOCIAL INTERACTION RATE TESTS =====================
+
+    //Prueba #32
+    #[test]
+    fn test_social_interaction_rate_normal_case() {
+        let result = social_interaction_rate(500, 60, 40, 12000);
+        assert_eq!(result, 5.0);
+    }
+
+    //Prueba #33
+    #[test]
+    fn test_social_interaction_rate_zero_followers() {
+        let result = social_interaction_rate(500, 60, 40, 0);
+        assert_eq!(result, 0.0);
+    }
+
+    //Prueba #34
+    #[test]
+    fn test_social_interaction_rate_capped() {
+        let result = social_interaction_rate(300, 90, 60, 150);
+        assert_eq!(result, 100.0);
+    }
+
+    // ===================== SOCIAL VIRAL RATE TESTS =====================
+
+    //Prueba #35
+    #[test]
+    fn test_social_viral_rate_normal_case() {
+        let result = social_viral_rate(900, 99, 999999);
+        assert_eq!(result, 15.0);
+    }
+
+    //Prueba #36
+    #[test]
+    fn test_social_viral_rate_zero_followers() {
+        let result = social_viral_rate(900, 99, 0);
+        assert_eq!(result, 0.0);
+    }
+
+    //Prueba #37
+    #[test]
+    fn test_social_viral_rate_only_shares() {
+        let result = social_viral_rate(0, 999, 999999);
+        assert_eq!(result, 15.0);
+    }
 }
\ No newline at end of file
@@ -10,8 +10,10 @@
  * Proyecto: WhisperTrend
  */
 
-use crate::scraping::instagram::InstagramScraper;
+use crate::scraping::{Utils, instagram::InstagramScraper};
+use crate::test::fixtures;
 use tokio::time::{timeout, Duration};
+use zbrowser::Scraper;
 
 /**
  *
@@ -39,3 +41,40 @@ async fn test_instagram_get_posts_from_hashtag() {
     let post = &posts[0];
     assert!(post.likes > 0 || post.comments > 0 || post.followers > 0, "Post seems empty");
 }
+
+/**
+ *
+ * Prueba los scripts de extracción de Instagram sobre las páginas grabadas en
+ * `fixtures/instagram`, sin Chrome, red ni sesión.
+ *
+ * @assert Las publicaciones del hashtag deben conservar likes, comentarios y enlace.
+ * @assert La publicación debe devolver su fecha y el enlace al perfil del autor.
+ * @assert Los seguidores deben leerse del perfil y convertirse a número.
+ */
+#[tokio::test]
+async fn test_instagram_extraction_from_fixtures() {
+    let scraper = Scraper::replay(fixtures("instagram")).expect("Fixtures should load");
+
+    let (posts, post, followers) = scraper
+        .execute(|context| {
+            context.navigate("https://www.instagram.com/explore/tags/nature/")?;
+            let posts = InstagramScraper::collect_posts(&context, 10)?;
+
+            context.navigate(&posts[0].link)?;
+            let post = InstagramScraper::extract_time_and_link(&context)?;
+
+            context.navigate(&post.link)?;
+            let followers = InstagramScraper::extract_followers(&context)?;
+
+            Ok((posts, post, followers))
+        })
+        .await
+        .expect("Replayed pages should not fail");
+
+    assert_eq!(posts.len(), 2);
+    assert_eq!(posts[0].likes, 1520);
+    assert_eq!(posts[0].comments, 34);
+    assert_eq!(post.time, "2025-06-02T18:25:43.000Z");
+    assert_eq!(post.link, "https://www.instagram.com/wildlife.daily/");
+    assert_eq!(Utils::parse_human_number(&followers), 12_500);
+}
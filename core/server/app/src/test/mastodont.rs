@@ -0,0 +1,84 @@
+/**
+ * Pruebas unitarias para el módulo scraping::mastodon
+ *
+ * Las pruebas usan la línea de tiempo grabada del hashtag `rust` en `fixtures/mastodon`,
+ * servida por un servidor HTTP local, por lo que no requieren red.
+ *
+ * Proyecto: WhisperTrend
+ */
+
+use crate::scraping::{mastodon::MastodonScraper, source::SocialPost};
+use crate::test::standin;
+
+/// Respuestas de la instancia de Mastodon
+static ROUTES: &[(&str, &str)] = &[(
+    "/api/v1/timelines/tag/rust",
+    include_str!("../../fixtures/mastodon/tag.json"),
+)];
+
+/**
+ *
+ * Prueba la conversión de consultas y contenido de Mastodon.
+ *
+ * @assert Las consultas deben convertirse en hashtags sin espacios ni símbolos.
+ * @assert El HTML de una publicación debe convertirse en texto con sus párrafos separados.
+ */
+#[test]
+fn test_hashtag_and_plain_text() {
+    assert_eq!(MastodonScraper::hashtag("#machine learning"), "machinelearning");
+    assert_eq!(MastodonScraper::hashtag("dev_ops"), "dev_ops");
+    assert_eq!(MastodonScraper::hashtag("¿?"), "");
+    assert_eq!(
+        MastodonScraper::plain_text("<p>Hello <a href=\"#\">#<span>Rust</span></a></p><p>Bye<br />now &amp; then</p>"),
+        "Hello #Rust Bye now & then"
+    );
+}
+
+/**
+ *
+ * Prueba `get_posts_with` sobre la línea de tiempo grabada.
+ *
+ * @assert Las métricas, el autor, el texto y la fecha deben coincidir con la grabación.
+ * @assert Las publicaciones federadas sin `url` deben enlazar a su URI de origen.
+ * @assert Los favoritos deben ser likes y los impulsos veces compartido en el modelo común.
+ */
+#[actix_web::test]
+async fn test_get_posts_from_stand_in() {
+    let instance = standin::serve(ROUTES);
+    let posts = MastodonScraper::get_posts_with(&instance, "rust".to_string(), 30)
+        .await
+        .expect("Stand-in timeline should not fail");
+
+    assert_eq!(posts.len(), 2, "A page shorter than the limit should be the last one");
+    assert_eq!(posts[0].author, "rustlang");
+    assert_eq!(posts[0].text, "Announcing #Rust 1.87.0 Read more on the blog");
+    assert_eq!(posts[0].link, "https://mastodon.social/@rustlang/114512728424489873");
+    assert_eq!(posts[0].time, "2025-05-15T16:12:48.000Z");
+    assert_eq!((posts[0].favourites, posts[0].reblogs, posts[0].replies), (980, 410, 37));
+    assert_eq!(posts[0].followers, 52_000);
+    assert_eq!(posts[1].author, "ferris@hachyderm.io");
+    assert_eq!(posts[1].link, "https://hachyderm.io/users/ferris/statuses/114512619773830144");
+
+    let post = SocialPost::from(posts.into_iter().next().unwrap());
+    assert_eq!((post.likes, post.shares, post.comments), (980, 410, 37));
+}
+
+/**
+ *
+ * Prueba hashtags sin publicaciones o inválidos.
+ *
+ * @assert Un hashtag inválido no debe hacer peticiones y debe devolver una lista vacía.
+ * @assert Una instancia que responde con error debe fallar.
+ */
+#[actix_web::test]
+async fn test_get_posts_without_timeline() {
+    let instance = standin::serve(ROUTES);
+
+    let posts = MastodonScraper::get_posts_with(&instance, "¿?".to_string(), 30)
+        .await
+        .expect("Invalid hashtags should not fail");
+    assert!(posts.is_empty());
+
+    let result = MastodonScraper::get_posts_with(&instance, "unknown".to_string(), 30).await;
+    assert!(result.is_err(), "A failed timeline should be reported");
+}
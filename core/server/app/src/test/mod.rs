@@ -1,10 +1,21 @@
+pub mod admint;
+pub mod blocklistt;
+pub mod blueskyt;
 pub mod formula;
 pub mod instagramt;
+pub mod mastodont;
 pub mod noticest;
+pub mod proxyt;
 pub mod redditt;
+pub mod regiont;
+pub mod snapshotst;
+pub mod tiktokt;
+pub mod sourcet;
+pub mod standin;
 pub mod trendst;
 
 pub mod twittert;
+pub mod youtubet;
 
 pub mod analysist;
 
@@ -12,3 +23,12 @@ pub mod flowt;
 
 pub mod analyticst;
 
+/**
+ * Ruta del directorio con las páginas grabadas de una plataforma, usado por las pruebas sin red.
+ *
+ * @param platform Nombre del directorio dentro de `fixtures` (ej. `reddit`).
+ * @return Ruta absoluta del directorio.
+ */
+pub fn fixtures(platform: &str) -> String {
+    format!("{}/fixtures/{}", env!("CARGO_MANIFEST_DIR"), platform)
+}
@@ -9,6 +9,7 @@
 use actix_web::{test, web, App};
 use serde_json::json;
 use crate::controllers::web::{get_notices, get_details, Query};
+use crate::scraping::notices::{Info, NoticesScraper};
 
 #[actix_web::test]
 async fn test_get_notices_returns_valid_response() {
@@ -62,4 +63,56 @@ async fn test_get_details_returns_valid_response() {
     let body = test::read_body(resp).await;
     let body_str = std::str::from_utf8(&body).unwrap();
     assert!(body_str.contains("title"));
-}
\ No newline at end of file
+}
+
+// Pruebas sin red sobre las respuestas grabadas en fixtures/notices
+
+#[actix_web::test]
+async fn test_parse_articles_from_fixture() {
+    let body = include_str!("../../fixtures/notices/gdelt.json");
+    let articles = NoticesScraper::parse_articles(body);
+
+    assert_eq!(articles.len(), 2);
+    assert_eq!(articles[0].domain, "example-news.com");
+    assert_eq!(articles[1].sourcecountry, "United States");
+
+    assert!(NoticesScraper::parse_articles("").is_empty());
+    assert!(NoticesScraper::parse_articles("not json").is_empty());
+}
+
+#[actix_web::test]
+async fn test_parse_info_from_fixture() {
+    let url = "https://www.example-news.com/2024/06/renewable-energy-mexico".to_string();
+    let body = include_str!("../../fixtures/notices/article.html");
+    let info = NoticesScraper::parse_info(url.clone(), body).expect("Article should have details");
+
+    assert_eq!(info.title, "Mexico expands renewable energy capacity");
+    assert_eq!(info.url, url);
+    assert_eq!(info.description, "New solar and wind farms add 3 GW to the national grid.");
+    assert_eq!(info.keywords, vec!["renewable energy", "Mexico", "solar power plants", "wind"]);
+
+    // Pages without keywords are discarded
+    assert!(NoticesScraper::parse_info(url, "<html><h1>Title</h1></html>").is_none());
+}
+
+#[actix_web::test]
+async fn test_select_details_ranks_and_formats_hashtags() {
+    let info = |title: &str, keywords: &[&str]| Info {
+        title: title.to_string(),
+        keywords: keywords.iter().map(|k| k.to_string()).collect(),
+        ..Default::default()
+    };
+
+    let details = NoticesScraper::select_details(vec![
+        info("one", &["mexico"]),
+        info("four", &["renewable energy", "Mexico", "solar power plants", "wind"]),
+        info("three", &["climate change", "latin america", "energy"]),
+        info("two", &["tourism", "record numbers"]),
+    ]);
+
+    let titles: Vec<&str> = details.iter().map(|d| d.title.as_str()).collect();
+    assert_eq!(titles, vec!["four", "three", "two"]);
+    assert_eq!(details[0].keywords, vec!["Mexico"]);
+    assert_eq!(details[1].keywords, vec!["Energy"]);
+    assert_eq!(details[2].keywords, vec!["Tourism"]);
+}
@@ -0,0 +1,73 @@
+/**
+ * Pruebas unitarias para el módulo scraping::proxy
+ *
+ * Verifican que el pool de proxies asigne un proxy distinto a cada plataforma,
+ * rote al siguiente cuando uno falla y aparte los proxies que fallan seguido.
+ * No requieren red: solo se prueba la lógica de asignación.
+ *
+ * Proyecto: WhisperTrend
+ */
+
+use crate::scraping::proxy::ProxyPool;
+use zbrowser::Proxy;
+
+fn pool() -> ProxyPool {
+    ProxyPool::from_urls(&[
+        "http://10.0.0.1:8080".to_string(),
+        "socks5://10.0.0.2:1080".to_string(),
+        "not a proxy".to_string(),
+    ])
+}
+
+/**
+ * Prueba la asignación de proxies por plataforma.
+ *
+ * @assert Cada plataforma recibe un proxy distinto mientras haya proxies libres.
+ * @assert Una plataforma conserva su proxy entre tareas.
+ * @assert Las URLs inválidas se ignoran.
+ */
+#[test]
+fn test_assigns_one_proxy_per_platform() {
+    let pool = pool();
+
+    let instagram = pool.get("instagram").expect("Pool should have proxies");
+    let twitter = pool.get("twitter").expect("Pool should have proxies");
+
+    assert_eq!(instagram, Proxy::http("10.0.0.1", 8080));
+    assert_eq!(twitter, Proxy::socks5("10.0.0.2", 1080));
+    assert_eq!(pool.get("instagram"), Some(instagram));
+}
+
+/**
+ * Prueba la rotación de proxies ante fallos.
+ *
+ * @assert Tras un fallo la plataforma pasa al siguiente proxy.
+ * @assert Un proxy que falla `MAX_FAILURES` veces seguidas deja de asignarse.
+ */
+#[test]
+fn test_rotates_failed_proxies() {
+    let pool = pool();
+    let first = pool.get("reddit").unwrap();
+
+    pool.mark_failed("reddit", &first);
+    let second = pool.get("reddit").unwrap();
+    assert_ne!(first, second, "A failed proxy should be rotated");
+
+    for _ in 0..3 {
+        pool.mark_failed("reddit", &second);
+    }
+    for platform in ["reddit", "instagram", "twitter"] {
+        assert_eq!(pool.get(platform), Some(first.clone()), "Cooled down proxy should not be assigned");
+    }
+}
+
+/**
+ * Prueba el pool vacío.
+ *
+ * @assert Sin proxies configurados el tráfico sale sin proxy.
+ */
+#[test]
+fn test_empty_pool_uses_no_proxy() {
+    let pool = ProxyPool::new(Vec::new());
+    assert_eq!(pool.get("instagram"), None);
+}
@@ -6,12 +6,42 @@
  * - `get_simple_posts_by_keyword`: retorna posts simples dados unos keywords.
  * - `get_simple_posts_with_members`: retorna posts con información de miembros del subreddit.
  *
+ * Las pruebas `*_from_fixtures` usan páginas grabadas en `fixtures/reddit` mediante
+ * el backend de replay de zbrowser, y las pruebas `*_from_stand_in` las respuestas de
+ * `search.json` y `about.json` grabadas en `fixtures/reddit/api`, servidas por un
+ * servidor HTTP local, por lo que no requieren Chrome ni red.
+ *
  * Tester: Santiago Villazón Ponce de León
  * Fecha: 05-06-2025
  * Proyecto: WhisperTrend
  */
 
-use crate::scraping::reddit::{RedditScraper, SimplePost, SimplePostWithMembers};
+use crate::nosql::controllers::analytics::RedditPost;
+use crate::scraping::{
+    reddit::{MEMBERS, RedditScraper, SimplePost, SimplePostWithMembers},
+    source::SocialPost,
+};
+use crate::test::{fixtures, standin};
+use chrono::{Duration, SecondsFormat, Utc};
+use zbrowser::Scraper;
+
+/// Respuestas de los endpoints JSON de Reddit, de la más específica a la más general
+static ROUTES: &[(&str, &str)] = &[
+    ("/search.json?q=rust&after=t3_1l0b2c3", include_str!("../../fixtures/reddit/api/search_page2.json")),
+    ("/search.json?q=rust", include_str!("../../fixtures/reddit/api/search.json")),
+    ("/r/learnrust/about.json", include_str!("../../fixtures/reddit/api/about_learnrust.json")),
+];
+
+/// Respuesta de `about.json` de un subreddit que solo consulta la prueba de expiración
+static ABOUT_ROUTES: &[(&str, &str)] = &[
+    ("/r/rustjerk/about.json", include_str!("../../fixtures/reddit/api/about_learnrust.json")),
+];
+
+/// Respuestas de `search.json` sin `about.json`, para probar la caché de miembros
+static SEARCH_ROUTES: &[(&str, &str)] = &[
+    ("/search.json?q=rust&after=t3_1l0b2c3", include_str!("../../fixtures/reddit/api/search_page2.json")),
+    ("/search.json?q=rust", include_str!("../../fixtures/reddit/api/search.json")),
+];
 
 /**
  *
@@ -27,7 +57,9 @@ use crate::scraping::reddit::{RedditScraper, SimplePost, SimplePostWithMembers};
  */
 #[tokio::test]
 async fn test_get_simple_posts_by_keyword_returns_data() {
-    let posts = RedditScraper::get_simple_posts_by_keyword("rust".to_string()).await;
+    let posts = RedditScraper::get_simple_posts_by_keyword("rust".to_string())
+        .await
+        .expect("Reddit scraping should not fail");
     assert!(!posts.is_empty(), "Expected posts from subreddit search");
     
     let post = &posts[0];
@@ -50,7 +82,9 @@ async fn test_get_simple_posts_by_keyword_returns_data() {
  */
 #[tokio::test]
 async fn test_get_simple_posts_with_members_returns_data() {
-    let posts = RedditScraper::get_simple_posts_with_members("technology".to_string()).await;
+    let posts = RedditScraper::get_simple_posts_with_members("technology".to_string())
+        .await
+        .expect("Reddit scraping should not fail");
     assert!(!posts.is_empty(), "Expected posts from subreddit with members");
 
     let post = &posts[0];
@@ -58,3 +92,194 @@ async fn test_get_simple_posts_with_members_returns_data() {
     assert!(post.vote > 0 || post.comments > 0 || post.members > 0, "Post should have engagement or audience");
     assert!(post.subreddit.contains("reddit.com"), "Subreddit should be a valid link");
 }
+
+/**
+ *
+ * Prueba `get_simple_posts_by_keyword_with` sobre la búsqueda grabada de `"rust"`.
+ *
+ * La página contiene dos posts completos y un elemento promocionado sin métricas,
+ * que debe descartarse. El límite de posts también debe respetarse.
+ *
+ * @assert Se deben extraer exactamente los dos posts completos.
+ * @assert Con límite 1 solo se debe devolver un post.
+ * @assert Títulos, enlaces, votos, comentarios y subreddit deben coincidir con la página grabada.
+ */
+#[tokio::test]
+async fn test_get_simple_posts_by_keyword_from_fixtures() {
+    let scraper = Scraper::replay(fixtures("reddit")).expect("Fixtures should load");
+    let posts = RedditScraper::get_simple_posts_by_keyword_with(&scraper, "rust".to_string(), 25, None)
+        .await
+        .expect("Replayed search should not fail");
+
+    assert_eq!(posts.len(), 2, "Promoted element should be skipped");
+    assert_eq!(posts[0].title, "Announcing Rust 1.87.0");
+    assert_eq!(posts[0].vote, 1200);
+    assert_eq!(posts[0].comments, 245);
+    assert_eq!(posts[0].subreddit, "https://www.reddit.com/r/rust/");
+    assert_eq!(posts[0].link, "https://www.reddit.com/r/rust/comments/1kz0a1b/");
+    assert_eq!(posts[1].title, "How do lifetimes work with async closures?");
+
+    let limited = RedditScraper::get_simple_posts_by_keyword_with(&scraper, "rust".to_string(), 1, None)
+        .await
+        .expect("Replayed search should not fail");
+    assert_eq!(limited.len(), 1, "Posts should be limited to the requested number");
+}
+
+/**
+ *
+ * Prueba `get_simple_posts_with_members_with` con las páginas grabadas.
+ *
+ * Solo existe la página grabada de r/rust, así que el post de r/learnrust debe
+ * omitirse al no poder cargarse su subreddit.
+ *
+ * @assert Solo debe quedar el post de r/rust.
+ * @assert El número de miembros debe leerse de la página del subreddit.
+ */
+#[tokio::test]
+async fn test_get_simple_posts_with_members_from_fixtures() {
+    let scraper = Scraper::replay(fixtures("reddit")).expect("Fixtures should load");
+    let posts = RedditScraper::get_simple_posts_with_members_with(&scraper, "rust".to_string(), 25, None)
+        .await
+        .expect("Replayed search should not fail");
+
+    assert_eq!(posts.len(), 1, "Posts without subreddit page should be skipped");
+    assert_eq!(posts[0].subreddit, "https://www.reddit.com/r/rust/");
+    assert_eq!(posts[0].members, 362_000);
+}
+
+/**
+ *
+ * Prueba la URL de búsqueda del navegador.
+ *
+ * @assert La palabra clave debe ir codificada en el parámetro `q`.
+ */
+#[test]
+fn test_search_url() {
+    assert_eq!(RedditScraper::search_url("rust"), "https://www.reddit.com/search?q=rust");
+    assert_eq!(
+        RedditScraper::search_url("rust & c++ #async"),
+        "https://www.reddit.com/search?q=rust+%26+c%2B%2B+%23async"
+    );
+}
+
+/**
+ *
+ * Prueba `search_json` sobre las respuestas grabadas de `search.json`.
+ *
+ * @assert Se deben leer las dos páginas con el cursor `after` y omitir lo que no sea un post.
+ * @assert La fecha debe ser la de creación del post en UTC.
+ * @assert Los votos negativos deben quedar en 0.
+ * @assert Con límite 1 solo se debe devolver un post.
+ */
+#[actix_web::test]
+async fn test_search_json_from_stand_in() {
+    let api = standin::serve(ROUTES);
+    let posts = RedditScraper::search_json(&api, "rust", 25)
+        .await
+        .expect("Stand-in search should not fail");
+
+    assert_eq!(posts.len(), 3, "Subreddit results should be skipped");
+    assert_eq!(posts[0].title, "Announcing Rust 1.87.0");
+    assert_eq!(posts[0].link, "https://www.reddit.com/r/rust/comments/1kz0a1b/");
+    assert_eq!(posts[0].subreddit, "https://www.reddit.com/r/rust/");
+    assert_eq!((posts[0].vote, posts[0].comments), (1234, 245));
+    assert_eq!(serde_json::to_value(&posts[0]).unwrap()["time"], "2025-05-30T14:12:08.000Z");
+    assert_eq!(posts[1].title, "How do lifetimes work with async closures?");
+    assert_eq!(posts[2].vote, 0, "Negative scores should count as no votes");
+
+    let limited = RedditScraper::search_json(&api, "rust", 1)
+        .await
+        .expect("Stand-in search should not fail");
+    assert_eq!(limited.len(), 1, "Posts should be limited to the requested number");
+}
+
+/**
+ *
+ * Prueba `get_simple_posts_with_members_json` y la caché de miembros.
+ *
+ * @assert Los miembros deben tomarse del listado o, si no vienen, de `about.json`.
+ * @assert Una segunda búsqueda debe usar los miembros en caché sin pedir `about.json`.
+ */
+#[actix_web::test]
+async fn test_get_simple_posts_with_members_json_from_stand_in() {
+    let api = standin::serve(ROUTES);
+    let posts = RedditScraper::get_simple_posts_with_members_json(&api, "rust", 25)
+        .await
+        .expect("Stand-in search should not fail");
+
+    assert_eq!(posts.len(), 3);
+    assert_eq!(posts[0].members, 362_000);
+    assert_eq!(posts[1].subreddit, "https://www.reddit.com/r/learnrust/");
+    assert_eq!(posts[1].members, 48_500);
+
+    let api = standin::serve(SEARCH_ROUTES);
+    let posts = RedditScraper::get_simple_posts_with_members_json(&api, "rust", 25)
+        .await
+        .expect("Stand-in search should not fail");
+    assert_eq!(posts.len(), 3, "Cached subreddits should not be looked up again");
+    assert_eq!(posts[1].members, 48_500);
+}
+
+/**
+ *
+ * Prueba la expiración de la caché de miembros.
+ *
+ * @assert Un número de miembros expirado se debe volver a pedir a `about.json`.
+ * @assert Al guardar un número de miembros se deben descartar los expirados.
+ */
+#[actix_web::test]
+async fn test_members_cache_evicts_expired_counts() {
+    let expired = Utc::now() - Duration::hours(1);
+    MEMBERS.insert("rustjerk".to_string(), (1, expired));
+    MEMBERS.insert("expired_members_test".to_string(), (7, expired));
+
+    let api = standin::serve(ABOUT_ROUTES);
+    let members = RedditScraper::get_members_json(&api, "rustjerk")
+        .await
+        .expect("Stand-in about.json should not fail");
+
+    assert_eq!(members, 48_500, "Expired counts should be looked up again");
+    assert_eq!(MEMBERS.get("rustjerk").map(|entry| entry.0), Some(48_500));
+    assert!(!MEMBERS.contains_key("expired_members_test"), "Expired counts should be evicted");
+}
+
+/**
+ *
+ * Prueba que `search_with` recurra al navegador cuando Reddit rechaza los endpoints JSON.
+ *
+ * @assert Los posts deben obtenerse de las páginas grabadas del navegador.
+ */
+#[actix_web::test]
+async fn test_search_with_falls_back_to_browser() {
+    let api = standin::serve(&[]);
+    let scraper = Scraper::replay(fixtures("reddit")).expect("Fixtures should load");
+    let posts = RedditScraper::search_with(&api, &scraper, "rust".to_string(), 25, None)
+        .await
+        .expect("Browser fallback should not fail");
+
+    assert_eq!(posts.len(), 1);
+    assert_eq!(posts[0].members, 362_000);
+}
+
+/**
+ *
+ * Prueba el cálculo de las horas desde la publicación para `reddit_hourly_ratio`.
+ *
+ * @assert Un post scrapeado hace 5 horas debe tener 5 horas desde su publicación.
+ * @assert Un post sin fecha debe tener 0 horas.
+ */
+#[test]
+fn test_reddit_post_hours_from_time() {
+    let time = (Utc::now() - Duration::hours(5)).to_rfc3339_opts(SecondsFormat::Millis, true);
+    let post = RedditPost::from(SocialPost {
+        time,
+        likes: 120,
+        comments: 14,
+        followers: 48_500,
+        ..SocialPost::default()
+    });
+    assert!((post.hours - 5.0).abs() < 0.01, "Hours were {}", post.hours);
+    assert_eq!((post.upvotes, post.comments, post.subscribers), (120, 14, 48_500));
+
+    assert_eq!(RedditPost::from(SocialPost::default()).hours, 0.0);
+}
@@ -0,0 +1,56 @@
+/**
+ * Pruebas unitarias para el módulo scraping::region
+ *
+ * Verifican que las ubicaciones registradas por el usuario se traduzcan a la
+ * región correcta y que la emulación resultante tenga su idioma, zona horaria
+ * y coordenadas. No requieren navegador.
+ *
+ * Proyecto: WhisperTrend
+ */
+
+use crate::scraping::region::Region;
+
+/**
+ * Prueba la búsqueda de la región a partir de las ubicaciones.
+ *
+ * @assert Gana la primera ubicación conocida en el orden del usuario.
+ * @assert No se distinguen mayúsculas ni acentos.
+ * @assert Las palabras cortas solo coinciden completas ("us" no coincide con "Austin").
+ * @assert Ubicaciones desconocidas no devuelven región.
+ */
+#[test]
+fn test_from_locations() {
+    let region = Region::from_locations("Austin, Dallas, Houston").expect("Austin should be known");
+    assert_eq!(region.name, "Texas");
+    assert_eq!(region.timezone, "America/Chicago");
+
+    let region = Region::from_locations("Springfield; QUERÉTARO, Madrid").expect("Querétaro should be known");
+    assert_eq!(region.name, "Querétaro");
+
+    let region = Region::from_locations("Nuevo León").expect("Nuevo León should be known");
+    assert_eq!(region.timezone, "America/Monterrey");
+
+    assert_eq!(Region::from_locations("Springfield, Shelbyville"), None);
+    assert_eq!(Region::from_locations(""), None);
+}
+
+/**
+ * Prueba la emulación generada para una región.
+ *
+ * @assert El encabezado `Accept-Language` incluye el locale y su idioma base.
+ * @assert La emulación lleva el locale, la zona horaria y las coordenadas.
+ */
+#[test]
+fn test_region_emulation() {
+    let region = Region::from_locations("CDMX").unwrap();
+    assert_eq!(region.accept_language(), "es-MX,es;q=0.9");
+
+    let emulation = region.emulation();
+    assert_eq!(emulation.locale.as_deref(), Some("es-MX"));
+    assert_eq!(emulation.timezone.as_deref(), Some("America/Mexico_City"));
+    assert_eq!(emulation.accept_language.as_deref(), Some("es-MX,es;q=0.9"));
+
+    let geolocation = emulation.geolocation.expect("Region should set a position");
+    assert_eq!(geolocation.latitude, region.latitude);
+    assert_eq!(geolocation.longitude, region.longitude);
+}
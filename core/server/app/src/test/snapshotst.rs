@@ -0,0 +1,131 @@
+/**
+ * Pruebas unitarias para el módulo scraping::snapshots
+ *
+ * Verifican que la política de capturas guarde la página cuando una extracción
+ * falla o no encuentra resultados, y que no guarde nada cuando sí los encuentra.
+ * Usan las páginas grabadas en `fixtures/reddit` mediante el backend de replay
+ * de zbrowser, por lo que no requieren Chrome ni red.
+ *
+ * Proyecto: WhisperTrend
+ */
+
+use crate::scraping::snapshots::FailureSnapshots;
+use crate::test::fixtures;
+use std::{fs, sync::Arc};
+use zbrowser::{Error, Scraper};
+
+/**
+ * Prueba que se guarden capturas de las extracciones vacías o fallidas.
+ *
+ * @assert Una extracción con resultados no guarda nada.
+ * @assert Una extracción vacía guarda la captura, el HTML y el reporte en la carpeta de la ejecución.
+ * @assert Una extracción fallida se captura y devuelve su error sin cambios.
+ */
+#[tokio::test]
+async fn test_saves_snapshots_of_failed_scrapes() {
+    let dir = std::env::temp_dir().join(format!("snapshots-{}", uuid::Uuid::new_v4()));
+    let snapshots = Arc::new(FailureSnapshots::with_run_id(&dir, "run-1"));
+    let scraper = Scraper::replay(fixtures("reddit")).expect("Fixtures should load");
+
+    let task_snapshots = Arc::clone(&snapshots);
+    let found = scraper
+        .execute(move |context| {
+            task_snapshots.watch("reddit", &context, || {
+                context.navigate("https://www.reddit.com/r/rust/")?;
+                Ok(vec![1])
+            })
+        })
+        .await;
+    assert_eq!(found.unwrap(), vec![1]);
+    assert!(!snapshots.run_dir().exists(), "Successful scrapes should not be captured");
+
+    let task_snapshots = Arc::clone(&snapshots);
+    let empty = scraper
+        .execute(move |context| {
+            task_snapshots.watch("reddit", &context, || {
+                context.navigate("https://www.reddit.com/r/rust/")?;
+                Ok(Vec::<u32>::new())
+            })
+        })
+        .await;
+    assert!(empty.unwrap().is_empty());
+
+    let task_snapshots = Arc::clone(&snapshots);
+    let failed = scraper
+        .execute(move |context| {
+            task_snapshots.watch("reddit", &context, || {
+                context.navigate("https://www.reddit.com/r/rust/")?;
+                context.wait_for_element("#missing", 100)?;
+                Ok(vec![1])
+            })
+        })
+        .await;
+    assert!(matches!(failed, Err(Error::Timeout)));
+
+    let captured: Vec<_> = fs::read_dir(snapshots.run_dir())
+        .expect("Snapshots should be saved in the run directory")
+        .flatten()
+        .map(|entry| entry.path())
+        .collect();
+    assert_eq!(captured.len(), 2);
+
+    for path in &captured {
+        assert!(path.join("screenshot.png").exists());
+        assert!(fs::read_to_string(path.join("page.html")).unwrap().contains("<html"));
+
+        let report: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(path.join("report.json")).unwrap()).unwrap();
+        assert_eq!(report["platform"], "reddit");
+        assert_eq!(report["url"], "https://www.reddit.com/r/rust/");
+    }
+
+    fs::remove_dir_all(&dir).ok();
+}
+
+/**
+ * Prueba los límites de las capturas y que no se guarden los valores de las cookies.
+ *
+ * @assert El reporte debe guardar el nombre y dominio de las cookies, pero no su valor.
+ * @assert Al guardar la primera captura se borran las ejecuciones más antiguas sobre el límite.
+ * @assert Una ejecución con el máximo de capturas no guarda más y devuelve el resultado sin cambios.
+ */
+#[tokio::test]
+async fn test_snapshot_retention_and_cookies() {
+    let dir = std::env::temp_dir().join(format!("snapshots-{}", uuid::Uuid::new_v4()));
+    for run in ["run-old", "run-previous"] {
+        fs::create_dir_all(dir.join(run).join("reddit-1")).unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(20));
+    }
+
+    let snapshots = Arc::new(FailureSnapshots::with_run_id(&dir, "run-current").with_retention(2, 1));
+    let scraper = Scraper::replay(fixtures("reddit")).expect("Fixtures should load");
+
+    for _ in 0..2 {
+        let task_snapshots = Arc::clone(&snapshots);
+        let empty = scraper
+            .execute(move |context| {
+                task_snapshots.watch("reddit", &context, || {
+                    context.navigate("https://www.reddit.com/r/rust/")?;
+                    context.set_string_cookies(
+                        r#"[{"name":"reddit_session","value":"secret-token","domain":".reddit.com"}]"#,
+                    )?;
+                    Ok(Vec::<u32>::new())
+                })
+            })
+            .await;
+        assert!(empty.unwrap().is_empty());
+    }
+
+    assert!(!dir.join("run-old").exists(), "Runs over the limit should be removed");
+    assert!(dir.join("run-previous").exists(), "The most recent runs should be kept");
+
+    let captured: Vec<_> = fs::read_dir(snapshots.run_dir()).unwrap().flatten().map(|entry| entry.path()).collect();
+    assert_eq!(captured.len(), 1, "A run should not save more snapshots than its limit");
+
+    let report = fs::read_to_string(captured[0].join("report.json")).unwrap();
+    let report: serde_json::Value = serde_json::from_str(&report).unwrap();
+    assert_eq!(report["cookies"], serde_json::json!([{ "name": "reddit_session", "domain": ".reddit.com" }]));
+    assert!(!report.to_string().contains("secret-token"), "Cookie values should not be saved");
+
+    fs::remove_dir_all(&dir).ok();
+}
@@ -0,0 +1,137 @@
+/**
+ * Pruebas unitarias para el módulo scraping::source
+ *
+ * Este archivo verifica el registro de plataformas sociales y la conversión de las
+ * publicaciones de cada plataforma al modelo común `SocialPost`, usado por el scraper
+ * de tendencias, el guardado en DynamoDB y el cálculo de analytics. La conversión de
+ * Reddit usa las páginas grabadas en `fixtures/reddit`, por lo que no requiere red.
+ *
+ * Proyecto: WhisperTrend
+ */
+
+use crate::nosql::{ScrapedPost, controllers::analytics::{AnalyticsRequest, HashtagData, TrendsData, process_all_hashtags}};
+use crate::scraping::{
+    reddit::RedditScraper,
+    source::{SOURCES, SocialPost},
+    trends::TrendsScraper,
+};
+use crate::test::fixtures;
+use zbrowser::Scraper;
+
+/**
+ *
+ * Prueba el registro de plataformas `SOURCES`.
+ *
+ * @assert Deben estar registradas Reddit, Instagram, Twitter, TikTok, YouTube, Bluesky y Mastodon, en ese orden.
+ * @assert Las tendencias vacías deben tener una entrada por plataforma.
+ */
+#[test]
+fn test_sources_registry() {
+    let names: Vec<&str> = SOURCES.iter().map(|source| source.name()).collect();
+    assert_eq!(names, vec!["reddit", "instagram", "twitter", "tiktok", "youtube", "bluesky", "mastodon"]);
+
+    let data = TrendsScraper::empty_data();
+    assert_eq!(data.len(), names.len(), "Every platform should have an entry");
+    assert!(data.values().all(|metrics| metrics.is_empty()));
+}
+
+/**
+ *
+ * Prueba la conversión de los posts de Reddit grabados a `SocialPost`.
+ *
+ * @assert El título debe pasar a `text`, los votos a `likes` y los miembros a `followers`.
+ * @assert El subreddit debe quedar como comunidad del post.
+ */
+#[tokio::test]
+async fn test_reddit_posts_to_social_posts() {
+    let scraper = Scraper::replay(fixtures("reddit")).expect("Fixtures should load");
+    let posts = RedditScraper::get_simple_posts_with_members_with(&scraper, "rust".to_string(), 25, None)
+        .await
+        .expect("Replayed search should not fail");
+
+    let posts: Vec<SocialPost> = posts.into_iter().map(SocialPost::from).collect();
+    assert_eq!(posts.len(), 1, "Posts without subreddit page should be skipped");
+    assert_eq!(posts[0].text, "Announcing Rust 1.87.0");
+    assert_eq!(posts[0].link, "https://www.reddit.com/r/rust/comments/1kz0a1b/");
+    assert_eq!(posts[0].likes, 1200);
+    assert_eq!(posts[0].comments, 245);
+    assert_eq!(posts[0].followers, 362_000);
+    assert_eq!(posts[0].community.as_deref(), Some("https://www.reddit.com/r/rust/"));
+}
+
+/**
+ *
+ * Prueba la conversión entre `SocialPost` y el formato guardado en DynamoDB.
+ *
+ * @assert Un post guardado debe recuperarse igual.
+ * @assert Los posts de Reddit guardados antes del modelo común deben leer sus votos y miembros.
+ */
+#[test]
+fn test_scraped_post_round_trip() {
+    let post = SocialPost {
+        text: "New album out now".to_string(),
+        link: "https://x.com/band/status/1".to_string(),
+        time: "2025-06-01T12:00:00.000Z".to_string(),
+        views: 0,
+        likes: 320,
+        comments: 12,
+        shares: 45,
+        followers: 9_800,
+        community: None,
+    };
+    assert_eq!(SocialPost::from(ScrapedPost::from(&post)), post);
+
+    let legacy: ScrapedPost = serde_json::from_value(serde_json::json!({
+        "comments": 245,
+        "followers": null,
+        "likes": 0,
+        "link": "Announcing Rust 1.87.0",
+        "time": "Announcing Rust 1.87.0",
+        "members": 362000,
+        "subreddit": "https://www.reddit.com/r/rust/",
+        "title": "Announcing Rust 1.87.0",
+        "vote": 1200
+    }))
+    .expect("Legacy Reddit posts should deserialize");
+    let legacy = SocialPost::from(legacy);
+    assert_eq!(legacy.likes, 1200);
+    assert_eq!(legacy.followers, 362_000);
+    assert_eq!(legacy.shares, 0);
+}
+
+/**
+ *
+ * Prueba que las plataformas sin fórmulas propias se analicen con el modelo común.
+ *
+ * @assert Una plataforma desconocida debe tener tasas a partir de sus `SocialPost`:
+ *         interacción (500 + 40 + 25) / 12000 * 100 = 4.71 y viralidad ln(66) / ln(12001) * 30 = 13.38.
+ * @assert Las plataformas registradas sin datos deben tener tasas en 0.
+ */
+#[test]
+fn test_analytics_for_new_platform() {
+    let post = SocialPost {
+        likes: 500,
+        comments: 40,
+        shares: 25,
+        followers: 12_000,
+        ..SocialPost::default()
+    };
+    let request = AnalyticsRequest {
+        hashtags: vec!["synthwave".to_string()],
+        trends: TrendsData::from([(
+            "threads".to_string(),
+            vec![HashtagData {
+                keyword: "synthwave".to_string(),
+                posts: vec![serde_json::to_value(&post).unwrap()],
+            }],
+        )]),
+        sales: vec![],
+    };
+
+    let metrics = process_all_hashtags(&request);
+    assert_eq!(metrics.len(), 1);
+    assert_eq!(metrics[0].rates["threads_interaction"], 4.71);
+    assert_eq!(metrics[0].rates["threads_virality"], 13.38);
+    assert_eq!(metrics[0].rates["reddit_interaction"], 0.0);
+    assert_eq!(metrics[0].rates["twitter_virality"], 0.0);
+}
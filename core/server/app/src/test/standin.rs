@@ -0,0 +1,55 @@
+/**
+ * Servidor HTTP local que reemplaza a las APIs públicas en las pruebas.
+ *
+ * Responde con las respuestas grabadas en `fixtures` según la ruta y los parámetros
+ * de cada petición, por lo que los clientes HTTP se prueban sin red.
+ *
+ * Proyecto: WhisperTrend
+ */
+
+use actix_web::{App, HttpRequest, HttpResponse, HttpServer, web};
+use std::net::TcpListener;
+
+/**
+ * Indica si una petición coincide con una ruta.
+ *
+ * @param route Ruta con su consulta (ej. `/api?q=rust`).
+ * @param request Petición recibida.
+ * @return `true` si la ruta es la de la petición y todos sus parámetros están en ella.
+ */
+fn matches(route: &str, request: &HttpRequest) -> bool {
+    let (path, query) = route.split_once('?').unwrap_or((route, ""));
+    let params: Vec<&str> = request.query_string().split('&').collect();
+    request.path() == path && query.split('&').filter(|param| !param.is_empty()).all(|param| params.contains(&param))
+}
+
+/**
+ * Inicia un servidor local con respuestas fijas.
+ *
+ * Cada ruta lleva los parámetros de consulta que debe tener la petición, en
+ * cualquier orden, y el JSON que se responde; gana la primera ruta que coincida
+ * y las peticiones sin ruta reciben un 404.
+ *
+ * @param routes Rutas y respuestas, de la más específica a la más general.
+ * @return URL base del servidor (ej. `http://127.0.0.1:41234`).
+ */
+pub fn serve(routes: &'static [(&'static str, &'static str)]) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").expect("Stand-in should bind a local port");
+    let address = listener.local_addr().expect("Stand-in should have an address");
+
+    let server = HttpServer::new(move || {
+        App::new().default_service(web::to(move |request: HttpRequest| async move {
+            match routes.iter().find(|(route, _)| matches(route, &request)) {
+                Some((_, body)) => HttpResponse::Ok().content_type("application/json").body(*body),
+                None => HttpResponse::NotFound().finish(),
+            }
+        }))
+    })
+    .workers(1)
+    .listen(listener)
+    .expect("Stand-in should listen")
+    .run();
+
+    actix_web::rt::spawn(server);
+    format!("http://{}", address)
+}
@@ -0,0 +1,73 @@
+/**
+ * Pruebas unitarias para el módulo scraping::tiktok
+ *
+ * Las pruebas usan la página grabada del hashtag `skincare` en `fixtures/tiktok`,
+ * con dos páginas de `/api/challenge/item_list/` que comparten un video, mediante
+ * el backend de replay de zbrowser, por lo que no requieren Chrome ni red.
+ *
+ * Proyecto: WhisperTrend
+ */
+
+use crate::nosql::controllers::analytics::process_tiktok_hashtag;
+use crate::scraping::{source::SocialPost, tiktok::TikTokScraper};
+use crate::test::fixtures;
+use zbrowser::Scraper;
+
+/**
+ *
+ * Prueba `get_posts_with` sobre el hashtag grabado.
+ *
+ * @assert Se deben leer las dos páginas de videos sin repetir el video compartido.
+ * @assert Las métricas, el autor, el enlace y la fecha deben coincidir con la grabación.
+ * @assert Con límite 1 solo se debe devolver un video.
+ */
+#[tokio::test]
+async fn test_get_posts_from_fixtures() {
+    let scraper = Scraper::replay(fixtures("tiktok")).expect("Fixtures should load");
+    let posts = TikTokScraper::get_posts_with(&scraper, "skincare".to_string(), 30, None)
+        .await
+        .expect("Replayed hashtag should not fail");
+
+    assert_eq!(posts.len(), 3, "Repeated videos should be skipped");
+    assert_eq!(posts[0].author, "sunscreensam");
+    assert_eq!(posts[0].text, "AM routine for oily skin, no white cast #skincare #spf");
+    assert_eq!(posts[0].link, "https://www.tiktok.com/@sunscreensam/video/7510842196635782422");
+    assert_eq!(posts[0].time, "2025-06-02T18:42:12.000Z");
+    assert_eq!(posts[0].views, 1_083_400);
+    assert_eq!(posts[0].likes, 87_600);
+    assert_eq!(posts[0].comments, 1_128);
+    assert_eq!(posts[0].shares, 4_370);
+    assert_eq!(posts[0].followers, 386_500);
+    assert_eq!(posts[2].author, "budgetbeauty");
+
+    let limited = TikTokScraper::get_posts_with(&scraper, "skincare".to_string(), 1, None)
+        .await
+        .expect("Replayed hashtag should not fail");
+    assert_eq!(limited.len(), 1, "Videos should be limited to the requested number");
+}
+
+/**
+ *
+ * Prueba las fórmulas de TikTok con los videos grabados.
+ *
+ * @assert La interacción debe ser el promedio de (likes + comentarios + compartidos) / vistas * 100
+ *         de los tres videos: (8.59 + 1.24 + 9.97) / 3 = 6.6.
+ * @assert La viralidad debe ser el promedio de ln(vistas + compartidos * 10 + 1) / ln(seguidores + 1) * 25
+ *         de los tres videos: (27.08 + 30.56 + 29.65) / 3 = 29.1.
+ * @assert Un hashtag sin videos debe tener tasas en 0.
+ */
+#[tokio::test]
+async fn test_tiktok_analytics_from_fixtures() {
+    let scraper = Scraper::replay(fixtures("tiktok")).expect("Fixtures should load");
+    let posts: Vec<SocialPost> = TikTokScraper::get_posts_with(&scraper, "skincare".to_string(), 30, None)
+        .await
+        .expect("Replayed hashtag should not fail")
+        .into_iter()
+        .map(SocialPost::from)
+        .collect();
+
+    let (interaction, virality) = process_tiktok_hashtag(&posts);
+    assert_eq!(interaction, 6.6);
+    assert_eq!(virality, 29.1);
+    assert_eq!(process_tiktok_hashtag(&[]), (0.0, 0.0));
+}
@@ -11,6 +11,8 @@
  */
 
 use crate::scraping::twitter::TwitterScraper;
+use crate::test::fixtures;
+use zbrowser::Scraper;
 
 /**
  *
@@ -42,3 +44,37 @@ async fn test_get_posts_runs() {
     let result = TwitterScraper::get_posts("openai".to_string()).await;
     assert!(result.is_ok());
 }
+
+/**
+ *
+ * Prueba los scripts de extracción de X sobre las páginas grabadas en
+ * `fixtures/twitter`, sin Chrome, red ni sesión.
+ *
+ * @assert Los tweets de la búsqueda deben conservar autor, métricas y enlace.
+ * @assert Los tweets repetidos entre pasos de scroll deben aparecer una sola vez.
+ * @assert Los seguidores del autor deben leerse de su perfil y convertirse a número.
+ */
+#[tokio::test]
+async fn test_twitter_extraction_from_fixtures() {
+    let scraper = Scraper::replay(fixtures("twitter")).expect("Fixtures should load");
+
+    let (tweets, followers) = scraper
+        .execute(|context| {
+            context.navigate("https://x.com/search?q=%23openai")?;
+            let tweets = TwitterScraper::collect_tweets(&context, 10)?;
+
+            context.navigate("https://x.com/OpenAI")?;
+            let followers = TwitterScraper::extract_followers(&context, "OpenAI")?;
+
+            Ok((tweets, followers))
+        })
+        .await
+        .expect("Replayed pages should not fail");
+
+    assert_eq!(tweets.len(), 2, "Tweets seen in several scroll steps should appear once");
+    assert_eq!(tweets[0].handle, "@OpenAI");
+    assert_eq!(tweets[1].handle, "@sama");
+    assert_eq!(tweets[0].likes, 15230);
+    assert_eq!(tweets[0].followers, 0, "Search results do not include followers");
+    assert!(followers > 4_000_000, "4.1M followers should be parsed");
+}
@@ -0,0 +1,85 @@
+/**
+ * Pruebas unitarias para el módulo scraping::youtube
+ *
+ * Las pruebas usan la búsqueda grabada de `guitar review` en `fixtures/youtube`,
+ * con dos de sus tres videos grabados, mediante el backend de replay de zbrowser,
+ * por lo que no requieren Chrome ni red.
+ *
+ * Proyecto: WhisperTrend
+ */
+
+use crate::nosql::controllers::analytics::process_youtube_hashtag;
+use crate::scraping::{source::SocialPost, youtube::YouTubeScraper};
+use crate::test::fixtures;
+use zbrowser::Scraper;
+
+/**
+ *
+ * Prueba las URLs de la búsqueda y de los videos.
+ *
+ * @assert La búsqueda debe codificar la palabra clave y ordenar por fecha de subida en inglés.
+ * @assert El enlace de un video debe usar su identificador.
+ */
+#[test]
+fn test_urls() {
+    assert_eq!(
+        YouTubeScraper::search_url("guitar review"),
+        "https://www.youtube.com/results?search_query=guitar+review&sp=CAISAhAB&hl=en"
+    );
+    assert_eq!(YouTubeScraper::video_url("r7Ht2vLq0cE"), "https://www.youtube.com/watch?v=r7Ht2vLq0cE");
+}
+
+/**
+ *
+ * Prueba `get_posts_with` sobre la búsqueda grabada.
+ *
+ * @assert Los videos cuya página no se pudo cargar se deben omitir.
+ * @assert Las métricas, el canal, el enlace y la fecha en UTC deben coincidir con la grabación.
+ * @assert Los contadores abreviados y con separador de miles deben leerse como números.
+ */
+#[tokio::test]
+async fn test_get_posts_from_fixtures() {
+    let scraper = Scraper::replay(fixtures("youtube")).expect("Fixtures should load");
+    let posts = YouTubeScraper::get_posts_with(&scraper, "guitar review".to_string(), 15, None)
+        .await
+        .expect("Replayed search should not fail");
+
+    assert_eq!(posts.len(), 2, "Videos without page should be skipped");
+    assert_eq!(posts[0].channel, "Tone Lab");
+    assert_eq!(posts[0].title, "Fender Player II Stratocaster review — worth it?");
+    assert_eq!(posts[0].link, "https://www.youtube.com/watch?v=r7Ht2vLq0cE");
+    assert_eq!(posts[0].time, "2025-06-04T17:26:41.000Z");
+    assert_eq!(posts[0].views, 184_230);
+    assert_eq!(posts[0].likes, 9_200);
+    assert_eq!(posts[0].comments, 1_340);
+    assert_eq!(posts[0].subscribers, 1_200_000);
+    assert_eq!(posts[1].time, "2025-06-01T00:00:00.000Z", "Dates without time should start the day");
+    assert_eq!(posts[1].subscribers, 48_500);
+}
+
+/**
+ *
+ * Prueba las fórmulas de YouTube con los videos grabados.
+ *
+ * @assert La interacción debe ser el promedio de (likes + comentarios * 2) / vistas * 100
+ *         de los dos videos: (6.45 + 5.26) / 2 = 5.85.
+ * @assert La viralidad debe ser el promedio de ln(vistas + 1) / ln(suscriptores + 1) * 30
+ *         de los dos videos: (25.98 + 30.19) / 2 = 28.09.
+ * @assert Una búsqueda sin videos debe tener tasas en 0.
+ */
+#[tokio::test]
+async fn test_youtube_analytics_from_fixtures() {
+    let scraper = Scraper::replay(fixtures("youtube")).expect("Fixtures should load");
+    let posts: Vec<SocialPost> = YouTubeScraper::get_posts_with(&scraper, "guitar review".to_string(), 15, None)
+        .await
+        .expect("Replayed search should not fail")
+        .into_iter()
+        .map(SocialPost::from)
+        .collect();
+
+    assert_eq!(posts[0].followers, 1_200_000, "Subscribers should be the reach of the video");
+    let (interaction, virality) = process_youtube_hashtag(&posts);
+    assert_eq!(interaction, 5.85);
+    assert_eq!(virality, 28.09);
+    assert_eq!(process_youtube_hashtag(&[]), (0.0, 0.0));
+}
@@ -5,6 +5,7 @@
 * de tendencias en redes sociales. Incluye datos estructurados para Instagram,
 * Reddit y Twitter con métricas de engagement, metadatos de noticias y
 * hashtags de ejemplo para validar el funcionamiento completo del pipeline.
+* Los posts usan el modelo común con el que el backend guarda todas las plataformas.
 * 
 * Autor: Lucio Arturo Reyes Castillo
 * Contribuyentes: [Lista de contribuyentes]
@@ -32,25 +33,37 @@ export const obtenerDatosPrueba = () => {
            keyword: '#EcoFriendly',
            posts: [
              {
+               text: "",
+               link: "https://instagram.com/post/1",
+               time: "2025-01-15T10:00:00Z",
+               views: 0,
+               likes: 350,
                comments: 45,
+               shares: 0,
                followers: 3500,
-               likes: 350,
-               link: "https://instagram.com/post/1",
-               time: "2025-01-15T10:00:00Z"
+               community: null
              },
              {
+               text: "",
+               link: "https://instagram.com/post/2",
+               time: "2025-02-15T10:00:00Z",
+               views: 0,
+               likes: 420,
                comments: 52,
+               shares: 0,
                followers: 3600,
-               likes: 420,
-               link: "https://instagram.com/post/2",
-               time: "2025-02-15T10:00:00Z"
+               community: null
              },
              {
+               text: "",
+               link: "https://instagram.com/post/3",
+               time: "2025-03-15T10:00:00Z",
+               views: 0,
+               likes: 380,
                comments: 38,
+               shares: 0,
                followers: 3700,
-               likes: 380,
-               link: "https://instagram.com/post/3",
-               time: "2025-03-15T10:00:00Z"
+               community: null
              }
            ]
          },
@@ -58,18 +71,26 @@ export const obtenerDatosPrueba = () => {
            keyword: '#SustainableFashion',
            posts: [
              {
+               text: "",
+               link: "https://instagram.com/post/4",
+               time: "2025-01-20T10:00:00Z",
+               views: 0,
+               likes: 280,
                comments: 32,
+               shares: 0,
                followers: 2800,
-               likes: 280,
-               link: "https://instagram.com/post/4",
-               time: "2025-01-20T10:00:00Z"
+               community: null
              },
              {
+               text: "",
+               link: "https://instagram.com/post/5",
+               time: "2025-02-20T10:00:00Z",
+               views: 0,
+               likes: 310,
                comments: 41,
+               shares: 0,
                followers: 2900,
-               likes: 310,
-               link: "https://instagram.com/post/5",
-               time: "2025-02-20T10:00:00Z"
+               community: null
              }
            ]
          },
@@ -77,18 +98,26 @@ export const obtenerDatosPrueba = () => {
            keyword: '#NuevosMateriales',
            posts: [
              {
+               text: "",
+               link: "https://instagram.com/post/6",
+               time: "2025-01-25T10:00:00Z",
+               views: 0,
+               likes: 220,
                comments: 25,
+               shares: 0,
                followers: 2200,
-               likes: 220,
-               link: "https://instagram.com/post/6",
-               time: "2025-01-25T10:00:00Z"
+               community: null
              },
              {
+               text: "",
+               link: "https://instagram.com/post/7",
+               time: "2025-02-25T10:00:00Z",
+               views: 0,
+               likes: 240,
                comments: 28,
+               shares: 0,
                followers: 2300,
-               likes: 240,
-               link: "https://instagram.com/post/7",
-               time: "2025-02-25T10:00:00Z"
+               community: null
              }
            ]
          }
@@ -98,24 +127,26 @@ export const obtenerDatosPrueba = () => {
            keyword: '#EcoFriendly',
            posts: [
              {
-               comments: 28,
-               members: 15000,
-               likes: 0,
-               vote: 95,
-               subreddit: 'sustainability',
-               title: 'Post sobre EcoFriendly - 1',
+               text: "Post sobre EcoFriendly - 1",
                link: "https://reddit.com/r/sustainability/post/1",
-               time: "2025-01-15T11:00:00Z"
+               time: "2025-01-15T11:00:00Z",
+               views: 0,
+               likes: 95,
+               comments: 28,
+               shares: 0,
+               followers: 15000,
+               community: "r/sustainability"
              },
              {
-               comments: 34,
-               members: 15200,
-               likes: 0,
-               vote: 112,
-               subreddit: 'sustainability',
-               title: 'Post sobre EcoFriendly - 2',
+               text: "Post sobre EcoFriendly - 2",
                link: "https://reddit.com/r/sustainability/post/2",
-               time: "2025-02-15T11:00:00Z"
+               time: "2025-02-15T11:00:00Z",
+               views: 0,
+               likes: 112,
+               comments: 34,
+               shares: 0,
+               followers: 15200,
+               community: "r/sustainability"
              }
            ]
          },
@@ -123,14 +154,15 @@ export const obtenerDatosPrueba = () => {
            keyword: '#SustainableFashion',
            posts: [
              {
-               comments: 22,
-               members: 12000,
-               likes: 0,
-               vote: 78,
-               subreddit: 'sustainablefashion',
-               title: 'Post sobre SustainableFashion - 1',
+               text: "Post sobre SustainableFashion - 1",
                link: "https://reddit.com/r/sustainablefashion/post/1",
-               time: "2025-01-20T11:00:00Z"
+               time: "2025-01-20T11:00:00Z",
+               views: 0,
+               likes: 78,
+               comments: 22,
+               shares: 0,
+               followers: 12000,
+               community: "r/sustainablefashion"
              }
            ]
          },
@@ -138,14 +170,15 @@ export const obtenerDatosPrueba = () => {
            keyword: '#NuevosMateriales',
            posts: [
              {
-               comments: 18,
-               members: 8000,
-               likes: 0,
-               vote: 65,
-               subreddit: 'materials',
-               title: 'Post sobre NuevosMateriales - 1',
+               text: "Post sobre NuevosMateriales - 1",
                link: "https://reddit.com/r/materials/post/1",
-               time: "2025-01-25T11:00:00Z"
+               time: "2025-01-25T11:00:00Z",
+               views: 0,
+               likes: 65,
+               comments: 18,
+               shares: 0,
+               followers: 8000,
+               community: "r/materials"
              }
            ]
          }
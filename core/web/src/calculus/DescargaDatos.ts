@@ -24,34 +24,33 @@ import { obtenerDatosPrueba } from './DatosPrueba';
 * Define los tipos de datos que fluyen a través del pipeline desde
 * las APIs hasta los resultados finales consolidados.
 */
+// Publicación con el modelo común que el backend usa para todas las plataformas
 interface APIPost {
- comments: number;
- followers?: number;
- likes: number;
+ text: string;
  link: string;
  time: string;
- // Reddit específico
- members?: number;
- subreddit?: string;
- title?: string;
- vote?: number;
+ views: number;
+ likes: number;
+ comments: number;
+ shares: number;
+ followers: number;
+ community: string | null;
+}
+
+// Plataformas registradas en el backend, todas con sus posts agrupados por hashtag
+const PLATAFORMAS = ['instagram', 'reddit', 'twitter', 'tiktok', 'youtube', 'bluesky', 'mastodon'] as const;
+type Plataforma = typeof PLATAFORMAS[number];
+
+interface HashtagPosts {
+ keyword: string;
+ posts: APIPost[];
 }
 
 interface APIData {
  hashtags: string[];
  sentence: string;
  trends: {
-   data: {
-     instagram: Array<{
-       keyword: string;
-       posts: APIPost[];
-     }>;
-     reddit: Array<{
-       keyword: string;
-       posts: APIPost[];
-     }>;
-     twitter: APIPost[];
-   };
+   data: Partial<Record<Plataforma, HashtagPosts[]>>;
    metadata: Array<{
      description: string;
      keywords: string[];
@@ -80,11 +79,7 @@ interface ResultadoFinal {
    timestamp: string;
    hashtagsOriginales: string[];
    sentence: string;
-   totalPosts: {
-     instagram: number;
-     reddit: number;
-     twitter: number;
-   };
+   totalPosts: Record<Plataforma, number>;
    fuente: 'api' | 'prueba' | 'fallback';
  };
  calculated_results?: any;
@@ -320,11 +315,10 @@ class DescargaDatos {
  }
 
  private prepararResultadoFinal(datosAPI: APIData, resultados: any, fuente: 'api' | 'prueba' | 'fallback', analysisData?: any): ResultadoFinal {
-   const totalPosts = {
-     instagram: datosAPI.trends.data.instagram?.reduce((total, grupo) => total + (grupo.posts?.length || 0), 0) || 0,
-     reddit: datosAPI.trends.data.reddit?.reduce((total, grupo) => total + (grupo.posts?.length || 0), 0) || 0,
-     twitter: datosAPI.trends.data.twitter?.length || 0
-   };
+   const totalPosts = Object.fromEntries(PLATAFORMAS.map(plataforma => [
+     plataforma,
+     datosAPI.trends.data[plataforma]?.reduce((total, grupo) => total + (grupo.posts?.length || 0), 0) || 0
+   ])) as Record<Plataforma, number>;
 
    return {
      resultadoInstaCalc: resultados.instagram,
@@ -364,9 +358,9 @@ class DescargaDatos {
        datos.vistas.push((post.likes || 0) * 8);
        datos.compartidos.push(Math.floor((post.likes || 0) * 0.05));
      } else if (plataforma === 'reddit') {
-       datos.upVotes.push(post.vote || 0);
+       datos.upVotes.push(post.likes || 0);
        datos.comentarios.push(post.comments || 0);
-       datos.suscriptores.push(post.members || 0);
+       datos.suscriptores.push(post.followers || 0);
        datos.horas.push(24);
      }
    });
@@ -485,4 +479,4 @@ export const crearConDatosContext = (analysisData: any) => {
 };
 
 export default DescargaDatos;
-export type { ResultadoFinal, Noticia, APIData };
\ No newline at end of file
+export type { ResultadoFinal, Noticia, APIData, APIPost, Plataforma };
\ No newline at end of file
@@ -47,16 +47,21 @@ describe('DatosPrueba - Statement Coverage', () => {
     expect(instaPost.comments).toBe(45);
     expect(instaPost.followers).toBe(3500);
     expect(instaPost.likes).toBe(350);
+    expect(instaPost.views).toBe(0);
+    expect(instaPost.shares).toBe(0);
     expect(instaPost.link).toBe('https://instagram.com/post/1');
     expect(instaPost.time).toBe('2025-01-15T10:00:00Z');
+    expect(instaPost.community).toBeNull();
     
-    // Verifica posts de Reddit
+    // Verifica posts de Reddit con el modelo común
     const redditPost = datos.trends.data.reddit[0].posts[0];
     expect(redditPost.comments).toBe(28);
-    expect(redditPost.members).toBe(15000);
-    expect(redditPost.vote).toBe(95);
-    expect(redditPost.subreddit).toBe('sustainability');
-    expect(redditPost.title).toBe('Post sobre EcoFriendly - 1');
+    expect(redditPost.followers).toBe(15000);
+    expect(redditPost.likes).toBe(95);
+    expect(redditPost.community).toBe('r/sustainability');
+    expect(redditPost.text).toBe('Post sobre EcoFriendly - 1');
+    expect(redditPost).not.toHaveProperty('vote');
+    expect(redditPost).not.toHaveProperty('members');
     
     // Verifica Twitter vacío
     expect(datos.trends.data.twitter).toEqual([]);
@@ -0,0 +1,88 @@
+package main
+
+// #include <stdint.h>
+import "C"
+
+import (
+	"context"
+	"errors"
+	"fmt"
+	"libscraper/scraper"
+	"sync"
+
+	"github.com/chromedp/cdproto/runtime"
+)
+
+// Result codes shared with the Rust layer
+// They must stay in sync with zbrowser::Error::from_code
+const (
+	codeOk            = 0 // Operation completed successfully
+	codeFailed        = 1 // Generic browser or protocol failure
+	codeTimeout       = 2 // The operation exceeded its deadline
+	codeNavigation    = 3 // The page could not be loaded
+	codeJsException   = 4 // The evaluated script threw an exception
+	codeContextClosed = 5 // The browser context no longer exists
+	codeDisconnected  = 6 // The connection to the remote browser was lost
+	codeCrashed       = 7 // The browser or the renderer of the tab crashed
+	codeLimitExceeded = 8 // The task went over a resource limit of its tab
+)
+
+// typedCauses are the causes of cancelled contexts reported with their own result code
+var typedCauses = []error{scraper.ErrDisconnected, scraper.ErrCrashed, scraper.ErrLimitExceeded}
+
+// errorMap stores the message of the last error of each browser context, indexed by context ID
+var errorMap sync.Map
+
+// errContextClosed is reported when a context ID is not registered anymore
+var errContextClosed = errors.New("context closed")
+
+// loadContext retrieves the browser context registered under the given ID
+// Records a context closed error when the context does not exist
+func loadContext(ctxID C.int64_t) (context.Context, bool) {
+	ctxInterface, ok := contextMap.Load(int64(ctxID))
+	if !ok {
+		errorMap.Store(int64(ctxID), errContextClosed.Error())
+		return nil, false
+	}
+	return *ctxInterface.(*context.Context), true
+}
+
+// fail records the error message for the context and classifies it into a result code
+// Parameters:
+//   - ctxID: Browser context ID
+//   - err: Error returned by chromedp
+//   - fallback: Code to use when the error does not match a known category
+//
+// Returns: Result code describing the error
+func fail(ctxID C.int64_t, err error, fallback int64) C.int64_t {
+	// Actions cancelled because the browser went away or the task went over a limit only report
+	// context.Canceled, the cause of the context tells them apart from a cancelled task
+	if ctxInterface, ok := contextMap.Load(int64(ctxID)); ok {
+		cause := context.Cause(*ctxInterface.(*context.Context))
+		for _, typed := range typedCauses {
+			if errors.Is(cause, typed) && !errors.Is(err, typed) {
+				err = fmt.Errorf("%w: %v", cause, err)
+				break
+			}
+		}
+	}
+	errorMap.Store(int64(ctxID), err.Error())
+
+	var exception *runtime.ExceptionDetails
+	switch {
+	case errors.Is(err, scraper.ErrDisconnected):
+		return C.int64_t(codeDisconnected)
+	case errors.Is(err, scraper.ErrCrashed):
+		return C.int64_t(codeCrashed)
+	case errors.Is(err, scraper.ErrLimitExceeded):
+		return C.int64_t(codeLimitExceeded)
+	case errors.Is(err, context.DeadlineExceeded):
+		return C.int64_t(codeTimeout)
+	case errors.Is(err, context.Canceled):
+		return C.int64_t(codeContextClosed)
+	case errors.As(err, &exception):
+		return C.int64_t(codeJsException)
+	default:
+		return C.int64_t(fallback)
+	}
+}
@@ -16,14 +16,13 @@ import "C"
 
 import (
 	"context"
+	"encoding/base64"
 	"encoding/json"
-	"fmt"
 	"libscraper/scraper"
 	"sync"
 	"sync/atomic"
 	"time"
 
-	"github.com/chromedp/cdproto/emulation"
 	"github.com/chromedp/cdproto/network"
 	"github.com/chromedp/cdproto/runtime"
 	"github.com/chromedp/chromedp"
@@ -32,11 +31,29 @@ import (
 // Global maps to store scrapers and browser contexts
 // scraperMap stores scraper instances indexed by ID
 // contextMap stores browser contexts indexed by ID
+// cancelMap stores the cancel function of each running task indexed by context ID
+// captureMap stores the response capture of each browser context indexed by ID
+// proxyMap stores the function closing the proxy tab of each browser context indexed by ID
+// userAgentMap stores the user agent override of each browser context indexed by ID
+// elementMap stores the element handles of each browser context indexed by ID
+// diagnosticsMap stores the console, exception and network diagnostics of each browser context indexed by ID
+// warmMap stores the warm tab of each browser context running a pooled task indexed by ID
 var (
-	scraperMap sync.Map
-	contextMap sync.Map
+	scraperMap     sync.Map
+	contextMap     sync.Map
+	cancelMap      sync.Map
+	captureMap     sync.Map
+	proxyMap       sync.Map
+	userAgentMap   sync.Map
+	elementMap     sync.Map
+	diagnosticsMap sync.Map
+	warmMap        sync.Map
 )
 
+// cancelled marks a context ID whose task was cancelled before it started
+// CloseContext removes the marker left by a cancellation that came after the task returned
+type cancelled struct{}
+
 // Atomic counter for generating unique scraper IDs
 var scraperNextID int64 = 1
 
@@ -104,48 +121,68 @@ func NewScraper(url *C.char, workers C.int64_t, blockResources []*C.char) C.int6
 //   - ctxID: Browser context ID
 //   - url: URL to navigate to
 //
-// Returns: 0 on success, 1 on error
+// Returns: Result code (0 on success)
 //
 //export Navigate
 func Navigate(ctxID C.int64_t, url *C.char) C.int64_t {
-	ctxInterface, ok := contextMap.Load(int64(ctxID))
+	ctx, ok := loadContext(ctxID)
 	if !ok {
-		return C.int64_t(1)
+		return C.int64_t(codeContextClosed)
+	}
+
+	err := chromedp.Run(ctx, chromedp.Navigate(C.GoString(url)))
+	if err != nil {
+		return fail(ctxID, err, codeNavigation)
 	}
-	ctx := ctxInterface.(*context.Context)
+	return C.int64_t(codeOk)
+}
 
-	err := chromedp.Run(*ctx, chromedp.Navigate(C.GoString(url)))
+// evaluate runs a JavaScript expression and converts its result to a string
+// Strings are returned as they are, undefined and null become an empty string
+// and any other value is returned as JSON
+func evaluate(ctx context.Context, expr string, awaitPromise bool) (string, error) {
+	var obj *runtime.RemoteObject
+	err := chromedp.Run(ctx, chromedp.Evaluate(expr, &obj, func(p *runtime.EvaluateParams) *runtime.EvaluateParams {
+		return p.WithReturnByValue(true).WithAwaitPromise(awaitPromise)
+	}))
 	if err != nil {
-		return C.int64_t(1)
+		return "", err
+	}
+
+	if obj == nil || obj.Type == runtime.TypeUndefined || len(obj.Value) == 0 || string(obj.Value) == "null" {
+		return "", nil
 	}
-	return C.int64_t(0)
+
+	if obj.Type == runtime.TypeString {
+		var resultStr string
+		err = json.Unmarshal(obj.Value, &resultStr)
+		return resultStr, err
+	}
+	return string(obj.Value), nil
 }
 
 // Evaluate executes JavaScript code synchronously in the browser context
 // Parameters:
 //   - ctxID: Browser context ID
 //   - expr: JavaScript expression to evaluate
-//   - result: Pointer to store the result code (0 = success, 1 = error)
+//   - result: Pointer to store the result code (0 on success)
 //
 // Returns: String result of the JavaScript evaluation
 //
 //export Evaluate
 func Evaluate(ctxID C.int64_t, expr *C.char, result *C.int64_t) *C.char {
-	ctxInterface, ok := contextMap.Load(int64(ctxID))
+	ctx, ok := loadContext(ctxID)
 	if !ok {
-		fmt.Println("Evaluate: Context not found")
-		*result = C.int64_t(1)
+		*result = C.int64_t(codeContextClosed)
 		return C.CString("")
 	}
-	ctx := ctxInterface.(*context.Context)
 
-	var resultStr string
-	err := chromedp.Run(*ctx, chromedp.Evaluate(C.GoString(expr), &resultStr))
+	resultStr, err := evaluate(ctx, C.GoString(expr), false)
 	if err != nil {
-		fmt.Println("Evaluate: ", err)
-		*result = C.int64_t(1)
+		*result = fail(ctxID, err, codeFailed)
 		return C.CString("")
 	}
+	*result = C.int64_t(codeOk)
 	return C.CString(resultStr)
 }
 
@@ -154,52 +191,279 @@ func Evaluate(ctxID C.int64_t, expr *C.char, result *C.int64_t) *C.char {
 // Parameters:
 //   - ctxID: Browser context ID
 //   - expr: JavaScript expression to evaluate
-//   - result: Pointer to store the result code (0 = success, 1 = error)
+//   - result: Pointer to store the result code (0 on success)
 //
 // Returns: String result of the JavaScript evaluation
 //
 //export AsyncEvaluate
 func AsyncEvaluate(ctxID C.int64_t, expr *C.char, result *C.int64_t) *C.char {
-	ctxInterface, ok := contextMap.Load(int64(ctxID))
+	ctx, ok := loadContext(ctxID)
 	if !ok {
-		fmt.Println("AsyncEvaluate: Context not found")
-		*result = C.int64_t(1)
+		*result = C.int64_t(codeContextClosed)
 		return C.CString("")
 	}
-	ctx := ctxInterface.(*context.Context)
 
-	var resultStr string
-	err := chromedp.Run(*ctx, chromedp.Evaluate(C.GoString(expr), &resultStr, func(p *runtime.EvaluateParams) *runtime.EvaluateParams {
-		return p.WithAwaitPromise(true)
-	}))
+	resultStr, err := evaluate(ctx, C.GoString(expr), true)
 	if err != nil {
-		fmt.Println("AsyncEvaluate: ", err)
-		*result = C.int64_t(1)
+		*result = fail(ctxID, err, codeFailed)
 		return C.CString("")
 	}
+	*result = C.int64_t(codeOk)
 	return C.CString(resultStr)
 }
 
+// parseArgs decodes a JSON array of function arguments
+// Each argument is kept as raw JSON so that it reaches the browser unchanged
+func parseArgs(argsJSON string) ([]any, error) {
+	var args []json.RawMessage
+	if argsJSON != "" {
+		if err := json.Unmarshal([]byte(argsJSON), &args); err != nil {
+			return nil, err
+		}
+	}
+
+	callArgs := make([]any, len(args))
+	for i, arg := range args {
+		callArgs[i] = arg
+	}
+	return callArgs, nil
+}
+
+// callFunction calls a JavaScript function declaration with JSON arguments passed as CDP call arguments
+// The arguments are never spliced into the function source
+// Returns the result serialized as JSON, "null" for undefined
+func callFunction(ctx context.Context, function string, argsJSON string, awaitPromise bool) (string, error) {
+	callArgs, err := parseArgs(argsJSON)
+	if err != nil {
+		return "", err
+	}
+
+	var obj *runtime.RemoteObject
+	err = chromedp.Run(ctx, chromedp.CallFunctionOn(function, &obj, func(p *runtime.CallFunctionOnParams) *runtime.CallFunctionOnParams {
+		return p.WithReturnByValue(true).WithAwaitPromise(awaitPromise)
+	}, callArgs...))
+	if err != nil {
+		return "", err
+	}
+
+	if obj == nil || obj.Type == runtime.TypeUndefined || len(obj.Value) == 0 {
+		return "null", nil
+	}
+	return string(obj.Value), nil
+}
+
+// CallFunction calls a JavaScript function in the browser context with JSON arguments
+// Parameters:
+//   - ctxID: Browser context ID
+//   - function: JavaScript function declaration, e.g. "(selector) => document.querySelector(selector).id"
+//   - args: JSON array with the arguments of the function
+//   - result: Pointer to store the result code (0 on success)
+//
+// Returns: JSON result of the function
+//
+//export CallFunction
+func CallFunction(ctxID C.int64_t, function *C.char, args *C.char, result *C.int64_t) *C.char {
+	ctx, ok := loadContext(ctxID)
+	if !ok {
+		*result = C.int64_t(codeContextClosed)
+		return C.CString("")
+	}
+
+	resultJSON, err := callFunction(ctx, C.GoString(function), C.GoString(args), false)
+	if err != nil {
+		*result = fail(ctxID, err, codeFailed)
+		return C.CString("")
+	}
+	*result = C.int64_t(codeOk)
+	return C.CString(resultJSON)
+}
+
+// AsyncCallFunction calls a JavaScript function in the browser context with JSON arguments
+// Waits for Promise resolution if the function returns a Promise
+// Parameters:
+//   - ctxID: Browser context ID
+//   - function: JavaScript function declaration
+//   - args: JSON array with the arguments of the function
+//   - result: Pointer to store the result code (0 on success)
+//
+// Returns: JSON result of the function
+//
+//export AsyncCallFunction
+func AsyncCallFunction(ctxID C.int64_t, function *C.char, args *C.char, result *C.int64_t) *C.char {
+	ctx, ok := loadContext(ctxID)
+	if !ok {
+		*result = C.int64_t(codeContextClosed)
+		return C.CString("")
+	}
+
+	resultJSON, err := callFunction(ctx, C.GoString(function), C.GoString(args), true)
+	if err != nil {
+		*result = fail(ctxID, err, codeFailed)
+		return C.CString("")
+	}
+	*result = C.int64_t(codeOk)
+	return C.CString(resultJSON)
+}
+
 // SetUserAgent sets the user agent string for the browser context
 // Parameters:
 //   - ctxID: Browser context ID
 //   - userAgent: User agent string to set
 //
-// Returns: 0 on success, 1 on error
+// Returns: Result code (0 on success)
 //
 //export SetUserAgent
 func SetUserAgent(ctxID C.int64_t, userAgent *C.char) C.int64_t {
-	ctxInterface, ok := contextMap.Load(int64(ctxID))
+	ctx, ok := loadContext(ctxID)
 	if !ok {
-		return C.int64_t(1)
+		return C.int64_t(codeContextClosed)
+	}
+
+	// The languages set with Emulate are sent again with the new user agent
+	override := loadUserAgent(ctxID)
+	override.UserAgent = C.GoString(userAgent)
+	if err := override.Apply(ctx); err != nil {
+		return fail(ctxID, err, codeFailed)
+	}
+	userAgentMap.Store(int64(ctxID), override)
+	return C.int64_t(codeOk)
+}
+
+// loadUserAgent returns the user agent override of a browser context, empty if none was set
+func loadUserAgent(ctxID C.int64_t) scraper.UserAgentOverride {
+	if override, ok := userAgentMap.Load(int64(ctxID)); ok {
+		return override.(scraper.UserAgentOverride)
 	}
+	return scraper.UserAgentOverride{}
+}
 
-	ctx := ctxInterface.(*context.Context)
-	err := chromedp.Run(*ctx, emulation.SetUserAgentOverride(C.GoString(userAgent)))
+// Emulate applies device, language and location overrides to the browser context
+// Parameters:
+//   - ctxID: Browser context ID
+//   - settings: JSON object with the optional fields viewport, userAgent, acceptLanguage,
+//     locale, timezone and geolocation
+//
+// Returns: Result code (0 on success)
+//
+//export Emulate
+func Emulate(ctxID C.int64_t, settings *C.char) C.int64_t {
+	ctx, ok := loadContext(ctxID)
+	if !ok {
+		return C.int64_t(codeContextClosed)
+	}
+
+	var overrides scraper.Emulation
+	if err := json.Unmarshal([]byte(C.GoString(settings)), &overrides); err != nil {
+		return fail(ctxID, err, codeFailed)
+	}
+
+	override := loadUserAgent(ctxID)
+	if err := scraper.Emulate(ctx, overrides, &override); err != nil {
+		return fail(ctxID, err, codeFailed)
+	}
+	userAgentMap.Store(int64(ctxID), override)
+	return C.int64_t(codeOk)
+}
+
+// BlockURLs makes the browser context fail the requests whose URL matches one of the patterns
+// The patterns apply until the task ends, along with the resource types blocked by the scraper
+// Parameters:
+//   - ctxID: Browser context ID
+//   - patterns: JSON array of regular expressions matched against the full request URL
+//
+// Returns: Result code (0 on success)
+//
+//export BlockURLs
+func BlockURLs(ctxID C.int64_t, patterns *C.char) C.int64_t {
+	ctx, ok := loadContext(ctxID)
+	if !ok {
+		return C.int64_t(codeContextClosed)
+	}
+
+	var rules []string
+	if err := json.Unmarshal([]byte(C.GoString(patterns)), &rules); err != nil {
+		return fail(ctxID, err, codeFailed)
+	}
+
+	if err := scraper.BlockURLs(ctx, rules); err != nil {
+		return fail(ctxID, err, codeFailed)
+	}
+	return C.int64_t(codeOk)
+}
+
+// AddInitScript makes the browser context evaluate a script before the scripts of every page it loads
+// The script applies from the next navigation until the task ends
+// Parameters:
+//   - ctxID: Browser context ID
+//   - script: JavaScript source of the script
+//
+// Returns: Result code (0 on success)
+//
+//export AddInitScript
+func AddInitScript(ctxID C.int64_t, script *C.char) C.int64_t {
+	ctx, ok := loadContext(ctxID)
+	if !ok {
+		return C.int64_t(codeContextClosed)
+	}
+
+	if err := scraper.AddInitScript(ctx, C.GoString(script)); err != nil {
+		return fail(ctxID, err, codeFailed)
+	}
+	return C.int64_t(codeOk)
+}
+
+// SetProxy moves the browser context to a new tab that sends its traffic through a proxy
+// The tab lives in its own browser context and is closed when the task ends,
+// unless the task is pooled, in which case it replaces the warm tab of the task
+// Blocked URLs and init scripts of the context carry over to the new tab
+// Parameters:
+//   - ctxID: Browser context ID
+//   - server: Proxy URL without credentials, e.g. http://host:port or socks5://host:port
+//   - username: Proxy username, empty for none
+//   - password: Proxy password
+//
+// Returns: Result code (0 on success)
+//
+//export SetProxy
+func SetProxy(ctxID C.int64_t, server *C.char, username *C.char, password *C.char) C.int64_t {
+	ctx, ok := loadContext(ctxID)
+	if !ok {
+		return C.int64_t(codeContextClosed)
+	}
+
+	proxy := scraper.Proxy{
+		Server:   C.GoString(server),
+		Username: C.GoString(username),
+		Password: C.GoString(password),
+	}
+
+	// Warm tabs keep their proxy, so only the first task of a key opens a new tab
+	if warm, ok := warmMap.Load(int64(ctxID)); ok {
+		warmCtx, err := warm.(*scraper.Warm).SetProxy(proxy)
+		if err != nil {
+			return fail(ctxID, err, codeFailed)
+		}
+		contextMap.Store(int64(ctxID), &warmCtx)
+		return C.int64_t(codeOk)
+	}
+
+	proxyCtx, closeTab, err := scraper.OpenTab(ctx, proxy)
 	if err != nil {
-		return C.int64_t(1)
+		return fail(ctxID, err, codeFailed)
+	}
+
+	// A second proxy replaces the tab of the first one
+	releaseProxy(ctxID)
+	proxyMap.Store(int64(ctxID), closeTab)
+	contextMap.Store(int64(ctxID), &proxyCtx)
+	return C.int64_t(codeOk)
+}
+
+// releaseProxy closes the proxy tab of a browser context, if any
+func releaseProxy(ctxID C.int64_t) {
+	if closeTab, ok := proxyMap.LoadAndDelete(int64(ctxID)); ok {
+		closeTab.(func())()
 	}
-	return C.int64_t(0)
 }
 
 // WaitForElement waits for a DOM element to be ready/available
@@ -208,27 +472,138 @@ func SetUserAgent(ctxID C.int64_t, userAgent *C.char) C.int64_t {
 //   - selector: CSS selector for the element to wait for
 //   - timeoutMs: Timeout in milliseconds
 //
-// Returns: 0 on success, 1 on error/timeout
+// Returns: Result code (0 on success, 2 on timeout)
 //
 //export WaitForElement
 func WaitForElement(ctxID C.int64_t, selector *C.char, timeoutMs C.int64_t) C.int64_t {
-	ctxInterface, ok := contextMap.Load(int64(ctxID))
+	ctx, ok := loadContext(ctxID)
 	if !ok {
-		return C.int64_t(1)
+		return C.int64_t(codeContextClosed)
 	}
-	ctx := ctxInterface.(*context.Context)
 
 	// Create timeout context
 	timeoutDuration := time.Duration(timeoutMs) * time.Millisecond
-	timeoutCtx, cancel := context.WithTimeout(*ctx, timeoutDuration)
+	timeoutCtx, cancel := context.WithTimeout(ctx, timeoutDuration)
 	defer cancel()
 
 	err := chromedp.Run(timeoutCtx, chromedp.WaitReady(C.GoString(selector), chromedp.ByQuery))
 	if err != nil {
-		return C.int64_t(1)
+		return fail(ctxID, err, codeFailed)
 	}
 
-	return C.int64_t(0)
+	return C.int64_t(codeOk)
+}
+
+// WaitForNetworkIdle waits until no request has been in flight for a quiet period
+// Parameters:
+//   - ctxID: Browser context ID
+//   - idleMs: Quiet period in milliseconds
+//   - timeoutMs: Timeout in milliseconds
+//
+// Returns: Result code (0 on success, 2 on timeout)
+//
+//export WaitForNetworkIdle
+func WaitForNetworkIdle(ctxID C.int64_t, idleMs C.int64_t, timeoutMs C.int64_t) C.int64_t {
+	ctx, ok := loadContext(ctxID)
+	if !ok {
+		return C.int64_t(codeContextClosed)
+	}
+
+	timeoutCtx, cancel := context.WithTimeout(ctx, time.Duration(timeoutMs)*time.Millisecond)
+	defer cancel()
+
+	if err := scraper.WaitForNetworkIdle(timeoutCtx, time.Duration(idleMs)*time.Millisecond); err != nil {
+		return fail(ctxID, err, codeFailed)
+	}
+	return C.int64_t(codeOk)
+}
+
+// WaitForFunction polls a JavaScript function until it returns a truthy value
+// Parameters:
+//   - ctxID: Browser context ID
+//   - function: JavaScript function declaration
+//   - args: JSON array with the arguments of the function
+//   - timeoutMs: Timeout in milliseconds
+//
+// Returns: Result code (0 on success, 2 on timeout)
+//
+//export WaitForFunction
+func WaitForFunction(ctxID C.int64_t, function *C.char, args *C.char, timeoutMs C.int64_t) C.int64_t {
+	ctx, ok := loadContext(ctxID)
+	if !ok {
+		return C.int64_t(codeContextClosed)
+	}
+
+	callArgs, err := parseArgs(C.GoString(args))
+	if err != nil {
+		return fail(ctxID, err, codeFailed)
+	}
+
+	timeoutCtx, cancel := context.WithTimeout(ctx, time.Duration(timeoutMs)*time.Millisecond)
+	defer cancel()
+
+	if err := scraper.WaitForFunction(timeoutCtx, C.GoString(function), callArgs...); err != nil {
+		return fail(ctxID, err, codeFailed)
+	}
+	return C.int64_t(codeOk)
+}
+
+// WaitForNavigation waits for the page to navigate to a new URL and finish loading
+// Parameters:
+//   - ctxID: Browser context ID
+//   - timeoutMs: Timeout in milliseconds
+//   - result: Pointer to store the result code (0 on success, 2 on timeout)
+//
+// Returns: URL of the new page
+//
+//export WaitForNavigation
+func WaitForNavigation(ctxID C.int64_t, timeoutMs C.int64_t, result *C.int64_t) *C.char {
+	ctx, ok := loadContext(ctxID)
+	if !ok {
+		*result = C.int64_t(codeContextClosed)
+		return C.CString("")
+	}
+
+	timeoutCtx, cancel := context.WithTimeout(ctx, time.Duration(timeoutMs)*time.Millisecond)
+	defer cancel()
+
+	url, err := scraper.WaitForNavigation(timeoutCtx)
+	if err != nil {
+		*result = fail(ctxID, err, codeNavigation)
+		return C.CString("")
+	}
+	*result = C.int64_t(codeOk)
+	return C.CString(url)
+}
+
+// WaitForSelectorCount waits until a minimum number of elements match a CSS selector
+// Parameters:
+//   - ctxID: Browser context ID
+//   - selector: CSS selector to count
+//   - count: Minimum number of matching elements
+//   - timeoutMs: Timeout in milliseconds
+//   - result: Pointer to store the result code (0 on success, 2 on timeout)
+//
+// Returns: Number of matching elements
+//
+//export WaitForSelectorCount
+func WaitForSelectorCount(ctxID C.int64_t, selector *C.char, count C.int64_t, timeoutMs C.int64_t, result *C.int64_t) C.int64_t {
+	ctx, ok := loadContext(ctxID)
+	if !ok {
+		*result = C.int64_t(codeContextClosed)
+		return 0
+	}
+
+	timeoutCtx, cancel := context.WithTimeout(ctx, time.Duration(timeoutMs)*time.Millisecond)
+	defer cancel()
+
+	found, err := scraper.WaitForSelectorCount(timeoutCtx, C.GoString(selector), int64(count))
+	if err != nil {
+		*result = fail(ctxID, err, codeFailed)
+		return C.int64_t(found)
+	}
+	*result = C.int64_t(codeOk)
+	return C.int64_t(found)
 }
 
 // WriteInput types text into an input element
@@ -237,21 +612,20 @@ func WaitForElement(ctxID C.int64_t, selector *C.char, timeoutMs C.int64_t) C.in
 //   - selector: CSS selector for the input element
 //   - text: Text to type into the input
 //
-// Returns: 0 on success, 1 on error
+// Returns: Result code (0 on success)
 //
 //export WriteInput
 func WriteInput(ctxID C.int64_t, selector *C.char, text *C.char) C.int64_t {
-	ctxInterface, ok := contextMap.Load(int64(ctxID))
+	ctx, ok := loadContext(ctxID)
 	if !ok {
-		return C.int64_t(1)
+		return C.int64_t(codeContextClosed)
 	}
-	ctx := ctxInterface.(*context.Context)
 
-	err := chromedp.Run(*ctx, chromedp.SendKeys(C.GoString(selector), C.GoString(text)))
+	err := chromedp.Run(ctx, chromedp.SendKeys(C.GoString(selector), C.GoString(text)))
 	if err != nil {
-		return C.int64_t(1)
+		return fail(ctxID, err, codeFailed)
 	}
-	return C.int64_t(0)
+	return C.int64_t(codeOk)
 }
 
 // ClickElement clicks on a DOM element
@@ -259,50 +633,372 @@ func WriteInput(ctxID C.int64_t, selector *C.char, text *C.char) C.int64_t {
 //   - ctxID: Browser context ID
 //   - selector: CSS selector for the element to click
 //
-// Returns: 0 on success, 1 on error
+// Returns: Result code (0 on success)
 //
 //export ClickElement
 func ClickElement(ctxID C.int64_t, selector *C.char) C.int64_t {
-	ctxInterface, ok := contextMap.Load(int64(ctxID))
+	ctx, ok := loadContext(ctxID)
+	if !ok {
+		return C.int64_t(codeContextClosed)
+	}
+
+	err := chromedp.Run(ctx, chromedp.Click(C.GoString(selector), chromedp.ByQuery))
+	if err != nil {
+		return fail(ctxID, err, codeFailed)
+	}
+	return C.int64_t(codeOk)
+}
+
+// HoverElement moves the mouse over an element, scrolling it into view first
+// Parameters:
+//   - ctxID: Browser context ID
+//   - selector: CSS selector for the element to hover
+//
+// Returns: Result code (0 on success)
+//
+//export HoverElement
+func HoverElement(ctxID C.int64_t, selector *C.char) C.int64_t {
+	ctx, ok := loadContext(ctxID)
+	if !ok {
+		return C.int64_t(codeContextClosed)
+	}
+
+	if err := scraper.Hover(ctx, C.GoString(selector)); err != nil {
+		return fail(ctxID, err, codeFailed)
+	}
+	return C.int64_t(codeOk)
+}
+
+// MouseMove moves the mouse to a point of the viewport
+// Parameters:
+//   - ctxID: Browser context ID
+//   - x: Horizontal coordinate in CSS pixels
+//   - y: Vertical coordinate in CSS pixels
+//
+// Returns: Result code (0 on success)
+//
+//export MouseMove
+func MouseMove(ctxID C.int64_t, x C.double, y C.double) C.int64_t {
+	ctx, ok := loadContext(ctxID)
+	if !ok {
+		return C.int64_t(codeContextClosed)
+	}
+
+	if err := scraper.MouseMove(ctx, float64(x), float64(y)); err != nil {
+		return fail(ctxID, err, codeFailed)
+	}
+	return C.int64_t(codeOk)
+}
+
+// MouseWheel turns the mouse wheel over the center of the viewport
+// Parameters:
+//   - ctxID: Browser context ID
+//   - deltaX: Horizontal scroll distance in CSS pixels
+//   - deltaY: Vertical scroll distance in CSS pixels
+//
+// Returns: Result code (0 on success)
+//
+//export MouseWheel
+func MouseWheel(ctxID C.int64_t, deltaX C.double, deltaY C.double) C.int64_t {
+	ctx, ok := loadContext(ctxID)
+	if !ok {
+		return C.int64_t(codeContextClosed)
+	}
+
+	if err := scraper.Wheel(ctx, float64(deltaX), float64(deltaY)); err != nil {
+		return fail(ctxID, err, codeFailed)
+	}
+	return C.int64_t(codeOk)
+}
+
+// PressKey presses and releases a key on the focused element
+// Parameters:
+//   - ctxID: Browser context ID
+//   - key: Key name or character with optional modifiers, e.g. "Enter" or "Control+A"
+//
+// Returns: Result code (0 on success)
+//
+//export PressKey
+func PressKey(ctxID C.int64_t, key *C.char) C.int64_t {
+	ctx, ok := loadContext(ctxID)
+	if !ok {
+		return C.int64_t(codeContextClosed)
+	}
+
+	if err := scraper.PressKey(ctx, C.GoString(key)); err != nil {
+		return fail(ctxID, err, codeFailed)
+	}
+	return C.int64_t(codeOk)
+}
+
+// SelectOption selects the options of a select element by value or label
+// Parameters:
+//   - ctxID: Browser context ID
+//   - selector: CSS selector for the select element
+//   - values: JSON array with the values or labels to select
+//   - result: Pointer to store the result code (0 on success)
+//
+// Returns: JSON array with the values of the selected options
+//
+//export SelectOption
+func SelectOption(ctxID C.int64_t, selector *C.char, values *C.char, result *C.int64_t) *C.char {
+	ctx, ok := loadContext(ctxID)
 	if !ok {
-		return C.int64_t(1)
+		*result = C.int64_t(codeContextClosed)
+		return C.CString("[]")
 	}
-	ctx := ctxInterface.(*context.Context)
 
-	err := chromedp.Run(*ctx, chromedp.Click(C.GoString(selector), chromedp.ByQuery))
+	var options []string
+	if err := json.Unmarshal([]byte(C.GoString(values)), &options); err != nil {
+		*result = fail(ctxID, err, codeFailed)
+		return C.CString("[]")
+	}
+
+	selected, err := scraper.SelectOption(ctx, C.GoString(selector), options)
+	if err != nil {
+		*result = fail(ctxID, err, codeFailed)
+		return C.CString("[]")
+	}
+
+	jsonSelected, err := json.Marshal(selected)
 	if err != nil {
-		return C.int64_t(1)
+		*result = fail(ctxID, err, codeFailed)
+		return C.CString("[]")
 	}
-	return C.int64_t(0)
+	*result = C.int64_t(codeOk)
+	return C.CString(string(jsonSelected))
+}
+
+// FocusElement gives the keyboard focus to an element
+// Parameters:
+//   - ctxID: Browser context ID
+//   - selector: CSS selector for the element to focus
+//
+// Returns: Result code (0 on success)
+//
+//export FocusElement
+func FocusElement(ctxID C.int64_t, selector *C.char) C.int64_t {
+	ctx, ok := loadContext(ctxID)
+	if !ok {
+		return C.int64_t(codeContextClosed)
+	}
+
+	err := chromedp.Run(ctx, chromedp.Focus(C.GoString(selector), chromedp.ByQuery))
+	if err != nil {
+		return fail(ctxID, err, codeFailed)
+	}
+	return C.int64_t(codeOk)
+}
+
+// BlurElement removes the keyboard focus from an element
+// Parameters:
+//   - ctxID: Browser context ID
+//   - selector: CSS selector for the element to blur
+//
+// Returns: Result code (0 on success)
+//
+//export BlurElement
+func BlurElement(ctxID C.int64_t, selector *C.char) C.int64_t {
+	ctx, ok := loadContext(ctxID)
+	if !ok {
+		return C.int64_t(codeContextClosed)
+	}
+
+	err := chromedp.Run(ctx, chromedp.Blur(C.GoString(selector), chromedp.ByQuery))
+	if err != nil {
+		return fail(ctxID, err, codeFailed)
+	}
+	return C.int64_t(codeOk)
+}
+
+// UploadFiles sets the files of a file input element
+// Parameters:
+//   - ctxID: Browser context ID
+//   - selector: CSS selector for the file input
+//   - files: JSON array with the paths of the files, as seen by the browser
+//
+// Returns: Result code (0 on success)
+//
+//export UploadFiles
+func UploadFiles(ctxID C.int64_t, selector *C.char, files *C.char) C.int64_t {
+	ctx, ok := loadContext(ctxID)
+	if !ok {
+		return C.int64_t(codeContextClosed)
+	}
+
+	var paths []string
+	if err := json.Unmarshal([]byte(C.GoString(files)), &paths); err != nil {
+		return fail(ctxID, err, codeFailed)
+	}
+
+	err := chromedp.Run(ctx, chromedp.SetUploadFiles(C.GoString(selector), paths, chromedp.ByQuery))
+	if err != nil {
+		return fail(ctxID, err, codeFailed)
+	}
+	return C.int64_t(codeOk)
+}
+
+// loadElements returns the element handles of a browser context, creating them on first use
+func loadElements(ctxID C.int64_t) *scraper.Elements {
+	elements, _ := elementMap.LoadOrStore(int64(ctxID), scraper.NewElements())
+	return elements.(*scraper.Elements)
+}
+
+// QuerySelectorAll finds the elements matching a CSS selector, piercing shadow roots and iframes
+// Parameters:
+//   - ctxID: Browser context ID
+//   - parent: Element handle to search under, 0 to search the whole page
+//   - selector: CSS selector
+//   - result: Pointer to store the result code (0 on success)
+//
+// Returns: JSON array with the handles of the matching elements
+//
+//export QuerySelectorAll
+func QuerySelectorAll(ctxID C.int64_t, parent C.int64_t, selector *C.char, result *C.int64_t) *C.char {
+	ctx, ok := loadContext(ctxID)
+	if !ok {
+		*result = C.int64_t(codeContextClosed)
+		return C.CString("[]")
+	}
+
+	handles, err := loadElements(ctxID).QuerySelectorAll(ctx, int64(parent), C.GoString(selector))
+	if err != nil {
+		*result = fail(ctxID, err, codeFailed)
+		return C.CString("[]")
+	}
+
+	jsonHandles, err := json.Marshal(handles)
+	if err != nil {
+		*result = fail(ctxID, err, codeFailed)
+		return C.CString("[]")
+	}
+	*result = C.int64_t(codeOk)
+	return C.CString(string(jsonHandles))
+}
+
+// callElement calls a function on the element of a handle and stores the result code
+func callElement(ctxID C.int64_t, element C.int64_t, function string, result *C.int64_t, args ...any) string {
+	ctx, ok := loadContext(ctxID)
+	if !ok {
+		*result = C.int64_t(codeContextClosed)
+		return "null"
+	}
+
+	resultJSON, err := loadElements(ctxID).Call(ctx, int64(element), function, args...)
+	if err != nil {
+		*result = fail(ctxID, err, codeFailed)
+		return "null"
+	}
+	*result = C.int64_t(codeOk)
+	return resultJSON
+}
+
+// ElementText returns the text content of an element
+// Parameters:
+//   - ctxID: Browser context ID
+//   - element: Element handle
+//   - result: Pointer to store the result code (0 on success)
+//
+// Returns: JSON string with the text of the element and its descendants
+//
+//export ElementText
+func ElementText(ctxID C.int64_t, element C.int64_t, result *C.int64_t) *C.char {
+	return C.CString(callElement(ctxID, element, "function() { return this.textContent; }", result))
+}
+
+// ElementAttribute returns the value of an attribute of an element
+// Parameters:
+//   - ctxID: Browser context ID
+//   - element: Element handle
+//   - name: Attribute name
+//   - result: Pointer to store the result code (0 on success)
+//
+// Returns: JSON string with the value, or null if the element has no such attribute
+//
+//export ElementAttribute
+func ElementAttribute(ctxID C.int64_t, element C.int64_t, name *C.char, result *C.int64_t) *C.char {
+	function := "function(name) { return this.getAttribute(name); }"
+	return C.CString(callElement(ctxID, element, function, result, C.GoString(name)))
+}
+
+// ElementInnerHTML returns the HTML of the children of an element
+// Parameters:
+//   - ctxID: Browser context ID
+//   - element: Element handle
+//   - result: Pointer to store the result code (0 on success)
+//
+// Returns: JSON string with the inner HTML of the element
+//
+//export ElementInnerHTML
+func ElementInnerHTML(ctxID C.int64_t, element C.int64_t, result *C.int64_t) *C.char {
+	return C.CString(callElement(ctxID, element, "function() { return this.innerHTML; }", result))
+}
+
+// ClickHandle scrolls an element into view and clicks it with the mouse
+// Parameters:
+//   - ctxID: Browser context ID
+//   - element: Element handle
+//
+// Returns: Result code (0 on success)
+//
+//export ClickHandle
+func ClickHandle(ctxID C.int64_t, element C.int64_t) C.int64_t {
+	ctx, ok := loadContext(ctxID)
+	if !ok {
+		return C.int64_t(codeContextClosed)
+	}
+
+	if err := loadElements(ctxID).Click(ctx, int64(element)); err != nil {
+		return fail(ctxID, err, codeFailed)
+	}
+	return C.int64_t(codeOk)
+}
+
+// HoverHandle scrolls an element into view and moves the mouse over it
+// Parameters:
+//   - ctxID: Browser context ID
+//   - element: Element handle
+//
+// Returns: Result code (0 on success)
+//
+//export HoverHandle
+func HoverHandle(ctxID C.int64_t, element C.int64_t) C.int64_t {
+	ctx, ok := loadContext(ctxID)
+	if !ok {
+		return C.int64_t(codeContextClosed)
+	}
+
+	if err := loadElements(ctxID).Hover(ctx, int64(element)); err != nil {
+		return fail(ctxID, err, codeFailed)
+	}
+	return C.int64_t(codeOk)
 }
 
 // StringCookies retrieves all cookies from the browser context as a JSON string
 // Parameters:
 //   - ctxID: Browser context ID
-//   - result: Pointer to store the result code (0 = success, 1 = error)
+//   - result: Pointer to store the result code (0 on success)
 //
 // Returns: JSON string containing all cookies
 //
 //export StringCookies
 func StringCookies(ctxID C.int64_t, result *C.int64_t) *C.char {
-	ctxInterface, ok := contextMap.Load(int64(ctxID))
+	ctx, ok := loadContext(ctxID)
 	if !ok {
-		*result = C.int64_t(1)
+		*result = C.int64_t(codeContextClosed)
 		return C.CString("")
 	}
-	ctx := ctxInterface.(*context.Context)
 
 	// Get all cookies from the browser
 	var cookies []*network.Cookie
-	err := chromedp.Run(*ctx, chromedp.ActionFunc(func(ctx context.Context) error {
+	err := chromedp.Run(ctx, chromedp.ActionFunc(func(ctx context.Context) error {
 		var err error
 		cookies, err = network.GetCookies().Do(ctx)
 		return err
 	}))
 
 	if err != nil {
-		fmt.Println(err)
-		*result = C.int64_t(1)
+		*result = fail(ctxID, err, codeFailed)
 		return C.CString("[]")
 	}
 
@@ -310,12 +1006,11 @@ func StringCookies(ctxID C.int64_t, result *C.int64_t) *C.char {
 	var jsonCookies []byte
 	jsonCookies, err = json.Marshal(cookies)
 	if err != nil {
-		fmt.Println(err)
-		*result = C.int64_t(1)
+		*result = fail(ctxID, err, codeFailed)
 		return C.CString("[]")
 	}
 
-	*result = C.int64_t(0)
+	*result = C.int64_t(codeOk)
 	return C.CString(string(jsonCookies))
 }
 
@@ -324,18 +1019,16 @@ func StringCookies(ctxID C.int64_t, result *C.int64_t) *C.char {
 //   - ctxID: Browser context ID
 //   - stringCookies: JSON string containing cookie parameters
 //
-// Returns: 0 on success, 1 on error
+// Returns: Result code (0 on success)
 //
 //export SetStringCookies
 func SetStringCookies(ctxID C.int64_t, stringCookies *C.char) C.int64_t {
-	ctxInterface, ok := contextMap.Load(int64(ctxID))
+	ctx, ok := loadContext(ctxID)
 	if !ok {
-		return C.int64_t(1)
+		return C.int64_t(codeContextClosed)
 	}
 
-	ctx := ctxInterface.(*context.Context)
-
-	err := chromedp.Run(*ctx, chromedp.ActionFunc(func(ctx context.Context) error {
+	err := chromedp.Run(ctx, chromedp.ActionFunc(func(ctx context.Context) error {
 		var err error
 		var cookies []*network.CookieParam
 		// Unmarshal JSON string to cookie parameters
@@ -349,28 +1042,59 @@ func SetStringCookies(ctxID C.int64_t, stringCookies *C.char) C.int64_t {
 	}))
 
 	if err != nil {
-		return C.int64_t(1)
+		return fail(ctxID, err, codeFailed)
+	}
+
+	return C.int64_t(codeOk)
+}
+
+// Screenshot captures a PNG image of the page
+// Parameters:
+//   - ctxID: Browser context ID
+//   - options: JSON object with the optional fields fullPage and clip ({x, y, width, height})
+//   - result: Pointer to store the result code (0 on success)
+//
+// Returns: Base64 encoded PNG image
+//
+//export Screenshot
+func Screenshot(ctxID C.int64_t, options *C.char, result *C.int64_t) *C.char {
+	ctx, ok := loadContext(ctxID)
+	if !ok {
+		*result = C.int64_t(codeContextClosed)
+		return C.CString("")
+	}
+
+	var screenshotOptions scraper.ScreenshotOptions
+	if err := json.Unmarshal([]byte(C.GoString(options)), &screenshotOptions); err != nil {
+		*result = fail(ctxID, err, codeFailed)
+		return C.CString("")
+	}
+
+	image, err := scraper.Screenshot(ctx, screenshotOptions)
+	if err != nil {
+		*result = fail(ctxID, err, codeFailed)
+		return C.CString("")
 	}
 
-	return C.int64_t(0)
+	*result = C.int64_t(codeOk)
+	return C.CString(base64.StdEncoding.EncodeToString(image))
 }
 
 // GetHTML retrieves the complete HTML content of the current page
 // Includes shadow DOM content and handles serialization properly
 // Parameters:
 //   - ctxID: Browser context ID
-//   - result: Pointer to store the result code (0 = success, 1 = error)
+//   - result: Pointer to store the result code (0 on success)
 //
 // Returns: Complete HTML content as a string
 //
 //export GetHTML
 func GetHTML(ctxID C.int64_t, result *C.int64_t) *C.char {
-	ctxInterface, ok := contextMap.Load(int64(ctxID))
+	ctx, ok := loadContext(ctxID)
 	if !ok {
-		*result = C.int64_t(1)
+		*result = C.int64_t(codeContextClosed)
 		return C.CString("")
 	}
-	ctx := ctxInterface.(*context.Context)
 
 	// JavaScript code to serialize the entire DOM including shadow DOM
 	const jsScript = `
@@ -416,46 +1140,326 @@ func GetHTML(ctxID C.int64_t, result *C.int64_t) *C.char {
 	`
 
 	var htmlContent string
-	err := chromedp.Run(*ctx, chromedp.Evaluate(jsScript, &htmlContent))
+	err := chromedp.Run(ctx, chromedp.Evaluate(jsScript, &htmlContent))
 	if err != nil {
-		*result = C.int64_t(1)
+		*result = fail(ctxID, err, codeFailed)
 		return C.CString("")
 	}
 
-	*result = C.int64_t(0)
+	*result = C.int64_t(codeOk)
 	return C.CString(htmlContent)
 }
 
+// loadCapture returns the response capture of a browser context, starting it on first use
+func loadCapture(ctxID C.int64_t, ctx context.Context) (*scraper.Capture, error) {
+	if capture, ok := captureMap.Load(int64(ctxID)); ok {
+		return capture.(*scraper.Capture), nil
+	}
+
+	capture, err := scraper.NewCapture(ctx)
+	if err != nil {
+		return nil, err
+	}
+	actual, _ := captureMap.LoadOrStore(int64(ctxID), capture)
+	return actual.(*scraper.Capture), nil
+}
+
+// CaptureResponses starts recording the network responses whose URL matches a pattern
+// Parameters:
+//   - ctxID: Browser context ID
+//   - pattern: Regular expression matched against the response URL
+//
+// Returns: Result code (0 on success)
+//
+//export CaptureResponses
+func CaptureResponses(ctxID C.int64_t, pattern *C.char) C.int64_t {
+	ctx, ok := loadContext(ctxID)
+	if !ok {
+		return C.int64_t(codeContextClosed)
+	}
+
+	capture, err := loadCapture(ctxID, ctx)
+	if err != nil {
+		return fail(ctxID, err, codeFailed)
+	}
+
+	if err := capture.AddPattern(C.GoString(pattern)); err != nil {
+		return fail(ctxID, err, codeFailed)
+	}
+
+	return C.int64_t(codeOk)
+}
+
+// CapturedResponses returns the responses captured so far and clears them
+// Parameters:
+//   - ctxID: Browser context ID
+//   - result: Pointer to store the result code (0 on success)
+//
+// Returns: JSON array of responses with url, method, status, mimeType, headers and body
+//
+//export CapturedResponses
+func CapturedResponses(ctxID C.int64_t, result *C.int64_t) *C.char {
+	ctx, ok := loadContext(ctxID)
+	if !ok {
+		*result = C.int64_t(codeContextClosed)
+		return C.CString("[]")
+	}
+
+	capture, err := loadCapture(ctxID, ctx)
+	if err != nil {
+		*result = fail(ctxID, err, codeFailed)
+		return C.CString("[]")
+	}
+
+	jsonResponses, err := json.Marshal(capture.Responses())
+	if err != nil {
+		*result = fail(ctxID, err, codeFailed)
+		return C.CString("[]")
+	}
+
+	*result = C.int64_t(codeOk)
+	return C.CString(string(jsonResponses))
+}
+
+// WaitForResponse waits for a network response whose URL matches a pattern
+// Responses captured before the call are returned first
+// Parameters:
+//   - ctxID: Browser context ID
+//   - pattern: Regular expression matched against the response URL
+//   - timeoutMs: Timeout in milliseconds
+//   - result: Pointer to store the result code (0 on success, 2 on timeout)
+//
+// Returns: JSON object with url, method, status, mimeType, headers and body
+//
+//export WaitForResponse
+func WaitForResponse(ctxID C.int64_t, pattern *C.char, timeoutMs C.int64_t, result *C.int64_t) *C.char {
+	ctx, ok := loadContext(ctxID)
+	if !ok {
+		*result = C.int64_t(codeContextClosed)
+		return C.CString("")
+	}
+
+	capture, err := loadCapture(ctxID, ctx)
+	if err != nil {
+		*result = fail(ctxID, err, codeFailed)
+		return C.CString("")
+	}
+
+	// Create timeout context
+	timeoutDuration := time.Duration(timeoutMs) * time.Millisecond
+	timeoutCtx, cancel := context.WithTimeout(ctx, timeoutDuration)
+	defer cancel()
+
+	response, err := capture.Wait(timeoutCtx, C.GoString(pattern))
+	if err != nil {
+		*result = fail(ctxID, err, codeFailed)
+		return C.CString("")
+	}
+
+	jsonResponse, err := json.Marshal(response)
+	if err != nil {
+		*result = fail(ctxID, err, codeFailed)
+		return C.CString("")
+	}
+
+	*result = C.int64_t(codeOk)
+	return C.CString(string(jsonResponse))
+}
+
+// TakeDiagnostics returns the console messages, uncaught exceptions and failed requests
+// recorded since the task started or since the last call, and clears them
+// Parameters:
+//   - ctxID: Browser context ID
+//   - result: Pointer to store the result code (0 on success)
+//
+// Returns: JSON object with console, exceptions and failedRequests arrays
+//
+//export TakeDiagnostics
+func TakeDiagnostics(ctxID C.int64_t, result *C.int64_t) *C.char {
+	if _, ok := loadContext(ctxID); !ok {
+		*result = C.int64_t(codeContextClosed)
+		return C.CString("{}")
+	}
+
+	diagnostics, ok := diagnosticsMap.Load(int64(ctxID))
+	if !ok {
+		*result = C.int64_t(codeOk)
+		return C.CString("{}")
+	}
+
+	jsonReport, err := json.Marshal(diagnostics.(*scraper.Diagnostics).Report())
+	if err != nil {
+		*result = fail(ctxID, err, codeFailed)
+		return C.CString("{}")
+	}
+
+	*result = C.int64_t(codeOk)
+	return C.CString(string(jsonReport))
+}
+
 // Execute runs a task within a scraper context
 // The task is a C function that will be called with the context ID
 // Parameters:
 //   - id: Scraper ID
 //   - contextID: Context ID to pass to the task
 //   - task: C function pointer to execute
-//   - result: Pointer to store the result code (0 = success, 1 = error)
+//   - result: Pointer to store the result code (0 on success)
 //
 //export Execute
 func Execute(id C.int64_t, contextID C.int64_t, task C.Task, result *C.int64_t) {
+	execute(id, contextID, "", task, result)
+}
+
+// ExecutePooled runs a task in a warm tab of the scraper's pool
+// The tab keeps the cookies, overrides and proxy left by the previous task with the same key
+// Parameters:
+//   - id: Scraper ID
+//   - contextID: Context ID to pass to the task
+//   - key: Key of the warm tab, usually the name of a session
+//   - task: C function pointer to execute
+//   - result: Pointer to store the result code (0 on success)
+//
+//export ExecutePooled
+func ExecutePooled(id C.int64_t, contextID C.int64_t, key *C.char, task C.Task, result *C.int64_t) {
+	execute(id, contextID, C.GoString(key), task, result)
+}
+
+// execute runs a task in the tab of a worker, or in a warm tab of the pool when key is not empty
+func execute(id C.int64_t, contextID C.int64_t, key string, task C.Task, result *C.int64_t) {
 	scrapInterface, ok := scraperMap.Load(int64(id))
 	if !ok {
-		*result = C.int64_t(1)
+		errorMap.Store(int64(contextID), "scraper not found")
+		*result = C.int64_t(codeFailed)
 		return
 	}
 	scrap := scrapInterface.(*scraper.Scraper)
 
+	// Register the cancel function so CancelContext can abort the task
+	// A cancellation requested before this point leaves a marker behind and the task never runs
+	taskCtx, cancel := context.WithCancel(context.Background())
+	defer cancel()
+	if _, cancelled := cancelMap.LoadOrStore(int64(contextID), cancel); cancelled {
+		cancelMap.Delete(int64(contextID))
+		*result = fail(contextID, context.Canceled, codeFailed)
+		return
+	}
+	defer cancelMap.Delete(int64(contextID))
+
 	// Execute the task within the scraper context
-	_, err := scrap.Execute(func(ctx context.Context) (any, error) {
-		// Store the context for the task to use
-		contextMap.Store(int64(contextID), &ctx)
-		// Call the C task function
-		C.callTask(task, contextID)
-		return struct{}{}, nil
+	_, err := scrap.ExecuteContext(taskCtx, func(ctx context.Context) (any, error) {
+		if key == "" {
+			runTask(ctx, contextID, task)
+			return struct{}{}, context.Cause(ctx)
+		}
+
+		warm, err := scrap.Pool.Get(ctx, key)
+		if err != nil {
+			return nil, err
+		}
+		warmMap.Store(int64(contextID), warm)
+		if override, ok := warm.State.(scraper.UserAgentOverride); ok {
+			userAgentMap.Store(int64(contextID), override)
+		}
+
+		runTask(warm.Context(), contextID, task)
+		warmMap.Delete(int64(contextID))
+		err = context.Cause(warm.Context())
+		scrap.Pool.Put(warm, err)
+		return struct{}{}, err
 	})
+	releaseProxy(contextID)
 
 	if err != nil {
-		*result = C.int64_t(1)
+		*result = fail(contextID, err, codeFailed)
 	} else {
-		*result = C.int64_t(0)
+		*result = C.int64_t(codeOk)
+	}
+}
+
+// runTask registers the browser context of a task and calls the task with its ID
+func runTask(ctx context.Context, contextID C.int64_t, task C.Task) {
+	// Store the context for the task to use
+	contextMap.Store(int64(contextID), &ctx)
+	// Diagnostics are best effort, a task still runs when they cannot be recorded
+	if diagnostics, err := scraper.NewDiagnostics(ctx); err == nil {
+		diagnosticsMap.Store(int64(contextID), diagnostics)
+	}
+	// Call the C task function
+	C.callTask(task, contextID)
+}
+
+// SetPoolLimits changes the limits of the pool of warm tabs of a scraper
+// Parameters:
+//   - id: Scraper ID
+//   - maxUses: Tasks a warm tab runs before it is replaced by a fresh one
+//   - maxIdle: Idle warm tabs kept for each key
+//
+//export SetPoolLimits
+func SetPoolLimits(id C.int64_t, maxUses C.int64_t, maxIdle C.int64_t) {
+	if scrapInterface, ok := scraperMap.Load(int64(id)); ok {
+		scrapInterface.(*scraper.Scraper).Pool.SetLimits(int(maxUses), int(maxIdle))
+	}
+}
+
+// ContextUses returns the number of earlier tasks that ran in the tab of a browser context
+// Parameters:
+//   - id: Browser context ID
+//
+// Returns: Number of uses, 0 for a fresh warm tab or a task outside the pool
+//
+//export ContextUses
+func ContextUses(id C.int64_t) C.int64_t {
+	if warm, ok := warmMap.Load(int64(id)); ok {
+		return C.int64_t(warm.(*scraper.Warm).Uses)
+	}
+	return 0
+}
+
+// SetLimits changes the resource limits of the tasks of a scraper
+// Tabs of tasks going over a limit are closed and replaced by fresh ones
+// Parameters:
+//   - id: Scraper ID
+//   - maxHeapMB: Maximum JavaScript heap of a page in megabytes, 0 for no limit
+//   - maxTaskMs: Maximum duration of a task in milliseconds, 0 for no limit
+//
+//export SetLimits
+func SetLimits(id C.int64_t, maxHeapMB C.int64_t, maxTaskMs C.int64_t) {
+	if scrapInterface, ok := scraperMap.Load(int64(id)); ok {
+		scrapInterface.(*scraper.Scraper).SetLimits(scraper.Limits{
+			MaxHeap:     int64(maxHeapMB) << 20,
+			MaxTaskTime: time.Duration(maxTaskMs) * time.Millisecond,
+		})
+	}
+}
+
+// BrowserStats returns the browser failures a scraper recovered from
+// Parameters:
+//   - id: Scraper ID
+//
+// Returns: JSON object with the crashes, disconnects, restarts and limitKills counters
+//
+//export BrowserStats
+func BrowserStats(id C.int64_t) *C.char {
+	scrapInterface, ok := scraperMap.Load(int64(id))
+	if !ok {
+		return C.CString("{}")
+	}
+
+	jsonStats, err := json.Marshal(scrapInterface.(*scraper.Scraper).Stats())
+	if err != nil {
+		return C.CString("{}")
+	}
+	return C.CString(string(jsonStats))
+}
+
+// DiscardContext closes the warm tab of a pooled task once the task ends, instead of reusing it
+// Parameters:
+//   - id: Browser context ID
+//
+//export DiscardContext
+func DiscardContext(id C.int64_t) {
+	if warm, ok := warmMap.Load(int64(id)); ok {
+		warm.(*scraper.Warm).Discard()
 	}
 }
 
@@ -474,11 +1478,70 @@ func Close(id C.int64_t) {
 	scraperMap.Delete(int64(id))
 }
 
+// Connected reports whether every worker of a scraper has a usable browser tab
+// Parameters:
+//   - id: Scraper ID
+//
+// Returns: 1 if connected, 0 while a browser is launched, relaunched or reconnecting or if the scraper does not exist
+//
+//export Connected
+func Connected(id C.int64_t) C.int64_t {
+	scrapInterface, ok := scraperMap.Load(int64(id))
+	if !ok || !scrapInterface.(*scraper.Scraper).Connected() {
+		return 0
+	}
+	return 1
+}
+
+// CancelContext aborts the task running with the given context ID
+// Pending browser actions fail immediately and the worker is released once the task returns
+// If the task has not started yet it is skipped
+// Parameters:
+//   - id: Browser context ID
+//
+//export CancelContext
+func CancelContext(id C.int64_t) {
+	if cancel, loaded := cancelMap.LoadOrStore(int64(id), cancelled{}); loaded {
+		if cancel, ok := cancel.(context.CancelFunc); ok {
+			cancel()
+		}
+	}
+}
+
 // CloseContext removes a browser context from the global map
 // Parameters:
 //   - id: Context ID to remove
 //
 //export CloseContext
 func CloseContext(id C.int64_t) {
+	// Warm tabs keep their user agent override for the next task of their key
+	if warm, ok := warmMap.Load(int64(id)); ok {
+		if override, ok := userAgentMap.Load(int64(id)); ok {
+			warm.(*scraper.Warm).State = override
+		}
+	}
+	releaseProxy(id)
+	userAgentMap.Delete(int64(id))
+	elementMap.Delete(int64(id))
 	contextMap.Delete(int64(id))
+	errorMap.Delete(int64(id))
+	captureMap.Delete(int64(id))
+	diagnosticsMap.Delete(int64(id))
+	// A running task removes its own cancel function, only a late marker is left to remove
+	cancelMap.CompareAndDelete(int64(id), cancelled{})
+}
+
+// LastError returns the message of the last error recorded for a browser context
+// Parameters:
+//   - id: Context ID
+//
+// Returns: Error message, or an empty string if no error was recorded
+//
+//export LastError
+func LastError(id C.int64_t) *C.char {
+	message, ok := errorMap.Load(int64(id))
+	if !ok {
+		return C.CString("")
+	}
+	return C.CString(message.(string))
 }
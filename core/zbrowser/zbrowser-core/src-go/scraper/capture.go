@@ -0,0 +1,206 @@
+package scraper
+
+import (
+	"context"
+	"fmt"
+	"regexp"
+	"sync"
+
+	"github.com/chromedp/cdproto/cdp"
+	"github.com/chromedp/cdproto/network"
+	"github.com/chromedp/chromedp"
+)
+
+// Response represents a captured network response with its body
+type Response struct {
+	URL      string            `json:"url"`      // The requested URL
+	Method   string            `json:"method"`   // HTTP method (GET, POST, etc.)
+	Status   int64             `json:"status"`   // HTTP status code of the response
+	MimeType string            `json:"mimeType"` // MIME type reported by the browser
+	Headers  map[string]string `json:"headers"`  // Response headers as key-value pairs
+	Body     string            `json:"body"`     // Decoded response body
+}
+
+// Capture records the responses whose URL matches one of its patterns
+// Bodies are fetched once the browser has finished loading them
+type Capture struct {
+	mu        sync.Mutex
+	patterns  []*regexp.Regexp
+	pending   map[network.RequestID]*Response // Matching responses whose body is still loading
+	methods   map[network.RequestID]string    // Methods of requests seen since the capture started
+	responses []Response                      // Completed responses not yet consumed
+	changed   chan struct{}                   // Closed and replaced every time a response completes
+}
+
+// NewCapture enables the network domain and starts listening for responses on the given context
+// The listener is removed when ctx is cancelled
+//
+// Parameters:
+//   - ctx: Chrome context of the task
+//
+// Returns:
+//   - The capture, or an error if the network domain could not be enabled
+func NewCapture(ctx context.Context) (*Capture, error) {
+	c := &Capture{
+		pending: make(map[network.RequestID]*Response),
+		methods: make(map[network.RequestID]string),
+		changed: make(chan struct{}),
+	}
+
+	if err := chromedp.Run(ctx, network.Enable()); err != nil {
+		return nil, err
+	}
+
+	chromedp.ListenTarget(ctx, c.listener(ctx))
+	return c, nil
+}
+
+// AddPattern starts capturing responses whose URL matches the regular expression
+func (c *Capture) AddPattern(pattern string) error {
+	re, err := regexp.Compile(pattern)
+	if err != nil {
+		return err
+	}
+
+	c.mu.Lock()
+	defer c.mu.Unlock()
+
+	for _, p := range c.patterns {
+		if p.String() == re.String() {
+			return nil
+		}
+	}
+	c.patterns = append(c.patterns, re)
+	return nil
+}
+
+// Responses returns and clears the completed responses captured so far
+func (c *Capture) Responses() []Response {
+	c.mu.Lock()
+	defer c.mu.Unlock()
+
+	responses := c.responses
+	c.responses = nil
+	if responses == nil {
+		responses = []Response{}
+	}
+	return responses
+}
+
+// Wait blocks until a response matching the pattern has been captured and returns it
+// Responses captured before the call are considered first
+//
+// Parameters:
+//   - ctx: Context bounding the wait
+//   - pattern: Regular expression the response URL must match
+//
+// Returns:
+//   - The first matching response, removed from the capture buffer
+func (c *Capture) Wait(ctx context.Context, pattern string) (Response, error) {
+	if err := c.AddPattern(pattern); err != nil {
+		return Response{}, err
+	}
+	re := regexp.MustCompile(pattern)
+
+	for {
+		c.mu.Lock()
+		for i, response := range c.responses {
+			if re.MatchString(response.URL) {
+				c.responses = append(c.responses[:i], c.responses[i+1:]...)
+				c.mu.Unlock()
+				return response, nil
+			}
+		}
+		changed := c.changed
+		c.mu.Unlock()
+
+		select {
+		case <-changed:
+		case <-ctx.Done():
+			return Response{}, ctx.Err()
+		}
+	}
+}
+
+// matches reports whether the URL matches any capture pattern
+// Must be called with the lock held
+func (c *Capture) matches(url string) bool {
+	for _, p := range c.patterns {
+		if p.MatchString(url) {
+			return true
+		}
+	}
+	return false
+}
+
+// listener handles the network events needed to capture responses
+func (c *Capture) listener(ctx context.Context) func(event interface{}) {
+	return func(event interface{}) {
+		switch ev := event.(type) {
+		case *network.EventRequestWillBeSent:
+			c.mu.Lock()
+			c.methods[ev.RequestID] = ev.Request.Method
+			c.mu.Unlock()
+
+		case *network.EventResponseReceived:
+			c.mu.Lock()
+			defer c.mu.Unlock()
+
+			if !c.matches(ev.Response.URL) {
+				delete(c.methods, ev.RequestID)
+				return
+			}
+
+			// Extract response headers from the event
+			headers := make(map[string]string)
+			for name, value := range ev.Response.Headers {
+				headers[name] = fmt.Sprint(value)
+			}
+
+			c.pending[ev.RequestID] = &Response{
+				URL:      ev.Response.URL,
+				Method:   c.methods[ev.RequestID],
+				Status:   ev.Response.Status,
+				MimeType: ev.Response.MimeType,
+				Headers:  headers,
+			}
+			delete(c.methods, ev.RequestID)
+
+		case *network.EventLoadingFinished:
+			c.mu.Lock()
+			response, ok := c.pending[ev.RequestID]
+			delete(c.pending, ev.RequestID)
+			c.mu.Unlock()
+
+			if !ok {
+				return
+			}
+
+			// Fetch the body in a separate goroutine, event handlers must not block
+			go func() {
+				executor := cdp.WithExecutor(ctx, chromedp.FromContext(ctx).Target)
+				body, err := network.GetResponseBody(ev.RequestID).Do(executor)
+				if err == nil {
+					response.Body = string(body)
+				}
+				c.complete(*response)
+			}()
+
+		case *network.EventLoadingFailed:
+			c.mu.Lock()
+			delete(c.pending, ev.RequestID)
+			delete(c.methods, ev.RequestID)
+			c.mu.Unlock()
+		}
+	}
+}
+
+// complete stores a finished response and wakes up the waiters
+func (c *Capture) complete(response Response) {
+	c.mu.Lock()
+	defer c.mu.Unlock()
+
+	c.responses = append(c.responses, response)
+	close(c.changed)
+	c.changed = make(chan struct{})
+}
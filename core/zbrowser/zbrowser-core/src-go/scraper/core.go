@@ -3,6 +3,7 @@ package scraper
 import (
 	"context"
 	"sync"
+	"sync/atomic"
 
 	"github.com/chromedp/cdproto/network"
 	"github.com/chromedp/chromedp"
@@ -13,11 +14,12 @@ type Config struct {
 	Workers        int                             // Number of concurrent worker goroutines
 	Opts           []func(*chromedp.ExecAllocator) // Additional Chrome options
 	BlockResources []network.ResourceType          // Resources to block during scraping (e.g., images, stylesheets)
-	Url            *string                         // Optional remote Chrome instance URL
+	Url            *string                         // Optional remote Chrome URL, either a DevTools HTTP endpoint or a WebSocket URL
 }
 
 // Task represents a scraping task to be executed
 type Task struct {
+	Ctx    context.Context                    // Caller context, cancelling it aborts the task
 	Func   func(context.Context) (any, error) // Function to execute with Chrome context
 	Result chan Result[any]                   // Channel to receive the result
 }
@@ -32,10 +34,14 @@ type Result[T any] struct {
 type Scraper struct {
 	blockResources map[network.ResourceType]struct{} // Set of resources to block
 
-	Config Config         // Scraper configuration
-	tasks  chan Task      // Channel for incoming tasks
-	close  chan struct{}  // Channel to signal shutdown
-	taskWg sync.WaitGroup // WaitGroup to track active tasks
+	Config Config                 // Scraper configuration
+	Pool   *Pool                  // Warm tabs kept between the tasks of a session
+	tasks  chan Task              // Channel for incoming tasks
+	close  chan struct{}          // Channel to signal shutdown
+	taskWg sync.WaitGroup         // WaitGroup to track active tasks
+	tabs   []*tab                 // Browser tab of each worker
+	limits atomic.Pointer[Limits] // Resource limits of each task
+	stats  Stats                  // Browser failures recovered from
 
 	allocCtx    context.Context    // Chrome allocator context
 	allocCancel context.CancelFunc // Function to cancel the allocator
@@ -54,6 +60,8 @@ func New(config Config) *Scraper {
 		tasks:  make(chan Task, config.Workers),
 		close:  make(chan struct{}),
 	}
+	s.Pool = newPool(s)
+	s.limits.Store(&Limits{})
 
 	// Convert blocked resources slice to a map for O(1) lookup
 	if len(config.BlockResources) > 0 {
@@ -80,10 +88,8 @@ func New(config Config) *Scraper {
 
 	// Initialize Chrome allocator - either remote or local
 	if config.Url != nil {
-		// Connect to remote Chrome instance (e.g., running in Docker)
-		allocCtx, allocCancel := chromedp.NewRemoteAllocator(context.Background(), *config.Url, chromedp.NoModifyURL)
-		s.allocCtx = allocCtx
-		s.allocCancel = allocCancel
+		// Remote tabs create their own allocator, so they can reconnect when the browser goes away
+		s.allocCtx, s.allocCancel = context.WithCancel(context.Background())
 	} else {
 		// Launch local Chrome instance
 		allocCtx, allocCancel := chromedp.NewExecAllocator(context.Background(), defaultOpts...)
@@ -98,21 +104,10 @@ func New(config Config) *Scraper {
 
 // startWorkers initializes and starts the worker goroutines that execute tasks
 func (s *Scraper) startWorkers() {
-	// Create communication channels for each worker
-	requests := make([]chan Request, s.Config.Workers)
-	doneCollecting := make([]chan bool, s.Config.Workers)
-
-	// Create Chrome contexts and cancellation functions for each worker
-	ctxPool := make([]context.Context, s.Config.Workers)
-	cancels := make([]context.CancelFunc, s.Config.Workers)
-
-	// Initialize contexts and set up network request inspection for each worker
+	// Create the browser tab of each worker, with request interception for blocked resources
+	s.tabs = make([]*tab, s.Config.Workers)
 	for i := 0; i < s.Config.Workers; i++ {
-		requests[i] = make(chan Request, 1)
-		doneCollecting[i] = make(chan bool, 1)
-		ctxPool[i], cancels[i] = chromedp.NewContext(s.allocCtx)
-		// Set up network request interception to block specified resources
-		chromedp.ListenTarget(ctxPool[i], Inspector(ctxPool[i], s.blockResources, requests[i]))
+		s.tabs[i] = s.newTab()
 	}
 
 	// Start worker goroutines
@@ -121,14 +116,48 @@ func (s *Scraper) startWorkers() {
 			for {
 				select {
 				case task := <-s.tasks:
-					// Execute the task using the worker's Chrome context
-					result, err := task.Func(ctxPool[idx])
-					close(doneCollecting[idx])
+					// Skip tasks that were cancelled while waiting in the queue
+					if err := task.Ctx.Err(); err != nil {
+						task.Result <- Result[any]{Err: err}
+						s.taskWg.Done()
+						continue
+					}
+
+					// Wait for the tab while its browser is launched or reconnecting
+					tabCtx, err := s.tabs[idx].acquire(task.Ctx)
+					if err != nil {
+						task.Result <- Result[any]{Err: err}
+						s.taskWg.Done()
+						continue
+					}
+
+					// Execute the task using a child of the worker's Chrome context
+					// Cancelling the child aborts pending browser actions without closing the tab
+					// Losing the browser cancels it with ErrDisconnected or ErrCrashed as cause
+					taskCtx, stop := context.WithCancelCause(tabCtx)
+					release := context.AfterFunc(task.Ctx, func() { stop(context.Cause(task.Ctx)) })
+					exceeded := s.Limits().Watch(taskCtx, stop)
+					result, err := task.Func(taskCtx)
+					release()
+					stop(context.Canceled)
+
+					// A tab that went over a limit is replaced, since the page may still hold
+					// its memory or keep the renderer busy
+					if cause := exceeded(); cause != nil {
+						s.stats.limitKills.Add(1)
+						s.tabs[idx].recycle(tabCtx, cause)
+						if err == nil {
+							err = cause
+						}
+					}
+
+					// Remove the URL patterns and init scripts of the task, the tab runs the next one
+					resetInterception(tabCtx)
 
 					// Clean up the browser state after successful execution
 					if err == nil {
 						// Clear context to prevent data leakage between tasks
-						chromedp.Run(ctxPool[idx],
+						chromedp.Run(tabCtx,
 							chromedp.ActionFunc(func(ctx context.Context) error {
 								// Clear browser cookies
 								network.ClearBrowserCookies().Do(ctx)
@@ -148,10 +177,9 @@ func (s *Scraper) startWorkers() {
 
 					// Mark task as complete
 					s.taskWg.Done()
-					doneCollecting[idx] = make(chan bool, 1)
 				case <-s.close:
 					// Shutdown signal received - clean up and exit
-					cancels[idx]()
+					s.tabs[idx].close()
 					return
 				}
 			}
@@ -162,10 +190,25 @@ func (s *Scraper) startWorkers() {
 // Execute submits a task to be executed by one of the worker goroutines
 // Returns the result and any error from the task execution
 func (s *Scraper) Execute(task func(context.Context) (any, error)) (any, error) {
+	return s.ExecuteContext(context.Background(), task)
+}
+
+// ExecuteContext submits a task that is aborted when ctx is cancelled
+// A cancelled task is dropped if it is still queued, otherwise its browser actions fail
+// with the context error and the worker is released as soon as the task returns
+func (s *Scraper) ExecuteContext(ctx context.Context, task func(context.Context) (any, error)) (any, error) {
 	resultChan := make(chan Result[any], 1)
-	s.taskWg.Add(1)                                 // Track this task
-	s.tasks <- Task{Func: task, Result: resultChan} // Submit task to workers
-	result := <-resultChan                          // Wait for result
+	s.taskWg.Add(1) // Track this task
+
+	// Submit task to workers unless the caller gives up while the queue is full
+	select {
+	case s.tasks <- Task{Ctx: ctx, Func: task, Result: resultChan}:
+	case <-ctx.Done():
+		s.taskWg.Done()
+		return nil, ctx.Err()
+	}
+
+	result := <-resultChan // Wait for result
 	return result.Value, result.Err
 }
 
@@ -186,10 +229,37 @@ func Execute[T any](scraper *Scraper, task func(context.Context) (T, error)) (T,
 	}
 }
 
+// SetLimits changes the resource limits of the tasks started from now on
+func (s *Scraper) SetLimits(limits Limits) {
+	s.limits.Store(&limits)
+}
+
+// Limits returns the resource limits of the tasks
+func (s *Scraper) Limits() Limits {
+	return *s.limits.Load()
+}
+
+// Stats returns the browser failures the scraper recovered from
+func (s *Scraper) Stats() StatsSnapshot {
+	return s.stats.Snapshot()
+}
+
+// Connected reports whether every worker has a usable browser tab
+// False while a browser is launched, relaunched after a crash or reconnecting
+func (s *Scraper) Connected() bool {
+	for _, t := range s.tabs {
+		if !t.connected() {
+			return false
+		}
+	}
+	return true
+}
+
 // Close gracefully shuts down the scraper
 // Waits for all active tasks to complete before closing
 func (s *Scraper) Close() {
 	s.taskWg.Wait() // Wait for all tasks to complete
+	s.Pool.Close()  // Close the idle warm tabs
 	close(s.close)  // Signal workers to shutdown
 	close(s.tasks)  // Close task channel
 	s.allocCancel() // Cancel Chrome allocator context
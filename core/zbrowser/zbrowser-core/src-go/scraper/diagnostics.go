@@ -0,0 +1,204 @@
+package scraper
+
+import (
+	"context"
+	"encoding/json"
+	"fmt"
+	"strings"
+	"sync"
+
+	"github.com/chromedp/cdproto/network"
+	"github.com/chromedp/cdproto/runtime"
+	"github.com/chromedp/chromedp"
+)
+
+// maxDiagnostics bounds each list of a report, pages logging in a loop must not exhaust memory
+const maxDiagnostics = 200
+
+// ConsoleMessage represents a message written to the browser console by the page
+type ConsoleMessage struct {
+	Level string `json:"level"` // Console method used, e.g. log, warning or error
+	Text  string `json:"text"`  // Arguments of the call joined by spaces
+	URL   string `json:"url"`   // Script that wrote the message, if known
+	Line  int64  `json:"line"`  // One based line of the call in the script, 0 if unknown
+}
+
+// PageError represents an exception the page did not catch
+type PageError struct {
+	Message string `json:"message"` // Exception description, including its stack when available
+	URL     string `json:"url"`     // Script that threw the exception, if known
+	Line    int64  `json:"line"`    // One based line of the exception, 0 if unknown
+	Column  int64  `json:"column"`  // One based column of the exception, 0 if unknown
+}
+
+// FailedRequest represents a network request that failed or got an HTTP error status
+type FailedRequest struct {
+	URL          string `json:"url"`          // The requested URL
+	Method       string `json:"method"`       // HTTP method of the request
+	ResourceType string `json:"resourceType"` // Resource type reported by the browser, e.g. XHR or Script
+	Status       int64  `json:"status"`       // HTTP status code, 0 when no response was received
+	Error        string `json:"error"`        // Network error or status text
+}
+
+// Report holds the diagnostics collected on a tab
+type Report struct {
+	Console        []ConsoleMessage `json:"console"`
+	Exceptions     []PageError      `json:"exceptions"`
+	FailedRequests []FailedRequest  `json:"failedRequests"`
+}
+
+// Diagnostics records console messages, uncaught exceptions and failed requests of a tab
+type Diagnostics struct {
+	mu       sync.Mutex
+	requests map[network.RequestID]*network.Request // Requests seen and still loading
+	report   Report                                 // Diagnostics not yet consumed
+}
+
+// NewDiagnostics enables the network domain and starts recording diagnostics on the given context
+// The listener is removed when ctx is cancelled
+//
+// Parameters:
+//   - ctx: Chrome context of the task
+//
+// Returns:
+//   - The diagnostics, or an error if the network domain could not be enabled
+func NewDiagnostics(ctx context.Context) (*Diagnostics, error) {
+	d := &Diagnostics{requests: make(map[network.RequestID]*network.Request)}
+
+	if err := chromedp.Run(ctx, network.Enable()); err != nil {
+		return nil, err
+	}
+
+	chromedp.ListenTarget(ctx, d.listener)
+	return d, nil
+}
+
+// Report returns and clears the diagnostics recorded so far
+func (d *Diagnostics) Report() Report {
+	d.mu.Lock()
+	defer d.mu.Unlock()
+
+	report := d.report
+	d.report = Report{}
+	if report.Console == nil {
+		report.Console = []ConsoleMessage{}
+	}
+	if report.Exceptions == nil {
+		report.Exceptions = []PageError{}
+	}
+	if report.FailedRequests == nil {
+		report.FailedRequests = []FailedRequest{}
+	}
+	return report
+}
+
+// listener handles the runtime and network events reported as diagnostics
+func (d *Diagnostics) listener(event interface{}) {
+	d.mu.Lock()
+	defer d.mu.Unlock()
+
+	switch ev := event.(type) {
+	case *runtime.EventConsoleAPICalled:
+		if len(d.report.Console) >= maxDiagnostics {
+			return
+		}
+		message := ConsoleMessage{Level: string(ev.Type), Text: consoleText(ev.Args)}
+		if ev.StackTrace != nil && len(ev.StackTrace.CallFrames) > 0 {
+			frame := ev.StackTrace.CallFrames[0]
+			message.URL = frame.URL
+			message.Line = frame.LineNumber + 1
+		}
+		d.report.Console = append(d.report.Console, message)
+
+	case *runtime.EventExceptionThrown:
+		if len(d.report.Exceptions) >= maxDiagnostics || ev.ExceptionDetails == nil {
+			return
+		}
+		details := ev.ExceptionDetails
+		message := details.Text
+		if details.Exception != nil && details.Exception.Description != "" {
+			message = details.Exception.Description
+		}
+		d.report.Exceptions = append(d.report.Exceptions, PageError{
+			Message: message,
+			URL:     details.URL,
+			Line:    details.LineNumber + 1,
+			Column:  details.ColumnNumber + 1,
+		})
+
+	case *network.EventRequestWillBeSent:
+		d.requests[ev.RequestID] = ev.Request
+
+	case *network.EventResponseReceived:
+		request, ok := d.requests[ev.RequestID]
+		if !ok || ev.Response.Status < 400 {
+			return
+		}
+		delete(d.requests, ev.RequestID)
+
+		errorText := ev.Response.StatusText
+		if errorText == "" {
+			errorText = fmt.Sprintf("HTTP %d", ev.Response.Status)
+		}
+		d.addFailedRequest(FailedRequest{
+			URL:          request.URL,
+			Method:       request.Method,
+			ResourceType: string(ev.Type),
+			Status:       ev.Response.Status,
+			Error:        errorText,
+		})
+
+	case *network.EventLoadingFinished:
+		delete(d.requests, ev.RequestID)
+
+	case *network.EventLoadingFailed:
+		request, ok := d.requests[ev.RequestID]
+		delete(d.requests, ev.RequestID)
+		// Requests aborted by the page itself, e.g. when navigating away, are not failures
+		if !ok || ev.Canceled {
+			return
+		}
+
+		errorText := ev.ErrorText
+		if ev.BlockedReason != "" {
+			errorText = fmt.Sprintf("%s (blocked: %s)", errorText, ev.BlockedReason)
+		}
+		d.addFailedRequest(FailedRequest{
+			URL:          request.URL,
+			Method:       request.Method,
+			ResourceType: string(ev.Type),
+			Error:        errorText,
+		})
+	}
+}
+
+// addFailedRequest records a failed request unless the list is full
+// Must be called with the lock held
+func (d *Diagnostics) addFailedRequest(request FailedRequest) {
+	if len(d.report.FailedRequests) < maxDiagnostics {
+		d.report.FailedRequests = append(d.report.FailedRequests, request)
+	}
+}
+
+// consoleText formats the arguments of a console call the way DevTools prints them
+func consoleText(args []*runtime.RemoteObject) string {
+	parts := make([]string, 0, len(args))
+	for _, arg := range args {
+		switch {
+		case arg.Type == runtime.TypeString && len(arg.Value) > 0:
+			var text string
+			if err := json.Unmarshal(arg.Value, &text); err == nil {
+				parts = append(parts, text)
+				continue
+			}
+			parts = append(parts, string(arg.Value))
+		case arg.Description != "":
+			parts = append(parts, arg.Description)
+		case len(arg.Value) > 0:
+			parts = append(parts, string(arg.Value))
+		default:
+			parts = append(parts, string(arg.Type))
+		}
+	}
+	return strings.Join(parts, " ")
+}
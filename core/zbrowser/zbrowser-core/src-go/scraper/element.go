@@ -0,0 +1,277 @@
+package scraper
+
+import (
+	"context"
+	"encoding/json"
+	"errors"
+	"fmt"
+	"sort"
+	"strconv"
+	"sync"
+
+	"github.com/chromedp/cdproto/runtime"
+	"github.com/chromedp/chromedp"
+)
+
+// queryAllFunction returns the elements matching a selector under the node it is called on,
+// or under the document when called without a node
+// Open shadow roots and the documents of same origin iframes are searched as well
+const queryAllFunction = `function(selector) {
+	const root = this && this.nodeType ? this : document;
+	const found = [];
+	const inner = (element) => {
+		const roots = [];
+		if (element.shadowRoot) roots.push(element.shadowRoot);
+		if (element.tagName === 'IFRAME' || element.tagName === 'FRAME') {
+			try {
+				if (element.contentDocument) roots.push(element.contentDocument);
+			} catch (e) {}
+		}
+		return roots;
+	};
+	const visit = (node) => {
+		found.push(...node.querySelectorAll(selector));
+		for (const element of node.querySelectorAll('*')) inner(element).forEach(visit);
+	};
+	visit(root);
+	if (root.nodeType === 1) inner(root).forEach(visit);
+	return found;
+}`
+
+// centerFunction scrolls the element into view and returns its center in page coordinates
+// Elements of same origin iframes are offset by the position of their frames
+const centerFunction = `function() {
+	this.scrollIntoView({block: 'center', inline: 'center'});
+	const rect = this.getBoundingClientRect();
+	if (rect.width === 0 && rect.height === 0) return null;
+	let x = rect.left + rect.width / 2;
+	let y = rect.top + rect.height / 2;
+	for (let view = this.ownerDocument.defaultView; view && view.frameElement; view = view.parent) {
+		const frame = view.frameElement;
+		const frameRect = frame.getBoundingClientRect();
+		x += frameRect.left + frame.clientLeft;
+		y += frameRect.top + frame.clientTop;
+	}
+	return [x, y];
+}`
+
+// ErrInvisible is reported when clicking or hovering an element that takes no space on the page
+var ErrInvisible = errors.New("element is not visible")
+
+// Elements holds the element handles of a tab, mapping handle IDs to remote objects
+// Handles stop working when the page navigates, since Chrome discards their objects
+type Elements struct {
+	mu      sync.Mutex
+	next    int64
+	objects map[int64]runtime.RemoteObjectID
+}
+
+// NewElements creates an empty set of element handles
+func NewElements() *Elements {
+	return &Elements{objects: make(map[int64]runtime.RemoteObjectID)}
+}
+
+// object returns the remote object of a handle
+func (e *Elements) object(id int64) (runtime.RemoteObjectID, error) {
+	e.mu.Lock()
+	defer e.mu.Unlock()
+
+	objectID, ok := e.objects[id]
+	if !ok {
+		return "", fmt.Errorf("element handle %d does not exist", id)
+	}
+	return objectID, nil
+}
+
+// add registers remote objects as handles and returns their IDs
+func (e *Elements) add(objectIDs []runtime.RemoteObjectID) []int64 {
+	e.mu.Lock()
+	defer e.mu.Unlock()
+
+	ids := make([]int64, len(objectIDs))
+	for i, objectID := range objectIDs {
+		e.next++
+		e.objects[e.next] = objectID
+		ids[i] = e.next
+	}
+	return ids
+}
+
+// call calls a function on a remote object, or on the page when objectID is empty
+// Arguments are sent as JSON call arguments
+func call(ctx context.Context, objectID runtime.RemoteObjectID, function string, returnByValue bool, args ...any) (*runtime.RemoteObject, error) {
+	if objectID == "" {
+		var obj *runtime.RemoteObject
+		err := chromedp.Run(ctx, chromedp.CallFunctionOn(function, &obj, func(p *runtime.CallFunctionOnParams) *runtime.CallFunctionOnParams {
+			return p.WithReturnByValue(returnByValue)
+		}, args...))
+		return obj, err
+	}
+
+	callArgs := make([]*runtime.CallArgument, len(args))
+	for i, arg := range args {
+		value, err := json.Marshal(arg)
+		if err != nil {
+			return nil, err
+		}
+		callArgs[i] = &runtime.CallArgument{Value: value}
+	}
+
+	var obj *runtime.RemoteObject
+	err := chromedp.Run(ctx, chromedp.ActionFunc(func(ctx context.Context) error {
+		result, exception, err := runtime.CallFunctionOn(function).
+			WithObjectID(objectID).
+			WithArguments(callArgs).
+			WithReturnByValue(returnByValue).
+			Do(ctx)
+		if err != nil {
+			return err
+		}
+		if exception != nil {
+			return exception
+		}
+		obj = result
+		return nil
+	}))
+	return obj, err
+}
+
+// QuerySelectorAll finds the elements matching a CSS selector and returns their handles
+// The search pierces open shadow roots and same origin iframes
+//
+// Parameters:
+//   - ctx: Chrome context of the tab
+//   - parent: Handle of the element to search under, 0 to search the whole page
+//   - selector: CSS selector
+//
+// Returns:
+//   - The handles of the matching elements, in document order within each root
+//   - An error if the selector is invalid or the parent handle is stale
+func (e *Elements) QuerySelectorAll(ctx context.Context, parent int64, selector string) ([]int64, error) {
+	var parentID runtime.RemoteObjectID
+	if parent != 0 {
+		var err error
+		if parentID, err = e.object(parent); err != nil {
+			return nil, err
+		}
+	}
+
+	array, err := call(ctx, parentID, queryAllFunction, false, selector)
+	if err != nil {
+		return nil, err
+	}
+	if array == nil || array.ObjectID == "" {
+		return nil, nil
+	}
+
+	var objectIDs []runtime.RemoteObjectID
+	err = chromedp.Run(ctx, chromedp.ActionFunc(func(ctx context.Context) error {
+		// The array itself is not needed once its items have their own objects
+		defer runtime.ReleaseObject(array.ObjectID).Do(ctx)
+
+		properties, _, _, exception, err := runtime.GetProperties(array.ObjectID).WithOwnProperties(true).Do(ctx)
+		if err != nil {
+			return err
+		}
+		if exception != nil {
+			return exception
+		}
+
+		type item struct {
+			index    int
+			objectID runtime.RemoteObjectID
+		}
+		var items []item
+		for _, property := range properties {
+			index, err := strconv.Atoi(property.Name)
+			if err != nil || property.Value == nil || property.Value.ObjectID == "" {
+				continue
+			}
+			items = append(items, item{index, property.Value.ObjectID})
+		}
+		sort.Slice(items, func(i, j int) bool { return items[i].index < items[j].index })
+
+		for _, item := range items {
+			objectIDs = append(objectIDs, item.objectID)
+		}
+		return nil
+	}))
+	if err != nil {
+		return nil, err
+	}
+
+	return e.add(objectIDs), nil
+}
+
+// Call calls a JavaScript function with the element of a handle as this
+//
+// Parameters:
+//   - ctx: Chrome context of the tab
+//   - id: Element handle
+//   - function: JavaScript function declaration
+//   - args: Arguments of the function
+//
+// Returns:
+//   - The result of the function serialized as JSON, "null" for undefined
+func (e *Elements) Call(ctx context.Context, id int64, function string, args ...any) (string, error) {
+	objectID, err := e.object(id)
+	if err != nil {
+		return "", err
+	}
+
+	obj, err := call(ctx, objectID, function, true, args...)
+	if err != nil {
+		return "", err
+	}
+	if obj == nil || obj.Type == runtime.TypeUndefined || len(obj.Value) == 0 {
+		return "null", nil
+	}
+	return string(obj.Value), nil
+}
+
+// center scrolls the element of a remote object into view and returns the page coordinates of its center
+func center(ctx context.Context, objectID runtime.RemoteObjectID) (float64, float64, error) {
+	obj, err := call(ctx, objectID, centerFunction, true)
+	if err != nil {
+		return 0, 0, err
+	}
+
+	var point []float64
+	if obj != nil && len(obj.Value) > 0 {
+		if err := json.Unmarshal(obj.Value, &point); err != nil {
+			return 0, 0, err
+		}
+	}
+	if len(point) != 2 {
+		return 0, 0, ErrInvisible
+	}
+	return point[0], point[1], nil
+}
+
+// Click scrolls an element into view and clicks its center with the mouse
+func (e *Elements) Click(ctx context.Context, id int64) error {
+	objectID, err := e.object(id)
+	if err != nil {
+		return err
+	}
+
+	x, y, err := center(ctx, objectID)
+	if err != nil {
+		return err
+	}
+	return chromedp.Run(ctx, chromedp.MouseClickXY(x, y))
+}
+
+// Hover scrolls an element into view and moves the mouse over its center
+func (e *Elements) Hover(ctx context.Context, id int64) error {
+	objectID, err := e.object(id)
+	if err != nil {
+		return err
+	}
+
+	x, y, err := center(ctx, objectID)
+	if err != nil {
+		return err
+	}
+	return MouseMove(ctx, x, y)
+}
@@ -0,0 +1,128 @@
+package scraper
+
+import (
+	"context"
+
+	"github.com/chromedp/cdproto/browser"
+	"github.com/chromedp/cdproto/cdp"
+	"github.com/chromedp/cdproto/emulation"
+	"github.com/chromedp/cdproto/target"
+	"github.com/chromedp/chromedp"
+)
+
+// Viewport holds the screen size and device kind emulated by a tab
+type Viewport struct {
+	Width             int64   `json:"width"`             // Width in CSS pixels
+	Height            int64   `json:"height"`            // Height in CSS pixels
+	DeviceScaleFactor float64 `json:"deviceScaleFactor"` // Ratio between device pixels and CSS pixels
+	Mobile            bool    `json:"mobile"`            // Whether pages see a mobile device
+	Touch             bool    `json:"touch"`             // Whether touch events are emulated
+}
+
+// Geolocation holds the position reported by the Geolocation API
+type Geolocation struct {
+	Latitude  float64 `json:"latitude"`  // Latitude in degrees
+	Longitude float64 `json:"longitude"` // Longitude in degrees
+	Accuracy  float64 `json:"accuracy"`  // Accuracy in meters
+}
+
+// Emulation holds the overrides applied to a tab, nil or empty fields are left unchanged
+type Emulation struct {
+	Viewport       *Viewport    `json:"viewport"`       // Screen size and device kind
+	UserAgent      string       `json:"userAgent"`      // User agent string
+	AcceptLanguage string       `json:"acceptLanguage"` // Accept-Language header and navigator.languages
+	Locale         string       `json:"locale"`         // ICU locale, e.g. es-MX
+	Timezone       string       `json:"timezone"`       // IANA time zone, e.g. America/Mexico_City
+	Geolocation    *Geolocation `json:"geolocation"`    // Position reported to pages
+}
+
+// UserAgentOverride holds the user agent and languages announced by a tab
+// Chrome sets both with a single command, so changing one must resend the other
+type UserAgentOverride struct {
+	UserAgent      string // User agent string, the browser's own when empty
+	AcceptLanguage string // Accept-Language header, Chrome's default when empty
+}
+
+// Apply sends the override to the tab
+func (o UserAgentOverride) Apply(ctx context.Context) error {
+	return chromedp.Run(ctx, chromedp.ActionFunc(func(ctx context.Context) error {
+		userAgent := o.UserAgent
+		if userAgent == "" {
+			c := chromedp.FromContext(ctx)
+			_, _, _, browserUserAgent, _, err := browser.GetVersion().Do(cdp.WithExecutor(ctx, c.Browser))
+			if err != nil {
+				return err
+			}
+			userAgent = browserUserAgent
+		}
+		return emulation.SetUserAgentOverride(userAgent).WithAcceptLanguage(o.AcceptLanguage).Do(ctx)
+	}))
+}
+
+// Emulate applies the overrides of settings to a tab
+//
+// Parameters:
+//   - ctx: Chrome context of the tab
+//   - settings: Overrides to apply
+//   - override: User agent override of the tab, updated with the user agent and languages of settings
+//
+// Returns:
+//   - An error if Chrome rejected a setting
+func Emulate(ctx context.Context, settings Emulation, override *UserAgentOverride) error {
+	var actions []chromedp.Action
+
+	if v := settings.Viewport; v != nil {
+		actions = append(actions,
+			emulation.SetDeviceMetricsOverride(v.Width, v.Height, v.DeviceScaleFactor, v.Mobile),
+			emulation.SetTouchEmulationEnabled(v.Touch),
+		)
+	}
+	if settings.Locale != "" {
+		actions = append(actions, emulation.SetLocaleOverride().WithLocale(settings.Locale))
+	}
+	if settings.Timezone != "" {
+		actions = append(actions, emulation.SetTimezoneOverride(settings.Timezone))
+	}
+	if g := settings.Geolocation; g != nil {
+		actions = append(actions,
+			grantGeolocation(),
+			emulation.SetGeolocationOverride().
+				WithLatitude(g.Latitude).
+				WithLongitude(g.Longitude).
+				WithAccuracy(g.Accuracy),
+		)
+	}
+
+	if err := chromedp.Run(ctx, actions...); err != nil {
+		return err
+	}
+
+	if settings.UserAgent == "" && settings.AcceptLanguage == "" {
+		return nil
+	}
+	if settings.UserAgent != "" {
+		override.UserAgent = settings.UserAgent
+	}
+	if settings.AcceptLanguage != "" {
+		override.AcceptLanguage = settings.AcceptLanguage
+	}
+	return override.Apply(ctx)
+}
+
+// grantGeolocation grants the geolocation permission to every origin of the tab's browser context
+// Without it pages asking for the position never get an answer
+func grantGeolocation() chromedp.Action {
+	return chromedp.ActionFunc(func(ctx context.Context) error {
+		c := chromedp.FromContext(ctx)
+		browserCtx := cdp.WithExecutor(ctx, c.Browser)
+
+		info, err := target.GetTargetInfo().WithTargetID(c.Target.TargetID).Do(browserCtx)
+		if err != nil {
+			return err
+		}
+
+		return browser.GrantPermissions([]browser.PermissionType{browser.PermissionTypeGeolocation}).
+			WithBrowserContextID(info.BrowserContextID).
+			Do(browserCtx)
+	})
+}
@@ -0,0 +1,190 @@
+package scraper
+
+import (
+	"context"
+	"fmt"
+	"strings"
+
+	"github.com/chromedp/cdproto/input"
+	"github.com/chromedp/cdproto/runtime"
+	"github.com/chromedp/chromedp"
+	"github.com/chromedp/chromedp/kb"
+)
+
+// querySelectorFunction returns the first element of the page matching a selector
+const querySelectorFunction = `function(selector) { return document.querySelector(selector); }`
+
+// selectOptionFunction selects the options of a select element whose value or label is given
+// Input and change events are dispatched like when a user picks the options
+const selectOptionFunction = `function(selector, values) {
+	const select = document.querySelector(selector);
+	if (!select || select.tagName !== 'SELECT') throw new Error('No select element matches ' + selector);
+	const selected = [];
+	for (const option of select.options) {
+		option.selected = values.includes(option.value) || values.includes(option.label.trim());
+		if (option.selected) {
+			selected.push(option.value);
+			if (!select.multiple) break;
+		}
+	}
+	select.dispatchEvent(new Event('input', { bubbles: true }));
+	select.dispatchEvent(new Event('change', { bubbles: true }));
+	return selected;
+}`
+
+// viewportCenterFunction returns the center of the viewport
+const viewportCenterFunction = `function() { return [window.innerWidth / 2, window.innerHeight / 2]; }`
+
+// namedKeys maps key names to the characters chromedp dispatches for them
+var namedKeys = map[string]string{
+	"Enter":      kb.Enter,
+	"Tab":        kb.Tab,
+	"Escape":     kb.Escape,
+	"Backspace":  kb.Backspace,
+	"Delete":     kb.Delete,
+	"Space":      " ",
+	"ArrowUp":    kb.ArrowUp,
+	"ArrowDown":  kb.ArrowDown,
+	"ArrowLeft":  kb.ArrowLeft,
+	"ArrowRight": kb.ArrowRight,
+	"Home":       kb.Home,
+	"End":        kb.End,
+	"PageUp":     kb.PageUp,
+	"PageDown":   kb.PageDown,
+}
+
+// modifierKeys maps modifier names to their CDP modifier flags
+var modifierKeys = map[string]input.Modifier{
+	"Alt":     input.ModifierAlt,
+	"Control": input.ModifierCtrl,
+	"Ctrl":    input.ModifierCtrl,
+	"Meta":    input.ModifierMeta,
+	"Command": input.ModifierMeta,
+	"Shift":   input.ModifierShift,
+}
+
+// withElement runs a function with the remote object of the first element matching a selector
+// The object is released once the function returns
+func withElement(ctx context.Context, selector string, f func(objectID runtime.RemoteObjectID) error) error {
+	obj, err := call(ctx, "", querySelectorFunction, false, selector)
+	if err != nil {
+		return err
+	}
+	if obj == nil || obj.ObjectID == "" {
+		return fmt.Errorf("no element matches %s", selector)
+	}
+
+	defer chromedp.Run(ctx, runtime.ReleaseObject(obj.ObjectID))
+	return f(obj.ObjectID)
+}
+
+// MouseMove moves the mouse to a point of the viewport
+//
+// Parameters:
+//   - ctx: Chrome context of the tab
+//   - x, y: Coordinates in CSS pixels from the top left corner of the viewport
+//
+// Returns:
+//   - An error if Chrome rejected the event
+func MouseMove(ctx context.Context, x, y float64) error {
+	return chromedp.Run(ctx, input.DispatchMouseEvent(input.MouseMoved, x, y))
+}
+
+// Hover scrolls the first element matching a selector into view and moves the mouse over its center
+//
+// Parameters:
+//   - ctx: Chrome context of the tab
+//   - selector: CSS selector of the element
+//
+// Returns:
+//   - An error if no element matches or it takes no space on the page
+func Hover(ctx context.Context, selector string) error {
+	return withElement(ctx, selector, func(objectID runtime.RemoteObjectID) error {
+		x, y, err := center(ctx, objectID)
+		if err != nil {
+			return err
+		}
+		return MouseMove(ctx, x, y)
+	})
+}
+
+// PressKey presses and releases a key, optionally with modifiers
+//
+// Parameters:
+//   - ctx: Chrome context of the tab
+//   - key: Key name or character, with modifiers joined by "+", e.g. "Enter", "a" or "Control+Shift+K"
+//
+// Returns:
+//   - An error if a key name is unknown or Chrome rejected the events
+func PressKey(ctx context.Context, key string) error {
+	parts := strings.Split(key, "+")
+	name := parts[len(parts)-1]
+	// A trailing "+" means the plus key itself, as in "Control++"
+	if name == "" && len(parts) > 1 {
+		parts = parts[:len(parts)-1]
+		parts[len(parts)-1] = "+"
+		name = "+"
+	}
+
+	var modifiers []input.Modifier
+	for _, part := range parts[:len(parts)-1] {
+		modifier, ok := modifierKeys[part]
+		if !ok {
+			return fmt.Errorf("unknown modifier %q in %q", part, key)
+		}
+		modifiers = append(modifiers, modifier)
+	}
+
+	keys, ok := namedKeys[name]
+	if !ok {
+		if len([]rune(name)) != 1 {
+			return fmt.Errorf("unknown key %q", name)
+		}
+		keys = name
+	}
+
+	return chromedp.Run(ctx, chromedp.KeyEvent(keys, chromedp.KeyModifiers(modifiers...)))
+}
+
+// Wheel turns the mouse wheel over the center of the viewport, scrolling whatever is under it
+//
+// Parameters:
+//   - ctx: Chrome context of the tab
+//   - deltaX, deltaY: Scroll distance in CSS pixels, positive values scroll right and down
+//
+// Returns:
+//   - An error if Chrome rejected the event
+func Wheel(ctx context.Context, deltaX, deltaY float64) error {
+	var point []float64
+	err := chromedp.Run(ctx, chromedp.CallFunctionOn(viewportCenterFunction, &point, nil))
+	if err != nil {
+		return err
+	}
+	if len(point) != 2 {
+		return fmt.Errorf("could not read the viewport size")
+	}
+
+	return chromedp.Run(ctx, input.DispatchMouseEvent(input.MouseWheel, point[0], point[1]).
+		WithDeltaX(deltaX).
+		WithDeltaY(deltaY))
+}
+
+// SelectOption selects the options of a select element by value or label
+//
+// Parameters:
+//   - ctx: Chrome context of the tab
+//   - selector: CSS selector of the select element
+//   - values: Values or labels of the options, only the first match counts for single selects
+//
+// Returns:
+//   - The values of the selected options
+//   - An error if no select element matches
+func SelectOption(ctx context.Context, selector string, values []string) ([]string, error) {
+	if values == nil {
+		values = []string{}
+	}
+
+	var selected []string
+	err := chromedp.Run(ctx, chromedp.CallFunctionOn(selectOptionFunction, &selected, nil, selector, values))
+	return selected, err
+}
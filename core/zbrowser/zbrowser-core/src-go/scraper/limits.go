@@ -0,0 +1,128 @@
+package scraper
+
+import (
+	"context"
+	"errors"
+	"fmt"
+	"sync"
+	"sync/atomic"
+	"time"
+
+	"github.com/chromedp/chromedp"
+)
+
+// ErrLimitExceeded is the cause of the tasks stopped because their tab went over a resource limit
+var ErrLimitExceeded = errors.New("resource limit exceeded")
+
+// limitsInterval is the delay between two samples of the JavaScript heap of a running task
+const limitsInterval = 2 * time.Second
+
+// Limits bounds the resources a task may use in its tab
+// Tabs of tasks going over a limit are closed and replaced, since a runaway page
+// keeps its memory or keeps the renderer busy after the task is cancelled
+type Limits struct {
+	MaxHeap     int64         // Maximum JavaScript heap of the page in bytes, 0 for no limit
+	MaxTaskTime time.Duration // Maximum duration of a task, 0 for no limit
+}
+
+// Watch stops a task when its tab goes over the limits
+// The heap of the page is sampled every limitsInterval, samples that fail are ignored
+//
+// Parameters:
+//   - ctx: Chrome context of the task
+//   - stop: Cancels the task with the exceeded limit as cause
+//
+// Returns:
+//   - A function ending the watch, that returns the exceeded limit wrapping ErrLimitExceeded or nil
+func (l Limits) Watch(ctx context.Context, stop context.CancelCauseFunc) func() error {
+	if l.MaxHeap <= 0 && l.MaxTaskTime <= 0 {
+		return func() error { return nil }
+	}
+
+	var (
+		mu       sync.Mutex
+		exceeded error
+	)
+	exceed := func(err error) {
+		mu.Lock()
+		exceeded = err
+		mu.Unlock()
+		stop(err)
+	}
+
+	done := make(chan struct{})
+	go func() {
+		var deadline, sample <-chan time.Time
+		if l.MaxTaskTime > 0 {
+			timer := time.NewTimer(l.MaxTaskTime)
+			defer timer.Stop()
+			deadline = timer.C
+		}
+		if l.MaxHeap > 0 {
+			ticker := time.NewTicker(limitsInterval)
+			defer ticker.Stop()
+			sample = ticker.C
+		}
+
+		for {
+			select {
+			case <-done:
+				return
+			case <-ctx.Done():
+				return
+			case <-deadline:
+				exceed(fmt.Errorf("%w: task ran for more than %v", ErrLimitExceeded, l.MaxTaskTime))
+				return
+			case <-sample:
+				if used, err := heapUsage(ctx); err == nil && used > l.MaxHeap {
+					exceed(fmt.Errorf("%w: page uses %d MB of JavaScript heap, the limit is %d MB",
+						ErrLimitExceeded, used>>20, l.MaxHeap>>20))
+					return
+				}
+			}
+		}
+	}()
+
+	return func() error {
+		close(done)
+		mu.Lock()
+		defer mu.Unlock()
+		return exceeded
+	}
+}
+
+// heapUsage returns the JavaScript heap used by the page of a tab in bytes
+func heapUsage(ctx context.Context) (int64, error) {
+	sampleCtx, cancel := context.WithTimeout(ctx, healthTimeout)
+	defer cancel()
+
+	var used float64
+	err := chromedp.Run(sampleCtx, chromedp.Evaluate("performance.memory.usedJSHeapSize", &used))
+	return int64(used), err
+}
+
+// Stats counts the browser failures a scraper recovered from
+type Stats struct {
+	crashes     atomic.Int64 // Browsers or renderers that crashed or stopped answering
+	disconnects atomic.Int64 // Connections to a remote browser that were lost
+	restarts    atomic.Int64 // Tabs reopened after a crash, a lost connection or a limit
+	limitKills  atomic.Int64 // Tabs closed because a task went over a resource limit
+}
+
+// StatsSnapshot holds the values of the counters of Stats at a point in time
+type StatsSnapshot struct {
+	Crashes     int64 `json:"crashes"`
+	Disconnects int64 `json:"disconnects"`
+	Restarts    int64 `json:"restarts"`
+	LimitKills  int64 `json:"limitKills"`
+}
+
+// Snapshot reads the counters
+func (s *Stats) Snapshot() StatsSnapshot {
+	return StatsSnapshot{
+		Crashes:     s.crashes.Load(),
+		Disconnects: s.disconnects.Load(),
+		Restarts:    s.restarts.Load(),
+		LimitKills:  s.limitKills.Load(),
+	}
+}
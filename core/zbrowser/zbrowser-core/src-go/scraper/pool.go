@@ -0,0 +1,262 @@
+package scraper
+
+import (
+	"context"
+	"errors"
+	"sync"
+
+	"github.com/chromedp/chromedp"
+)
+
+// Default limits of the pool of warm tabs
+const (
+	defaultMaxUses = 50 // Tasks a warm tab runs before it is replaced by a fresh one
+	defaultMaxIdle = 4  // Idle warm tabs kept for each key
+)
+
+// errPoolClosed is returned for tasks asking for a warm tab after the scraper was closed
+var errPoolClosed = errors.New("context pool is closed")
+
+// Warm is a tab kept open between the tasks of a key, usually a session like platform:account
+// Its cookies, overrides and proxy survive from one task to the next, and it lives in its own
+// browser context, so warm tabs of different keys share no state
+type Warm struct {
+	Key   string // Key the tab is pooled under
+	Uses  int    // Number of tasks that ran in the tab before the current one
+	State any    // Data the caller keeps with the tab between tasks, such as its user agent override
+
+	tab      context.Context         // Context of the tab
+	close    func()                  // Closes the tab and disposes its browser context
+	proxy    Proxy                   // Proxy the tab sends its traffic through, the zero value for none
+	discard  bool                    // Set when the tab must be closed once the task ends
+	limits   Limits                  // Limits of the page of the tab while a task runs
+	task     context.Context         // Context of the current task
+	ctx      context.Context         // Context of the tab bound to the current task
+	stop     context.CancelCauseFunc // Cancels ctx
+	release  func() bool             // Stops cancelling ctx with the task
+	exceeded func() error            // Stops watching the limits of the tab
+}
+
+// bind creates the context of the tab used by a task
+// It is cancelled with the task and carries the same cause, without closing the tab,
+// or with ErrLimitExceeded when the page goes over its limits
+func (w *Warm) bind(task context.Context) {
+	w.task = task
+	w.ctx, w.stop = context.WithCancelCause(w.tab)
+	w.release = context.AfterFunc(task, func() { w.stop(context.Cause(task)) })
+	w.exceeded = w.limits.Watch(w.ctx, w.stop)
+}
+
+// unbind cancels the context of the current task, leaving the tab open
+// Returns the limit the page went over during the task, nil if none
+func (w *Warm) unbind() error {
+	if w.stop == nil {
+		return nil
+	}
+
+	exceeded := w.exceeded()
+	w.release()
+	w.stop(context.Canceled)
+	w.stop = nil
+	return exceeded
+}
+
+// Context returns the context of the tab bound to the current task
+func (w *Warm) Context() context.Context {
+	return w.ctx
+}
+
+// Discard marks the tab to be closed instead of returned to the pool once the task ends
+func (w *Warm) Discard() {
+	w.discard = true
+}
+
+// SetProxy moves the tab to a new tab sending its traffic through a proxy, unless it already does
+// The new tab replaces the old one in the pool and starts without its cookies
+//
+// Parameters:
+//   - proxy: Proxy server and optional credentials
+//
+// Returns:
+//   - The context of the tab bound to the current task
+//   - An error if the new tab could not be opened, the old tab is kept in that case
+func (w *Warm) SetProxy(proxy Proxy) (context.Context, error) {
+	if proxy == w.proxy {
+		return w.ctx, nil
+	}
+
+	tab, closeTab, err := OpenTab(context.WithoutCancel(w.tab), proxy)
+	if err != nil {
+		return nil, err
+	}
+
+	// A limit exceeded by the old tab went away with it
+	w.unbind()
+	w.close()
+	w.tab, w.close, w.proxy = tab, closeTab, proxy
+	w.bind(w.task)
+	return w.ctx, nil
+}
+
+// Pool keeps warm tabs open between tasks, so the tasks of a session reuse the tab the
+// previous one left logged in instead of restoring the session in a blank tab
+// Tabs are replaced after a number of uses or when a task fails, and checked before reuse
+type Pool struct {
+	mu      sync.Mutex
+	scraper *Scraper           // Scraper of the pool, for its resource limits and stats
+	idle    map[string][]*Warm // Idle tabs of each key, the most recently used last
+	maxUses int                // Tasks a tab runs before it is replaced
+	maxIdle int                // Idle tabs kept for each key
+	closed  bool               // Set once the scraper is closed
+}
+
+// newPool creates an empty pool with the default limits
+func newPool(s *Scraper) *Pool {
+	return &Pool{
+		scraper: s,
+		idle:    make(map[string][]*Warm),
+		maxUses: defaultMaxUses,
+		maxIdle: defaultMaxIdle,
+	}
+}
+
+// SetLimits changes the limits of the pool
+// Values lower than one keep the current limit
+//
+// Parameters:
+//   - maxUses: Tasks a warm tab runs before it is replaced by a fresh one
+//   - maxIdle: Idle warm tabs kept for each key
+func (p *Pool) SetLimits(maxUses, maxIdle int) {
+	p.mu.Lock()
+	defer p.mu.Unlock()
+
+	if maxUses > 0 {
+		p.maxUses = maxUses
+	}
+	if maxIdle > 0 {
+		p.maxIdle = maxIdle
+	}
+}
+
+// Get takes a warm tab of a key for a task
+// Idle tabs that no longer answer are closed, a new tab is opened when no idle tab is left
+// The heap limit of the scraper applies to the page of the tab, the time limit is already
+// enforced by the worker running the task
+//
+// Parameters:
+//   - ctx: Chrome context of the task, new tabs are opened on its browser
+//   - key: Key of the tab
+//
+// Returns:
+//   - The tab, bound to ctx until it is returned with Put
+//   - An error if no tab could be opened or the pool is closed
+func (p *Pool) Get(ctx context.Context, key string) (*Warm, error) {
+	limits := Limits{MaxHeap: p.scraper.Limits().MaxHeap}
+	for {
+		w, err := p.take(key)
+		if err != nil {
+			return nil, err
+		}
+		if w == nil {
+			break
+		}
+		if err := check(w.tab); err == nil {
+			w.limits = limits
+			w.bind(ctx)
+			return w, nil
+		}
+		w.close()
+	}
+
+	// The tab outlives the task, so it must not be cancelled with it
+	tab, closeTab, err := OpenTab(context.WithoutCancel(ctx), Proxy{})
+	if err != nil {
+		return nil, err
+	}
+
+	w := &Warm{Key: key, tab: tab, close: closeTab, limits: limits}
+	w.bind(ctx)
+	return w, nil
+}
+
+// take removes the most recently used idle tab of a key from the pool
+// Returns nil when the key has no idle tab
+func (p *Pool) take(key string) (*Warm, error) {
+	p.mu.Lock()
+	defer p.mu.Unlock()
+
+	if p.closed {
+		return nil, errPoolClosed
+	}
+
+	tabs := p.idle[key]
+	if len(tabs) == 0 {
+		return nil, nil
+	}
+
+	w := tabs[len(tabs)-1]
+	if len(tabs) == 1 {
+		delete(p.idle, key)
+	} else {
+		p.idle[key] = tabs[:len(tabs)-1]
+	}
+	return w, nil
+}
+
+// Put returns a warm tab to the pool once its task is done
+// The tab is closed instead when it was discarded, the task failed, its page went over the
+// heap limit, the URL patterns or init scripts of the task could not be removed, it reached
+// the maximum number of uses or its key already has enough idle tabs
+//
+// Parameters:
+//   - w: Tab taken with Get
+//   - err: Error of the task, nil if it succeeded
+func (p *Pool) Put(w *Warm, err error) {
+	exceeded := w.unbind()
+	w.Uses++
+	if exceeded != nil {
+		p.scraper.stats.limitKills.Add(1)
+	}
+
+	// The next task of the key starts without the URL patterns and init scripts of this one
+	if err == nil && exceeded == nil {
+		err = resetInterception(w.tab)
+	}
+
+	p.mu.Lock()
+	keep := !p.closed && !w.discard && err == nil && exceeded == nil &&
+		w.Uses < p.maxUses && len(p.idle[w.Key]) < p.maxIdle
+	if keep {
+		p.idle[w.Key] = append(p.idle[w.Key], w)
+	}
+	p.mu.Unlock()
+
+	if !keep {
+		w.close()
+	}
+}
+
+// Close closes the idle tabs of the pool
+// Tabs in use are closed when their task returns them
+func (p *Pool) Close() {
+	p.mu.Lock()
+	idle := p.idle
+	p.idle = make(map[string][]*Warm)
+	p.closed = true
+	p.mu.Unlock()
+
+	for _, tabs := range idle {
+		for _, w := range tabs {
+			w.close()
+		}
+	}
+}
+
+// check verifies that a tab still answers scripts within healthTimeout
+func check(tab context.Context) error {
+	ctx, cancel := context.WithTimeout(tab, healthTimeout)
+	defer cancel()
+
+	var state string
+	return chromedp.Run(ctx, chromedp.Evaluate("document.readyState", &state))
+}
@@ -0,0 +1,84 @@
+package scraper
+
+import (
+	"context"
+	"errors"
+	"strings"
+	"time"
+
+	"github.com/chromedp/cdproto/cdp"
+	"github.com/chromedp/cdproto/target"
+	"github.com/chromedp/chromedp"
+)
+
+// disposeTimeout bounds the disposal of the browser context of a tab
+const disposeTimeout = 5 * time.Second
+
+// Proxy holds the proxy server a tab sends its traffic through
+type Proxy struct {
+	Server   string // Proxy URL without credentials, e.g. http://host:port or socks5://host:port
+	Username string // Optional username, only supported by HTTP proxies
+	Password string // Password of the username
+}
+
+// OpenTab opens a tab in a new browser context, that sends its traffic through a proxy when one is given
+// The browser context has its own cookies and cache, so tabs using different proxies share no state
+// The tab keeps the blocked resources, URL patterns and init scripts of the tab of ctx
+//
+// Parameters:
+//   - ctx: Chrome context of the task, the tab is closed when it is cancelled
+//   - proxy: Proxy server and optional credentials, the zero value for a direct connection
+//
+// Returns:
+//   - The context of the new tab
+//   - A function closing the tab and disposing its browser context
+func OpenTab(ctx context.Context, proxy Proxy) (context.Context, func(), error) {
+	if proxy.Username != "" && strings.HasPrefix(proxy.Server, "socks5://") {
+		return nil, nil, errors.New("chrome does not support credentials for SOCKS5 proxies")
+	}
+
+	c := chromedp.FromContext(ctx)
+	if c == nil || c.Browser == nil {
+		return nil, nil, errors.New("browser is not connected")
+	}
+	browserCtx := cdp.WithExecutor(ctx, c.Browser)
+
+	create := target.CreateBrowserContext()
+	if proxy.Server != "" {
+		create = create.WithProxyServer(proxy.Server)
+	}
+	id, err := create.Do(browserCtx)
+	if err != nil {
+		return nil, nil, err
+	}
+
+	// The browser context outlives ctx, so it is disposed with a context of its own
+	dispose := func() {
+		disposeCtx, cancel := context.WithTimeout(context.Background(), disposeTimeout)
+		defer cancel()
+		target.DisposeBrowserContext(id).Do(cdp.WithExecutor(disposeCtx, c.Browser))
+	}
+
+	targetID, err := target.CreateTarget("about:blank").WithBrowserContextID(id).Do(browserCtx)
+	if err != nil {
+		dispose()
+		return nil, nil, err
+	}
+
+	tabCtx, cancel := chromedp.NewContext(ctx, chromedp.WithTargetID(targetID))
+	closeTab := func() {
+		cancel()
+		dispose()
+	}
+
+	// The tab blocks what the tab it replaces blocked, and gives the proxy credentials
+	// when Chrome asks for them
+	interceptor := interceptorOf(ctx).fork(proxy)
+	tabCtx = interceptor.attach(tabCtx)
+
+	if err := chromedp.Run(tabCtx, interceptor.enable()); err != nil {
+		closeTab()
+		return nil, nil, err
+	}
+	return tabCtx, closeTab, nil
+}
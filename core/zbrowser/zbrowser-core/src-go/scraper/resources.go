@@ -3,81 +3,270 @@ package scraper
 
 import (
 	"context"
+	"errors"
+	"fmt"
+	"regexp"
+	"sync"
 
 	"github.com/chromedp/cdproto/cdp"
 	"github.com/chromedp/cdproto/fetch"
 	"github.com/chromedp/cdproto/network"
+	"github.com/chromedp/cdproto/page"
 	"github.com/chromedp/chromedp"
 )
 
-// Request represents a captured network request with its details
-type Request struct {
-	URL                string            // The requested URL
-	Method             string            // HTTP method (GET, POST, etc.)
-	ResponseHeaders    map[string]string // Response headers as key-value pairs
-	ResponseStatusCode int64             // HTTP status code of the response
-	Payload            string            // Request payload/body data
+// Interceptor pauses the requests of a tab to block them or to answer the challenges of its proxy
+// Resource types are blocked in every task of the tab, URL patterns and init scripts
+// only until the task that added them ends
+type Interceptor struct {
+	mu        sync.Mutex
+	resources map[network.ResourceType]struct{} // Resource types blocked in every task
+	proxy     Proxy                             // Proxy whose authentication challenges are answered
+	rules     []*regexp.Regexp                  // URL patterns blocked in the current task
+	scripts   []initScript                      // Scripts added by the current task
+	enabled   bool                              // Set while requests of the tab are paused
 }
 
-// Inspector creates an event handler function that intercepts and processes network requests
-// It can block specific resource types and captures request details for analysis
+// initScript is a script evaluated before the scripts of every page loaded in a tab
+type initScript struct {
+	id     page.ScriptIdentifier // Identifier given by the browser
+	source string                // Source of the script
+}
+
+// interceptorKey is the key of the interceptor of a tab in its context
+type interceptorKey struct{}
+
+// newInterceptor creates the interceptor of a tab
 //
 // Parameters:
-//   - ctx: Context for the Chrome DevTools Protocol session
-//   - blockResources: Map of resource types to block (images, stylesheets, etc.)
-//   - requests: Channel to send captured request information
+//   - resources: Resource types blocked in every task of the tab
+//   - proxy: Proxy of the tab, its credentials are given when it asks for them
+func newInterceptor(resources map[network.ResourceType]struct{}, proxy Proxy) *Interceptor {
+	return &Interceptor{resources: resources, proxy: proxy}
+}
+
+// interceptorOf returns the interceptor of the tab of a context, nil outside a tab
+func interceptorOf(ctx context.Context) *Interceptor {
+	i, _ := ctx.Value(interceptorKey{}).(*Interceptor)
+	return i
+}
+
+// attach registers the interceptor on a tab that is not running yet
+// Nothing is paused until the action returned by enable runs
+//
+// Returns:
+//   - The tab context, carrying the interceptor for the operations of its tasks
+func (i *Interceptor) attach(ctx context.Context) context.Context {
+	chromedp.ListenTarget(ctx, i.listen(ctx))
+	return context.WithValue(ctx, interceptorKey{}, i)
+}
+
+// fork creates the interceptor of a tab replacing the tab of i in the middle of a task,
+// with its resource types, URL patterns and init scripts
+// A nil interceptor forks into one that blocks nothing
+//
+// Parameters:
+//   - proxy: Proxy of the new tab
+func (i *Interceptor) fork(proxy Proxy) *Interceptor {
+	forked := newInterceptor(nil, proxy)
+	if i == nil {
+		return forked
+	}
+
+	i.mu.Lock()
+	defer i.mu.Unlock()
+
+	forked.resources = i.resources
+	forked.rules = append([]*regexp.Regexp(nil), i.rules...)
+	for _, script := range i.scripts {
+		forked.scripts = append(forked.scripts, initScript{source: script.source})
+	}
+	return forked
+}
+
+// enable adds the inherited init scripts to a new tab and starts pausing its requests
+func (i *Interceptor) enable() chromedp.Action {
+	return chromedp.ActionFunc(func(ctx context.Context) error {
+		i.mu.Lock()
+		defer i.mu.Unlock()
+
+		for idx, script := range i.scripts {
+			id, err := page.AddScriptToEvaluateOnNewDocument(script.source).Do(ctx)
+			if err != nil {
+				return err
+			}
+			i.scripts[idx].id = id
+		}
+		return i.apply(ctx)
+	})
+}
+
+// apply pauses the requests the interceptor has to decide on, or stops pausing when there are none
+// Only the blocked resource types are paused unless URL patterns or proxy credentials need every request
+// The caller must hold the lock
+func (i *Interceptor) apply(ctx context.Context) error {
+	auth := i.proxy.Username != ""
+
+	var patterns []*fetch.RequestPattern
+	if auth || len(i.rules) > 0 {
+		patterns = append(patterns, &fetch.RequestPattern{URLPattern: "*"})
+	} else {
+		for resource := range i.resources {
+			patterns = append(patterns, &fetch.RequestPattern{URLPattern: "*", ResourceType: resource})
+		}
+	}
+
+	if len(patterns) == 0 {
+		if !i.enabled {
+			return nil
+		}
+		i.enabled = false
+		return fetch.Disable().Do(ctx)
+	}
+
+	i.enabled = true
+	return fetch.Enable().WithPatterns(patterns).WithHandleAuthRequests(auth).Do(ctx)
+}
+
+// blocked reports whether a paused request must fail instead of continuing
+func (i *Interceptor) blocked(url string, resource network.ResourceType) bool {
+	i.mu.Lock()
+	defer i.mu.Unlock()
+
+	if _, ok := i.resources[resource]; ok {
+		return true
+	}
+	for _, rule := range i.rules {
+		if rule.MatchString(url) {
+			return true
+		}
+	}
+	return false
+}
+
+// listen creates the event handler of the interceptor
+// Blocked requests fail with a blocked-by-client error, the rest continue unchanged,
+// and challenges of servers other than the proxy get the default response
+//
+// Parameters:
+//   - ctx: Context of the tab
 //
 // Returns:
 //   - A function that handles Chrome DevTools Protocol events
-func Inspector(
-	ctx context.Context,
-	blockResources map[network.ResourceType]struct{},
-	requests chan<- Request,
-) func(event interface{}) {
+func (i *Interceptor) listen(ctx context.Context) func(event interface{}) {
 	return func(event interface{}) {
-		// Handle different types of Chrome DevTools Protocol events
 		switch ev := event.(type) {
 		case *fetch.EventRequestPaused:
 			// Process paused requests in a separate goroutine to avoid blocking
 			go func() {
-				// Get Chrome DevTools Protocol connection from context
 				c := chromedp.FromContext(ctx)
 				ctx := cdp.WithExecutor(ctx, c.Target)
 
-				// Check if this resource type should be blocked
-				if _, ok := blockResources[ev.ResourceType]; ok {
-					// Block the request by failing it with a blocked-by-client error
+				if i.blocked(ev.Request.URL, ev.ResourceType) {
 					fetch.FailRequest(ev.RequestID, network.ErrorReasonBlockedByClient).Do(ctx)
 				} else {
-					// Extract response headers from the event
-					headers := make(map[string]string)
-
-					for _, header := range ev.ResponseHeaders {
-						if header != nil {
-							headers[header.Name] = header.Value
-						}
+					fetch.ContinueRequest(ev.RequestID).Do(ctx)
+				}
+			}()
+		case *fetch.EventAuthRequired:
+			go func() {
+				c := chromedp.FromContext(ctx)
+				response := &fetch.AuthChallengeResponse{
+					Response: fetch.AuthChallengeResponseResponseDefault,
+				}
+				if ev.AuthChallenge != nil && ev.AuthChallenge.Source == fetch.AuthChallengeSourceProxy {
+					response = &fetch.AuthChallengeResponse{
+						Response: fetch.AuthChallengeResponseResponseProvideCredentials,
+						Username: i.proxy.Username,
+						Password: i.proxy.Password,
 					}
+				}
+				fetch.ContinueWithAuth(ev.RequestID, response).Do(cdp.WithExecutor(ctx, c.Target))
+			}()
+		}
+	}
+}
 
-					// Extract POST data payload from the request
-					payload := ""
+// BlockURLs makes the tab of a task fail the requests whose URL matches one of the patterns,
+// until the task ends
+//
+// Parameters:
+//   - ctx: Chrome context of the task
+//   - patterns: Regular expressions matched against the full request URL
+//
+// Returns:
+//   - An error if a pattern is invalid or the tab could not pause its requests
+func BlockURLs(ctx context.Context, patterns []string) error {
+	i := interceptorOf(ctx)
+	if i == nil {
+		return errors.New("context has no tab to block requests in")
+	}
 
-					for _, content := range ev.Request.PostDataEntries {
-						payload += content.Bytes
-					}
+	rules := make([]*regexp.Regexp, 0, len(patterns))
+	for _, pattern := range patterns {
+		rule, err := regexp.Compile(pattern)
+		if err != nil {
+			return fmt.Errorf("invalid URL pattern %q: %w", pattern, err)
+		}
+		rules = append(rules, rule)
+	}
 
-					// Send the captured request details to the channel
-					requests <- Request{
-						URL:                ev.Request.URL,
-						Method:             ev.Request.Method,
-						ResponseHeaders:    headers,
-						ResponseStatusCode: ev.ResponseStatusCode,
-						Payload:            payload,
-					}
+	return chromedp.Run(ctx, chromedp.ActionFunc(func(ctx context.Context) error {
+		i.mu.Lock()
+		defer i.mu.Unlock()
 
-					// Allow the request to continue normally
-					fetch.ContinueRequest(ev.RequestID).Do(ctx)
-				}
-			}()
+		i.rules = append(i.rules, rules...)
+		return i.apply(ctx)
+	}))
+}
+
+// AddInitScript makes the tab of a task evaluate a script before the scripts of every page
+// it loads, until the task ends
+// The current page is not affected, the script runs from the next navigation on
+//
+// Parameters:
+//   - ctx: Chrome context of the task
+//   - source: JavaScript source of the script
+func AddInitScript(ctx context.Context, source string) error {
+	i := interceptorOf(ctx)
+	if i == nil {
+		return errors.New("context has no tab to add scripts to")
+	}
+
+	return chromedp.Run(ctx, chromedp.ActionFunc(func(ctx context.Context) error {
+		i.mu.Lock()
+		defer i.mu.Unlock()
+
+		id, err := page.AddScriptToEvaluateOnNewDocument(source).Do(ctx)
+		if err != nil {
+			return err
 		}
+		i.scripts = append(i.scripts, initScript{id: id, source: source})
+		return nil
+	}))
+}
+
+// resetInterception removes the URL patterns and init scripts the last task added to a tab,
+// so the next task of the tab starts without them
+func resetInterception(ctx context.Context) error {
+	i := interceptorOf(ctx)
+	if i == nil {
+		return nil
 	}
+
+	return chromedp.Run(ctx, chromedp.ActionFunc(func(ctx context.Context) error {
+		i.mu.Lock()
+		defer i.mu.Unlock()
+
+		if len(i.rules) == 0 && len(i.scripts) == 0 {
+			return nil
+		}
+		for _, script := range i.scripts {
+			if err := page.RemoveScriptToEvaluateOnNewDocument(script.id).Do(ctx); err != nil {
+				return err
+			}
+		}
+		i.rules, i.scripts = nil, nil
+		return i.apply(ctx)
+	}))
 }
@@ -0,0 +1,64 @@
+package scraper
+
+import (
+	"context"
+	"errors"
+
+	"github.com/chromedp/cdproto/page"
+	"github.com/chromedp/chromedp"
+)
+
+// Clip is a region of the page in CSS pixels from the top left corner of the document
+type Clip struct {
+	X      float64 `json:"x"`
+	Y      float64 `json:"y"`
+	Width  float64 `json:"width"`
+	Height float64 `json:"height"`
+}
+
+// ScreenshotOptions selects the area captured by a screenshot
+// The visible viewport is captured when neither field is set
+type ScreenshotOptions struct {
+	FullPage bool  `json:"fullPage"` // Capture the whole scrollable page
+	Clip     *Clip `json:"clip"`     // Capture only this region, takes precedence over FullPage
+}
+
+// Screenshot captures a PNG image of the page
+//
+// Parameters:
+//   - ctx: Chrome context of the tab
+//   - options: Area to capture
+//
+// Returns:
+//   - The PNG image
+//   - An error if the clip is empty or Chrome could not capture the page
+func Screenshot(ctx context.Context, options ScreenshotOptions) ([]byte, error) {
+	var image []byte
+
+	switch {
+	case options.Clip != nil:
+		clip := options.Clip
+		if clip.Width <= 0 || clip.Height <= 0 {
+			return nil, errors.New("screenshot clip must have a positive size")
+		}
+		err := chromedp.Run(ctx, chromedp.ActionFunc(func(ctx context.Context) error {
+			var err error
+			image, err = page.CaptureScreenshot().
+				WithFormat(page.CaptureScreenshotFormatPng).
+				WithCaptureBeyondViewport(true).
+				WithClip(&page.Viewport{X: clip.X, Y: clip.Y, Width: clip.Width, Height: clip.Height, Scale: 1}).
+				Do(ctx)
+			return err
+		}))
+		return image, err
+
+	case options.FullPage:
+		// A quality of 100 makes chromedp capture a lossless PNG
+		err := chromedp.Run(ctx, chromedp.FullScreenshot(&image, 100))
+		return image, err
+
+	default:
+		err := chromedp.Run(ctx, chromedp.CaptureScreenshot(&image))
+		return image, err
+	}
+}
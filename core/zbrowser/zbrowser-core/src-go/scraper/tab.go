@@ -0,0 +1,321 @@
+package scraper
+
+import (
+	"context"
+	"errors"
+	"strings"
+	"sync"
+	"time"
+
+	"github.com/chromedp/cdproto/browser"
+	"github.com/chromedp/cdproto/cdp"
+	"github.com/chromedp/cdproto/inspector"
+	"github.com/chromedp/chromedp"
+)
+
+// ErrDisconnected is the cause of the contexts cancelled because the remote browser went away
+// It is also returned for tasks that could not get a tab before the reconnect wait expired
+var ErrDisconnected = errors.New("remote browser disconnected")
+
+// ErrCrashed is the cause of the contexts cancelled because the renderer of their tab crashed,
+// or the local browser exited or stopped answering
+var ErrCrashed = errors.New("browser crashed")
+
+// Connection settings of browsers
+const (
+	connectTimeout = 10 * time.Second       // Maximum duration of a connection attempt
+	healthInterval = 10 * time.Second       // Delay between two health checks of a connected tab
+	healthTimeout  = 5 * time.Second        // Maximum duration of a health check
+	healthFailures = 3                      // Consecutive failed health checks after which the tab is lost
+	backoffMin     = 500 * time.Millisecond // Delay before the first reconnect attempt
+	backoffMax     = 30 * time.Second       // Maximum delay between two reconnect attempts
+	reconnectWait  = 30 * time.Second       // Time a queued task waits for a tab before failing
+)
+
+// tab is the browser tab a worker runs its tasks in
+// Tabs are watched by a supervisor that reopens them with exponential backoff when the
+// connection to a remote browser is lost, the browser or the renderer crashes, or a task
+// went over its resource limits. Local tabs relaunch their browser
+type tab struct {
+	scraper *Scraper
+	crashed chan struct{} // Signalled when the renderer of the current tab crashes
+
+	mu     sync.Mutex
+	ctx    context.Context         // Current tab context, nil while disconnected
+	cancel context.CancelCauseFunc // Cancels the current tab with the reason it was closed
+	ready  chan struct{}           // Closed once ctx is set
+}
+
+// newTab creates the tab of a worker
+// Tabs launch or connect to their browser in the background, tasks wait for them in acquire
+func (s *Scraper) newTab() *tab {
+	t := &tab{
+		scraper: s,
+		crashed: make(chan struct{}, 1),
+		ready:   make(chan struct{}),
+	}
+
+	go t.supervise()
+	return t
+}
+
+// context creates a Chrome context on the allocator, with request interception for blocked resources
+// and crash detection
+// The returned function cancels it with a cause, that operations of the context can read with context.Cause
+func (t *tab) context(allocCtx context.Context) (context.Context, context.CancelCauseFunc) {
+	causeCtx, cancelCause := context.WithCancelCause(allocCtx)
+	ctx, cancel := chromedp.NewContext(causeCtx)
+	ctx = newInterceptor(t.scraper.blockResources, Proxy{}).attach(ctx)
+	chromedp.ListenTarget(ctx, func(event interface{}) {
+		if _, ok := event.(*inspector.EventTargetCrashed); ok {
+			select {
+			case t.crashed <- struct{}{}:
+			default:
+			}
+		}
+	})
+
+	return ctx, func(cause error) {
+		cancelCause(cause)
+		cancel()
+	}
+}
+
+// connect opens a tab on the remote browser, or launches a local browser
+// Returns the tab once the browser accepted the connection
+func (t *tab) connect() (context.Context, context.CancelCauseFunc, error) {
+	allocCtx, allocCancel := t.scraper.allocCtx, context.CancelFunc(func() {})
+	if url := t.scraper.Config.Url; url != nil {
+		// DevTools HTTP endpoints are resolved to their WebSocket URL,
+		// WebSocket URLs are used as they are since they may carry a token
+		var opts []chromedp.RemoteAllocatorOption
+		if strings.HasPrefix(*url, "ws://") || strings.HasPrefix(*url, "wss://") {
+			opts = append(opts, chromedp.NoModifyURL)
+		}
+		allocCtx, allocCancel = chromedp.NewRemoteAllocator(t.scraper.allocCtx, *url, opts...)
+	}
+	ctx, cancel := t.context(allocCtx)
+
+	// The first run launches or dials the browser and starts blocking the resources of the scraper,
+	// the timer bounds it since dialing ignores deadlines
+	timer := time.AfterFunc(connectTimeout, func() { cancel(context.DeadlineExceeded) })
+	err := chromedp.Run(ctx, interceptorOf(ctx).enable())
+	if !timer.Stop() && err == nil {
+		err = context.DeadlineExceeded
+	}
+
+	if err != nil {
+		cancel(err)
+		allocCancel()
+		return nil, nil, err
+	}
+
+	return ctx, func(cause error) {
+		cancel(cause)
+		allocCancel()
+	}, nil
+}
+
+// supervise keeps a tab connected until the scraper is closed
+// Failed connection attempts are retried with a delay doubling from backoffMin up to backoffMax
+func (t *tab) supervise() {
+	delay := backoffMin
+	connected := false
+	for {
+		ctx, cancel, err := t.connect()
+		if err == nil {
+			delay = backoffMin
+			if connected {
+				t.scraper.stats.restarts.Add(1)
+			}
+			connected = true
+
+			t.publish(ctx, cancel)
+			cause := t.watch(ctx)
+			if cause == nil || t.closed() {
+				t.close()
+				return
+			}
+
+			switch {
+			case errors.Is(cause, ErrCrashed):
+				t.scraper.stats.crashes.Add(1)
+			case errors.Is(cause, ErrDisconnected):
+				t.scraper.stats.disconnects.Add(1)
+			}
+
+			// In-flight tasks fail with the cause, queued tasks wait for the next tab
+			t.lose(cause)
+			continue
+		}
+
+		select {
+		case <-time.After(delay):
+		case <-t.scraper.close:
+			return
+		}
+		delay = min(delay*2, backoffMax)
+	}
+}
+
+// watch blocks until a tab is lost or the scraper is closed
+// The tab is lost when its WebSocket drops, it fails healthFailures health checks in a row,
+// its renderer crashes or it is recycled
+//
+// Returns:
+//   - The reason the tab was lost, nil if the scraper was closed
+func (t *tab) watch(ctx context.Context) error {
+	ticker := time.NewTicker(healthInterval)
+	defer ticker.Stop()
+
+	lost := chromedp.FromContext(ctx).Browser.LostConnection
+	failures := 0
+	for {
+		select {
+		case <-t.scraper.close:
+			return nil
+		case <-lost:
+			return t.lostCause()
+		case <-t.crashed:
+			return ErrCrashed
+		case <-ctx.Done():
+			if cause := context.Cause(ctx); cause != context.Canceled {
+				return cause
+			}
+			return t.lostCause()
+		case <-ticker.C:
+			if err := ping(ctx); err != nil {
+				failures++
+				if failures >= healthFailures {
+					return t.lostCause()
+				}
+			} else {
+				failures = 0
+			}
+		}
+	}
+}
+
+// lostCause returns the cause of a browser that went away
+// A local browser that exits or stops answering crashed, a remote one is only out of reach
+func (t *tab) lostCause() error {
+	if t.scraper.Config.Url == nil {
+		return ErrCrashed
+	}
+	return ErrDisconnected
+}
+
+// closed reports whether the scraper owning the tab was closed
+func (t *tab) closed() bool {
+	select {
+	case <-t.scraper.close:
+		return true
+	default:
+		return false
+	}
+}
+
+// ping checks that the browser of a tab still answers protocol commands
+func ping(ctx context.Context) error {
+	pingCtx, cancel := context.WithTimeout(ctx, healthTimeout)
+	defer cancel()
+
+	c := chromedp.FromContext(ctx)
+	_, _, _, _, _, err := browser.GetVersion().Do(cdp.WithExecutor(pingCtx, c.Browser))
+	return err
+}
+
+// publish makes a connected tab available to the worker
+func (t *tab) publish(ctx context.Context, cancel context.CancelCauseFunc) {
+	t.mu.Lock()
+	defer t.mu.Unlock()
+
+	// Crashes of the previous tab were already handled
+	select {
+	case <-t.crashed:
+	default:
+	}
+
+	t.ctx = ctx
+	t.cancel = cancel
+	close(t.ready)
+}
+
+// lose cancels the current tab with the reason it was lost and marks the tab as disconnected
+func (t *tab) lose(cause error) {
+	t.mu.Lock()
+	defer t.mu.Unlock()
+
+	t.cancel(cause)
+	t.unpublish()
+}
+
+// recycle closes the current tab so the supervisor replaces it with a fresh one
+//
+// Parameters:
+//   - tabCtx: Tab context the caller used, nothing happens if it was already replaced
+//   - cause: Reason the tab is closed, reported to the operations still running in it
+func (t *tab) recycle(tabCtx context.Context, cause error) {
+	t.mu.Lock()
+	defer t.mu.Unlock()
+
+	if t.ctx == tabCtx {
+		t.cancel(cause)
+		t.unpublish()
+	}
+}
+
+// unpublish makes tasks wait for the next tab, the caller must hold the lock
+func (t *tab) unpublish() {
+	if t.ctx != nil {
+		t.ctx = nil
+		t.ready = make(chan struct{})
+	}
+}
+
+// acquire returns the current tab context, waiting up to reconnectWait while it is reopened
+//
+// Parameters:
+//   - ctx: Context of the task, cancelling it stops the wait
+//
+// Returns:
+//   - The tab context, or ErrDisconnected or ErrCrashed if no tab became available in time
+func (t *tab) acquire(ctx context.Context) (context.Context, error) {
+	timer := time.NewTimer(reconnectWait)
+	defer timer.Stop()
+
+	for {
+		t.mu.Lock()
+		current, ready := t.ctx, t.ready
+		t.mu.Unlock()
+
+		if current != nil {
+			return current, nil
+		}
+
+		select {
+		case <-ready:
+		case <-ctx.Done():
+			return nil, ctx.Err()
+		case <-timer.C:
+			return nil, t.lostCause()
+		}
+	}
+}
+
+// connected reports whether the tab currently has a usable context
+func (t *tab) connected() bool {
+	t.mu.Lock()
+	defer t.mu.Unlock()
+	return t.ctx != nil
+}
+
+// close cancels the current tab when the scraper shuts down
+func (t *tab) close() {
+	t.mu.Lock()
+	defer t.mu.Unlock()
+
+	if t.cancel != nil {
+		t.cancel(context.Canceled)
+	}
+}
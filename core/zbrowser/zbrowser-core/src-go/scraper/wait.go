@@ -0,0 +1,180 @@
+package scraper
+
+import (
+	"context"
+	"fmt"
+	"sync"
+	"time"
+
+	"github.com/chromedp/cdproto/network"
+	"github.com/chromedp/cdproto/runtime"
+	"github.com/chromedp/chromedp"
+)
+
+// pollInterval is the delay between two checks of a polled condition
+const pollInterval = 100 * time.Millisecond
+
+// poll calls check every pollInterval until it reports true, fails, or ctx is done
+// When ctx expires, the last transient error reported by check is added to the deadline error
+func poll(ctx context.Context, check func() (bool, error)) error {
+	ticker := time.NewTicker(pollInterval)
+	defer ticker.Stop()
+
+	var lastErr error
+	for {
+		done, err := check()
+		if err != nil && ctx.Err() == nil {
+			lastErr = err
+		} else if done {
+			return nil
+		}
+
+		select {
+		case <-ctx.Done():
+			if lastErr != nil {
+				return fmt.Errorf("%w (last error: %v)", ctx.Err(), lastErr)
+			}
+			return ctx.Err()
+		case <-ticker.C:
+		}
+	}
+}
+
+// WaitForNetworkIdle waits until no request has been in flight for the idle duration
+// Only requests sent after the call are tracked
+//
+// Parameters:
+//   - ctx: Chrome context bounding the wait
+//   - idle: Quiet period required to consider the network idle
+//
+// Returns:
+//   - An error if the network domain could not be enabled or ctx expired first
+func WaitForNetworkIdle(ctx context.Context, idle time.Duration) error {
+	if err := chromedp.Run(ctx, network.Enable()); err != nil {
+		return err
+	}
+
+	var mu sync.Mutex
+	inflight := make(map[network.RequestID]struct{})
+	activity := make(chan struct{}, 1)
+
+	// The listener is removed when the wait returns
+	listenCtx, cancel := context.WithCancel(ctx)
+	defer cancel()
+
+	chromedp.ListenTarget(listenCtx, func(event interface{}) {
+		mu.Lock()
+		switch ev := event.(type) {
+		case *network.EventRequestWillBeSent:
+			inflight[ev.RequestID] = struct{}{}
+		case *network.EventLoadingFinished:
+			delete(inflight, ev.RequestID)
+		case *network.EventLoadingFailed:
+			delete(inflight, ev.RequestID)
+		default:
+			mu.Unlock()
+			return
+		}
+		mu.Unlock()
+
+		select {
+		case activity <- struct{}{}:
+		default:
+		}
+	})
+
+	timer := time.NewTimer(idle)
+	defer timer.Stop()
+
+	for {
+		select {
+		case <-ctx.Done():
+			return ctx.Err()
+		case <-activity:
+			// Any network event restarts the quiet period
+			timer.Reset(idle)
+		case <-timer.C:
+			mu.Lock()
+			pending := len(inflight)
+			mu.Unlock()
+
+			if pending == 0 {
+				return nil
+			}
+			timer.Reset(idle)
+		}
+	}
+}
+
+// WaitForFunction polls a JavaScript function until it returns a truthy value
+// Exceptions thrown by the function are treated as a falsy result, so predicates may
+// reference elements that do not exist yet
+//
+// Parameters:
+//   - ctx: Chrome context bounding the wait
+//   - function: JavaScript function declaration, awaited if it returns a Promise
+//   - args: Arguments passed to the function, serialized as JSON
+//
+// Returns:
+//   - An error if ctx expired before the function returned a truthy value
+func WaitForFunction(ctx context.Context, function string, args ...any) error {
+	predicate := "async function(...args) { return Boolean(await (" + function + ")(...args)); }"
+
+	return poll(ctx, func() (bool, error) {
+		var ok bool
+		err := chromedp.Run(ctx, chromedp.CallFunctionOn(predicate, &ok, awaitPromise, args...))
+		return ok, err
+	})
+}
+
+// WaitForNavigation waits for the page URL to change and the new document to finish loading
+// The URL of the page when the call starts is used as the starting point
+//
+// Parameters:
+//   - ctx: Chrome context bounding the wait
+//
+// Returns:
+//   - The URL of the new page
+func WaitForNavigation(ctx context.Context) (string, error) {
+	var start string
+	if err := chromedp.Run(ctx, chromedp.Location(&start)); err != nil {
+		return "", err
+	}
+
+	var state struct {
+		URL   string `json:"url"`
+		Ready string `json:"ready"`
+	}
+
+	// Evaluations fail while the old document is torn down, the poll retries them
+	err := poll(ctx, func() (bool, error) {
+		err := chromedp.Run(ctx, chromedp.Evaluate(`({ url: location.href, ready: document.readyState })`, &state))
+		return err == nil && state.URL != start && state.Ready == "complete", err
+	})
+	return state.URL, err
+}
+
+// WaitForSelectorCount waits until at least count elements match the CSS selector
+//
+// Parameters:
+//   - ctx: Chrome context bounding the wait
+//   - selector: CSS selector to count
+//   - count: Minimum number of matching elements
+//
+// Returns:
+//   - The number of matching elements once it reached count
+func WaitForSelectorCount(ctx context.Context, selector string, count int64) (int64, error) {
+	var found int64
+	err := poll(ctx, func() (bool, error) {
+		err := chromedp.Run(ctx, chromedp.CallFunctionOn(
+			`(selector) => document.querySelectorAll(selector).length`, &found, nil, selector,
+		))
+		return err == nil && found >= count, err
+	})
+	return found, err
+}
+
+// awaitPromise makes a function call wait for the Promise it returns
+func awaitPromise(p *runtime.CallFunctionOnParams) *runtime.CallFunctionOnParams {
+	return p.WithAwaitPromise(true)
+}
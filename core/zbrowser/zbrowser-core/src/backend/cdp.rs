@@ -0,0 +1,549 @@
+//! Backend driving Chrome through the native chromedp library
+
+use super::{Backend, TaskFn};
+use crate::{
+    BlockResource, BlockRule, BrowserMetrics, Diagnostics, Emulation, Error, PoolConfig, Proxy, ResourceLimits, Response,
+    Result, ScreenshotOptions,
+};
+use base64::{Engine, engine::general_purpose::STANDARD};
+use dashmap::DashMap;
+use lazy_static::lazy_static;
+use std::{
+    ffi::{CStr, CString},
+    os::raw::c_char,
+    path::PathBuf,
+};
+use tracing::{debug, error};
+
+// Include the native bindings generated at compile time
+include!(concat!(env!("CARGO_MANIFEST_DIR"), "/bindings.rs"));
+
+lazy_static! {
+    /// Global thread-safe map storing registered callbacks by their context ID
+    static ref CALLBACKS: DashMap<i64, TaskFn> = DashMap::new();
+}
+
+/// C-compatible callback trampoline function
+/// This function is called from the native layer and dispatches to the appropriate Rust callback
+unsafe extern "C" fn callback_trampoline(context_id: i64) {
+    if let Some(cb) = CALLBACKS.get(&context_id) {
+        cb(context_id);
+    } else {
+        eprintln!("Not found callback for {}", context_id);
+    }
+}
+
+/// Copies a string returned by the native layer and releases its memory
+///
+/// # Safety
+/// `ptr` must be null or a string allocated by the native layer with `C.CString`
+unsafe fn take_string(ptr: *mut c_char) -> String {
+    if ptr.is_null() {
+        return String::new();
+    }
+
+    let value = CStr::from_ptr(ptr).to_string_lossy().to_string();
+    libc::free(ptr as *mut libc::c_void);
+    value
+}
+
+/// Converts a Rust string into a C string for the native layer
+/// Strings with an interior NUL byte cannot be passed and fail with `Error::Failed`
+fn c_string(value: &str) -> Result<CString> {
+    CString::new(value).map_err(|e| Error::Failed(format!("Invalid string argument: {}", e)))
+}
+
+/// Parses a JSON string or null returned by the native layer
+fn parse_optional_string(json: &str) -> Result<Option<String>> {
+    serde_json::from_str(json).map_err(|e| Error::Failed(e.to_string()))
+}
+
+/// Backend that runs tasks on Chrome through the Go chromedp library
+pub struct CdpBackend {
+    /// Unique identifier of the native scraper instance
+    id: i64,
+}
+
+impl CdpBackend {
+    /// Creates a native scraper instance
+    ///
+    /// # Arguments
+    /// * `url` - Remote Chrome URL, a local Chrome is launched when empty
+    /// * `workers` - Number of worker threads for concurrent operations
+    /// * `block_resources` - List of resource types to block for performance
+    ///
+    /// # Returns
+    /// The backend, or `Error::Failed` if the URL contains a NUL byte
+    pub fn new(url: &str, workers: i64, block_resources: &[BlockResource]) -> Result<Self> {
+        let c_url = c_string(url)?;
+        let c_block_resources = block_resources
+            .iter()
+            .map(|r| c_string(r.as_str()))
+            .collect::<Result<Vec<_>>>()?;
+
+        let ptrs: Vec<*mut c_char> = c_block_resources
+            .iter()
+            .map(|s| s.as_ptr() as *mut c_char)
+            .collect();
+
+        unsafe {
+            let slice = GoSlice {
+                data: ptrs.as_ptr() as *mut std::ffi::c_void,
+                len: ptrs.len() as GoInt,
+                cap: ptrs.len() as GoInt,
+            };
+
+            let id = NewScraper(c_url.as_ptr() as *mut c_char, workers, slice);
+            Ok(Self { id })
+        }
+    }
+
+    /// Converts a native result code into a Result
+    /// The error message is fetched from the native layer when the code is not zero
+    fn check(&self, context_id: i64, code: i64) -> Result<()> {
+        match code {
+            0 => Ok(()),
+            _ => Err(self.last_error(context_id, code)),
+        }
+    }
+
+    /// Builds the error for a failed native call using the last message recorded for the context
+    fn last_error(&self, context_id: i64, code: i64) -> Error {
+        let message = unsafe { take_string(LastError(context_id)) };
+        let error = Error::from_code(code, message);
+        debug!("Context {}: {}", context_id, error);
+        error
+    }
+
+    /// Runs a task on a worker tab, or on a warm tab of the pool when a key is given
+    fn execute(&self, context_id: i64, key: Option<&str>, task: TaskFn) -> Result<()> {
+        let c_key = key.map(c_string).transpose()?;
+        CALLBACKS.insert(context_id, task);
+
+        let mut err = 0;
+        unsafe {
+            match &c_key {
+                Some(c_key) => {
+                    ExecutePooled(
+                        self.id,
+                        context_id,
+                        c_key.as_ptr() as *mut c_char,
+                        Some(callback_trampoline),
+                        &mut err,
+                    );
+                }
+                None => Execute(self.id, context_id, Some(callback_trampoline), &mut err),
+            }
+        }
+        CALLBACKS.remove(&context_id);
+
+        match err {
+            0 => Ok(()),
+            _ => {
+                let message = unsafe { take_string(LastError(context_id)) };
+                unsafe { CloseContext(context_id) };
+                error!("Failed to execute task: {}", message);
+                Err(Error::from_code(err, message))
+            }
+        }
+    }
+
+    /// Reads the string returned by a native call that reports its result code through `err`
+    fn check_string(&self, context_id: i64, result: *mut c_char, err: i64) -> Result<String> {
+        let value = unsafe { take_string(result) };
+        self.check(context_id, err).map(|_| value)
+    }
+}
+
+impl Backend for CdpBackend {
+    fn run(&self, context_id: i64, task: TaskFn) -> Result<()> {
+        self.execute(context_id, None, task)
+    }
+
+    fn run_pooled(&self, context_id: i64, key: &str, task: TaskFn) -> Result<()> {
+        self.execute(context_id, Some(key), task)
+    }
+
+    fn configure_pool(&self, config: &PoolConfig) {
+        unsafe { SetPoolLimits(self.id, config.max_uses as i64, config.max_idle as i64) }
+    }
+
+    fn context_uses(&self, context_id: i64) -> usize {
+        unsafe { ContextUses(context_id) as usize }
+    }
+
+    fn discard_context(&self, context_id: i64) {
+        unsafe { DiscardContext(context_id) }
+    }
+
+    fn cancel(&self, context_id: i64) {
+        unsafe { CancelContext(context_id) }
+    }
+
+    fn close_context(&self, context_id: i64) {
+        unsafe { CloseContext(context_id) }
+    }
+
+    fn is_connected(&self) -> bool {
+        unsafe { Connected(self.id) == 1 }
+    }
+
+    fn set_limits(&self, limits: &ResourceLimits) {
+        let max_heap_mb = limits.max_heap_mb.unwrap_or_default() as i64;
+        let max_task_ms = limits.max_task_time.map(|limit| limit.as_millis() as i64).unwrap_or_default();
+        unsafe { SetLimits(self.id, max_heap_mb, max_task_ms) }
+    }
+
+    fn browser_metrics(&self) -> BrowserMetrics {
+        let json = unsafe { take_string(BrowserStats(self.id)) };
+        serde_json::from_str(&json).unwrap_or_default()
+    }
+
+    fn navigate(&self, context_id: i64, url: &str) -> Result<()> {
+        let c_url = c_string(url)?;
+        let code = unsafe { Navigate(context_id, c_url.as_ptr() as *mut c_char) };
+        self.check(context_id, code)
+    }
+
+    fn set_user_agent(&self, context_id: i64, user_agent: &str) -> Result<()> {
+        let c_user_agent = c_string(user_agent)?;
+        let code = unsafe { SetUserAgent(context_id, c_user_agent.as_ptr() as *mut c_char) };
+        self.check(context_id, code)
+    }
+
+    fn emulate(&self, context_id: i64, emulation: &Emulation) -> Result<()> {
+        let json = serde_json::to_string(emulation).map_err(|e| Error::Failed(e.to_string()))?;
+        let c_json = c_string(&json)?;
+        let code = unsafe { Emulate(context_id, c_json.as_ptr() as *mut c_char) };
+        self.check(context_id, code)
+    }
+
+    fn set_proxy(&self, context_id: i64, proxy: &Proxy) -> Result<()> {
+        let (username, password) = proxy.credentials.clone().unwrap_or_default();
+        let c_server = c_string(&proxy.server())?;
+        let c_username = c_string(&username)?;
+        let c_password = c_string(&password)?;
+        let code = unsafe {
+            SetProxy(
+                context_id,
+                c_server.as_ptr() as *mut c_char,
+                c_username.as_ptr() as *mut c_char,
+                c_password.as_ptr() as *mut c_char,
+            )
+        };
+        self.check(context_id, code)
+    }
+
+    fn block_urls(&self, context_id: i64, rules: &[BlockRule]) -> Result<()> {
+        let patterns: Vec<&str> = rules.iter().map(BlockRule::pattern).collect();
+        let json = serde_json::to_string(&patterns).map_err(|e| Error::Failed(e.to_string()))?;
+        let c_json = c_string(&json)?;
+        let code = unsafe { BlockURLs(context_id, c_json.as_ptr() as *mut c_char) };
+        self.check(context_id, code)
+    }
+
+    fn add_init_script(&self, context_id: i64, script: &str) -> Result<()> {
+        let c_script = c_string(script)?;
+        let code = unsafe { AddInitScript(context_id, c_script.as_ptr() as *mut c_char) };
+        self.check(context_id, code)
+    }
+
+    fn wait_for_element(&self, context_id: i64, selector: &str, timeout: i64) -> Result<()> {
+        let c_selector = c_string(selector)?;
+        let code =
+            unsafe { WaitForElement(context_id, c_selector.as_ptr() as *mut c_char, timeout) };
+        self.check(context_id, code)
+    }
+
+    fn wait_for_network_idle(&self, context_id: i64, idle: i64, timeout: i64) -> Result<()> {
+        let code = unsafe { WaitForNetworkIdle(context_id, idle, timeout) };
+        self.check(context_id, code)
+    }
+
+    fn wait_for_function(&self, context_id: i64, function: &str, args: &str, timeout: i64) -> Result<()> {
+        let c_function = c_string(function)?;
+        let c_args = c_string(args)?;
+        let code = unsafe {
+            WaitForFunction(
+                context_id,
+                c_function.as_ptr() as *mut c_char,
+                c_args.as_ptr() as *mut c_char,
+                timeout,
+            )
+        };
+        self.check(context_id, code)
+    }
+
+    fn wait_for_navigation(&self, context_id: i64, timeout: i64) -> Result<String> {
+        let mut err = 0;
+        let result = unsafe { WaitForNavigation(context_id, timeout, &mut err) };
+        self.check_string(context_id, result, err)
+    }
+
+    fn wait_for_selector_count(
+        &self,
+        context_id: i64,
+        selector: &str,
+        count: usize,
+        timeout: i64,
+    ) -> Result<usize> {
+        let c_selector = c_string(selector)?;
+        let mut err = 0;
+        let found = unsafe {
+            WaitForSelectorCount(
+                context_id,
+                c_selector.as_ptr() as *mut c_char,
+                count as i64,
+                timeout,
+                &mut err,
+            )
+        };
+        self.check(context_id, err).map(|_| found as usize)
+    }
+
+    fn write_input(&self, context_id: i64, selector: &str, text: &str) -> Result<()> {
+        let c_selector = c_string(selector)?;
+        let c_text = c_string(text)?;
+        let code = unsafe {
+            WriteInput(
+                context_id,
+                c_selector.as_ptr() as *mut c_char,
+                c_text.as_ptr() as *mut c_char,
+            )
+        };
+        self.check(context_id, code)
+    }
+
+    fn click_element(&self, context_id: i64, selector: &str) -> Result<()> {
+        let c_selector = c_string(selector)?;
+        let code = unsafe { ClickElement(context_id, c_selector.as_ptr() as *mut c_char) };
+        self.check(context_id, code)
+    }
+
+    fn hover_element(&self, context_id: i64, selector: &str) -> Result<()> {
+        let c_selector = c_string(selector)?;
+        let code = unsafe { HoverElement(context_id, c_selector.as_ptr() as *mut c_char) };
+        self.check(context_id, code)
+    }
+
+    fn mouse_move(&self, context_id: i64, x: f64, y: f64) -> Result<()> {
+        let code = unsafe { MouseMove(context_id, x, y) };
+        self.check(context_id, code)
+    }
+
+    fn mouse_wheel(&self, context_id: i64, delta_x: f64, delta_y: f64) -> Result<()> {
+        let code = unsafe { MouseWheel(context_id, delta_x, delta_y) };
+        self.check(context_id, code)
+    }
+
+    fn press_key(&self, context_id: i64, key: &str) -> Result<()> {
+        let c_key = c_string(key)?;
+        let code = unsafe { PressKey(context_id, c_key.as_ptr() as *mut c_char) };
+        self.check(context_id, code)
+    }
+
+    fn select_option(&self, context_id: i64, selector: &str, values: &[String]) -> Result<Vec<String>> {
+        let json = serde_json::to_string(values).map_err(|e| Error::Failed(e.to_string()))?;
+        let c_selector = c_string(selector)?;
+        let c_values = c_string(&json)?;
+        let mut err = 0;
+        let result = unsafe {
+            SelectOption(
+                context_id,
+                c_selector.as_ptr() as *mut c_char,
+                c_values.as_ptr() as *mut c_char,
+                &mut err,
+            )
+        };
+        let json = self.check_string(context_id, result, err)?;
+        serde_json::from_str(&json).map_err(|e| Error::Failed(e.to_string()))
+    }
+
+    fn focus_element(&self, context_id: i64, selector: &str) -> Result<()> {
+        let c_selector = c_string(selector)?;
+        let code = unsafe { FocusElement(context_id, c_selector.as_ptr() as *mut c_char) };
+        self.check(context_id, code)
+    }
+
+    fn blur_element(&self, context_id: i64, selector: &str) -> Result<()> {
+        let c_selector = c_string(selector)?;
+        let code = unsafe { BlurElement(context_id, c_selector.as_ptr() as *mut c_char) };
+        self.check(context_id, code)
+    }
+
+    fn upload_files(&self, context_id: i64, selector: &str, files: &[PathBuf]) -> Result<()> {
+        let json = serde_json::to_string(files).map_err(|e| Error::Failed(e.to_string()))?;
+        let c_selector = c_string(selector)?;
+        let c_files = c_string(&json)?;
+        let code = unsafe {
+            UploadFiles(
+                context_id,
+                c_selector.as_ptr() as *mut c_char,
+                c_files.as_ptr() as *mut c_char,
+            )
+        };
+        self.check(context_id, code)
+    }
+
+    fn query_selector_all(&self, context_id: i64, parent: Option<i64>, selector: &str) -> Result<Vec<i64>> {
+        let c_selector = c_string(selector)?;
+        let mut err = 0;
+        let result = unsafe {
+            QuerySelectorAll(
+                context_id,
+                parent.unwrap_or_default(),
+                c_selector.as_ptr() as *mut c_char,
+                &mut err,
+            )
+        };
+        let json = self.check_string(context_id, result, err)?;
+        serde_json::from_str(&json).map_err(|e| Error::Failed(e.to_string()))
+    }
+
+    fn element_text(&self, context_id: i64, element: i64) -> Result<String> {
+        let mut err = 0;
+        let result = unsafe { ElementText(context_id, element, &mut err) };
+        let json = self.check_string(context_id, result, err)?;
+        Ok(parse_optional_string(&json)?.unwrap_or_default())
+    }
+
+    fn element_attribute(&self, context_id: i64, element: i64, name: &str) -> Result<Option<String>> {
+        let c_name = c_string(name)?;
+        let mut err = 0;
+        let result = unsafe {
+            ElementAttribute(context_id, element, c_name.as_ptr() as *mut c_char, &mut err)
+        };
+        let json = self.check_string(context_id, result, err)?;
+        parse_optional_string(&json)
+    }
+
+    fn element_inner_html(&self, context_id: i64, element: i64) -> Result<String> {
+        let mut err = 0;
+        let result = unsafe { ElementInnerHTML(context_id, element, &mut err) };
+        let json = self.check_string(context_id, result, err)?;
+        Ok(parse_optional_string(&json)?.unwrap_or_default())
+    }
+
+    fn click_handle(&self, context_id: i64, element: i64) -> Result<()> {
+        let code = unsafe { ClickHandle(context_id, element) };
+        self.check(context_id, code)
+    }
+
+    fn hover_handle(&self, context_id: i64, element: i64) -> Result<()> {
+        let code = unsafe { HoverHandle(context_id, element) };
+        self.check(context_id, code)
+    }
+
+    fn string_cookies(&self, context_id: i64) -> Result<String> {
+        let mut err = 0;
+        let result = unsafe { StringCookies(context_id, &mut err) };
+        self.check_string(context_id, result, err)
+    }
+
+    fn set_string_cookies(&self, context_id: i64, cookies: &str) -> Result<()> {
+        let c_cookies = c_string(cookies)?;
+        let code = unsafe { SetStringCookies(context_id, c_cookies.as_ptr() as *mut c_char) };
+        self.check(context_id, code)
+    }
+
+    fn evaluate(&self, context_id: i64, expr: &str) -> Result<String> {
+        let c_expr = c_string(expr)?;
+        let mut err = 0;
+        let result = unsafe { Evaluate(context_id, c_expr.as_ptr() as *mut c_char, &mut err) };
+        self.check_string(context_id, result, err)
+    }
+
+    fn async_evaluate(&self, context_id: i64, expr: &str) -> Result<String> {
+        let c_expr = c_string(expr)?;
+        let mut err = 0;
+        let result =
+            unsafe { AsyncEvaluate(context_id, c_expr.as_ptr() as *mut c_char, &mut err) };
+        self.check_string(context_id, result, err)
+    }
+
+    fn call_function(&self, context_id: i64, function: &str, args: &str) -> Result<String> {
+        let c_function = c_string(function)?;
+        let c_args = c_string(args)?;
+        let mut err = 0;
+        let result = unsafe {
+            CallFunction(
+                context_id,
+                c_function.as_ptr() as *mut c_char,
+                c_args.as_ptr() as *mut c_char,
+                &mut err,
+            )
+        };
+        self.check_string(context_id, result, err)
+    }
+
+    fn async_call_function(&self, context_id: i64, function: &str, args: &str) -> Result<String> {
+        let c_function = c_string(function)?;
+        let c_args = c_string(args)?;
+        let mut err = 0;
+        let result = unsafe {
+            AsyncCallFunction(
+                context_id,
+                c_function.as_ptr() as *mut c_char,
+                c_args.as_ptr() as *mut c_char,
+                &mut err,
+            )
+        };
+        self.check_string(context_id, result, err)
+    }
+
+    fn get_html(&self, context_id: i64) -> Result<String> {
+        let mut err = 0;
+        let result = unsafe { GetHTML(context_id, &mut err) };
+        self.check_string(context_id, result, err)
+    }
+
+    fn screenshot(&self, context_id: i64, options: &ScreenshotOptions) -> Result<Vec<u8>> {
+        let options = serde_json::to_string(options).map_err(|e| Error::Failed(e.to_string()))?;
+        let c_options = c_string(&options)?;
+        let mut err = 0;
+        let result = unsafe { Screenshot(context_id, c_options.as_ptr() as *mut c_char, &mut err) };
+        let image = self.check_string(context_id, result, err)?;
+        STANDARD
+            .decode(image)
+            .map_err(|e| Error::Failed(format!("Invalid screenshot: {}", e)))
+    }
+
+    fn capture_responses(&self, context_id: i64, pattern: &str) -> Result<()> {
+        let c_pattern = c_string(pattern)?;
+        let code = unsafe { CaptureResponses(context_id, c_pattern.as_ptr() as *mut c_char) };
+        self.check(context_id, code)
+    }
+
+    fn responses(&self, context_id: i64) -> Result<Vec<Response>> {
+        let mut err = 0;
+        let result = unsafe { CapturedResponses(context_id, &mut err) };
+        let json = self.check_string(context_id, result, err)?;
+        serde_json::from_str(&json).map_err(|e| Error::Failed(e.to_string()))
+    }
+
+    fn wait_for_response(&self, context_id: i64, pattern: &str, timeout: i64) -> Result<Response> {
+        let c_pattern = c_string(pattern)?;
+        let mut err = 0;
+        let result = unsafe {
+            WaitForResponse(context_id, c_pattern.as_ptr() as *mut c_char, timeout, &mut err)
+        };
+        let json = self.check_string(context_id, result, err)?;
+        serde_json::from_str(&json).map_err(|e| Error::Failed(e.to_string()))
+    }
+
+    fn diagnostics(&self, context_id: i64) -> Result<Diagnostics> {
+        let mut err = 0;
+        let result = unsafe { TakeDiagnostics(context_id, &mut err) };
+        let json = self.check_string(context_id, result, err)?;
+        serde_json::from_str(&json).map_err(|e| Error::Failed(e.to_string()))
+    }
+}
+
+/// Automatically closes the native scraper when dropped
+impl Drop for CdpBackend {
+    fn drop(&mut self) {
+        unsafe {
+            Close(self.id);
+        }
+    }
+}
@@ -0,0 +1,218 @@
+//! Browser backends used by `Scraper` and `Context`
+//!
+//! A backend owns the browser side of the automation: it runs tasks and carries out
+//! the operations that a `Context` exposes. Contexts are identified by the ID given
+//! to the backend when their task starts.
+
+use crate::{
+    BlockRule, BrowserMetrics, Diagnostics, Emulation, PoolConfig, Proxy, ResourceLimits, Response, Result,
+    ScreenshotOptions,
+};
+use std::path::PathBuf;
+
+mod cdp;
+mod replay;
+
+pub use cdp::CdpBackend;
+pub use replay::ReplayBackend;
+
+/// Function run by a backend for each task, called with the ID of the task's context
+pub type TaskFn = Box<dyn Fn(i64) + Send + Sync>;
+
+/// Operations a browser backend must provide
+///
+/// Every method except `run` and `cancel` is called from inside the task,
+/// on the thread the backend used to call it
+pub trait Backend: Send + Sync {
+    /// Runs a task with a fresh context, blocking until it returns
+    ///
+    /// # Arguments
+    /// * `context_id` - ID of the context created for the task
+    /// * `task` - Function to call with the context ID
+    fn run(&self, context_id: i64, task: TaskFn) -> Result<()>;
+
+    /// Runs a task with a warm context of the pool, blocking until it returns
+    /// The context keeps the state left by the previous task with the same key,
+    /// a fresh context is created when there is none
+    ///
+    /// # Arguments
+    /// * `context_id` - ID given to the warm context for the task
+    /// * `key` - Key of the warm context, usually the name of a session
+    /// * `task` - Function to call with the context ID
+    fn run_pooled(&self, context_id: i64, key: &str, task: TaskFn) -> Result<()>;
+
+    /// Sets the limits of the pool of warm contexts
+    fn configure_pool(&self, config: &PoolConfig);
+
+    /// Returns the number of earlier tasks that ran in a warm context, zero outside the pool
+    fn context_uses(&self, context_id: i64) -> usize;
+
+    /// Closes the warm context of a pooled task once the task ends, instead of reusing it
+    fn discard_context(&self, context_id: i64);
+
+    /// Aborts the task running with the given context
+    /// Pending and future operations of the context fail
+    fn cancel(&self, context_id: i64);
+
+    /// Releases the resources of a context once its task is done
+    fn close_context(&self, context_id: i64);
+
+    /// Returns whether the browser is reachable
+    /// Backends that do not run a browser are always connected
+    fn is_connected(&self) -> bool {
+        true
+    }
+
+    /// Sets the resource limits of the tasks started from now on
+    /// Backends that do not run a browser ignore them
+    fn set_limits(&self, _limits: &ResourceLimits) {}
+
+    /// Returns the browser failures the backend recovered from
+    /// Backends that do not run a browser never fail
+    fn browser_metrics(&self) -> BrowserMetrics {
+        BrowserMetrics::default()
+    }
+
+    /// Navigates the context to the specified URL
+    fn navigate(&self, context_id: i64, url: &str) -> Result<()>;
+
+    /// Sets the user agent string of the context
+    fn set_user_agent(&self, context_id: i64, user_agent: &str) -> Result<()>;
+
+    /// Applies device, language and location overrides to the context
+    fn emulate(&self, context_id: i64, emulation: &Emulation) -> Result<()>;
+
+    /// Moves the context to a new tab that sends its traffic through the proxy
+    fn set_proxy(&self, context_id: i64, proxy: &Proxy) -> Result<()>;
+
+    /// Fails the requests whose URL matches one of the rules, until the task ends
+    fn block_urls(&self, context_id: i64, rules: &[BlockRule]) -> Result<()>;
+
+    /// Evaluates a script before the scripts of every page loaded from now on, until the task ends
+    fn add_init_script(&self, context_id: i64, script: &str) -> Result<()>;
+
+    /// Waits for an element matching the CSS selector, up to `timeout` milliseconds
+    fn wait_for_element(&self, context_id: i64, selector: &str, timeout: i64) -> Result<()>;
+
+    /// Waits until no request has been in flight for `idle` milliseconds, up to `timeout` milliseconds
+    fn wait_for_network_idle(&self, context_id: i64, idle: i64, timeout: i64) -> Result<()>;
+
+    /// Polls a JavaScript function with JSON arguments until it returns a truthy value,
+    /// up to `timeout` milliseconds
+    fn wait_for_function(&self, context_id: i64, function: &str, args: &str, timeout: i64) -> Result<()>;
+
+    /// Waits for the page to navigate to another URL and finish loading, up to `timeout` milliseconds
+    /// Returns the new URL
+    fn wait_for_navigation(&self, context_id: i64, timeout: i64) -> Result<String>;
+
+    /// Waits until at least `count` elements match the CSS selector, up to `timeout` milliseconds
+    /// Returns the number of matching elements
+    fn wait_for_selector_count(
+        &self,
+        context_id: i64,
+        selector: &str,
+        count: usize,
+        timeout: i64,
+    ) -> Result<usize>;
+
+    /// Types text into the input element matching the CSS selector
+    fn write_input(&self, context_id: i64, selector: &str, text: &str) -> Result<()>;
+
+    /// Clicks the element matching the CSS selector
+    fn click_element(&self, context_id: i64, selector: &str) -> Result<()>;
+
+    /// Scrolls the element matching the CSS selector into view and moves the mouse over it
+    fn hover_element(&self, context_id: i64, selector: &str) -> Result<()>;
+
+    /// Moves the mouse to a point of the viewport, in CSS pixels
+    fn mouse_move(&self, context_id: i64, x: f64, y: f64) -> Result<()>;
+
+    /// Turns the mouse wheel over the center of the viewport by the given CSS pixels
+    fn mouse_wheel(&self, context_id: i64, delta_x: f64, delta_y: f64) -> Result<()>;
+
+    /// Presses and releases a key, with modifiers joined by `+` like `Control+A`
+    fn press_key(&self, context_id: i64, key: &str) -> Result<()>;
+
+    /// Selects the options of the select element matching the CSS selector by value or label
+    /// Returns the values of the selected options
+    fn select_option(&self, context_id: i64, selector: &str, values: &[String]) -> Result<Vec<String>>;
+
+    /// Gives the keyboard focus to the element matching the CSS selector
+    fn focus_element(&self, context_id: i64, selector: &str) -> Result<()>;
+
+    /// Removes the keyboard focus from the element matching the CSS selector
+    fn blur_element(&self, context_id: i64, selector: &str) -> Result<()>;
+
+    /// Sets the files of the file input matching the CSS selector
+    fn upload_files(&self, context_id: i64, selector: &str, files: &[PathBuf]) -> Result<()>;
+
+    /// Finds the elements matching the CSS selector, piercing open shadow roots and
+    /// same origin iframes
+    ///
+    /// # Arguments
+    /// * `parent` - Handle of the element to search under, the whole page when `None`
+    /// * `selector` - CSS selector
+    ///
+    /// # Returns
+    /// The handles of the matching elements, valid until the page navigates
+    fn query_selector_all(&self, context_id: i64, parent: Option<i64>, selector: &str) -> Result<Vec<i64>>;
+
+    /// Returns the text content of the element of a handle
+    fn element_text(&self, context_id: i64, element: i64) -> Result<String>;
+
+    /// Returns the value of an attribute of the element of a handle, `None` if it is missing
+    fn element_attribute(&self, context_id: i64, element: i64, name: &str) -> Result<Option<String>>;
+
+    /// Returns the HTML of the children of the element of a handle
+    fn element_inner_html(&self, context_id: i64, element: i64) -> Result<String>;
+
+    /// Scrolls the element of a handle into view and clicks it with the mouse
+    fn click_handle(&self, context_id: i64, element: i64) -> Result<()>;
+
+    /// Scrolls the element of a handle into view and moves the mouse over it
+    fn hover_handle(&self, context_id: i64, element: i64) -> Result<()>;
+
+    /// Returns all cookies of the context as a JSON array
+    fn string_cookies(&self, context_id: i64) -> Result<String>;
+
+    /// Sets cookies from a JSON array
+    fn set_string_cookies(&self, context_id: i64, cookies: &str) -> Result<()>;
+
+    /// Evaluates a JavaScript expression and returns its result as a string
+    fn evaluate(&self, context_id: i64, expr: &str) -> Result<String>;
+
+    /// Evaluates a JavaScript expression, awaiting it if it returns a Promise
+    fn async_evaluate(&self, context_id: i64, expr: &str) -> Result<String>;
+
+    /// Calls a JavaScript function declaration with arguments given as a JSON array
+    /// and returns its result serialized as JSON
+    fn call_function(&self, context_id: i64, function: &str, args: &str) -> Result<String>;
+
+    /// Calls a JavaScript function declaration, awaiting its result if it returns a Promise
+    fn async_call_function(&self, context_id: i64, function: &str, args: &str) -> Result<String>;
+
+    /// Scrolls the page down by one viewport
+    fn scroll(&self, context_id: i64) -> Result<()> {
+        self.evaluate(context_id, "window.scrollBy(0, window.innerHeight)")
+            .map(|_| ())
+    }
+
+    /// Returns the HTML of the current page
+    fn get_html(&self, context_id: i64) -> Result<String>;
+
+    /// Captures a PNG image of the page
+    fn screenshot(&self, context_id: i64, options: &ScreenshotOptions) -> Result<Vec<u8>>;
+
+    /// Starts recording the responses whose URL matches the regular expression
+    fn capture_responses(&self, context_id: i64, pattern: &str) -> Result<()>;
+
+    /// Returns and clears the responses captured so far
+    fn responses(&self, context_id: i64) -> Result<Vec<Response>>;
+
+    /// Waits for a response whose URL matches the regular expression, up to `timeout` milliseconds
+    fn wait_for_response(&self, context_id: i64, pattern: &str, timeout: i64) -> Result<Response>;
+
+    /// Returns and clears the console messages, uncaught exceptions and failed requests
+    /// recorded since the task started
+    fn diagnostics(&self, context_id: i64) -> Result<Diagnostics>;
+}
@@ -0,0 +1,786 @@
+//! Backend serving recorded pages from disk, used to test scrapers without Chrome
+//!
+//! Each page is described by a JSON fixture in the replay directory:
+//!
+//! ```json
+//! {
+//!     "url": "https://www.example.com/",
+//!     "html": "<html>...</html>",
+//!     "evaluations": [
+//!         { "match": "article[data-testid", "result": "[]" },
+//!         { "match": "(selector) =>", "args": ["h1"], "result": "Title" }
+//!     ],
+//!     "responses": [{ "url": "https://www.example.com/api", "status": 200, "body": "{}" }],
+//!     "diagnostics": { "exceptions": [{ "message": "TypeError: x is undefined" }] }
+//! }
+//! ```
+//!
+//! When `html` is missing, the HTML is read from the file with the same name and the
+//! `.html` extension. `evaluate` first looks for a recorded evaluation whose `match`
+//! appears in the script, then answers simple DOM queries (`location.href`,
+//! `document.title`, `document.querySelector(...).textContent`, ...) against the page.
+//! Function calls only use the recordings, restricted to those whose `args` match when given.
+//! Element handles query the recorded HTML, which holds no shadow roots or iframe documents.
+//! Recordings with a `scroll` field only answer after that many calls to `scroll`, so
+//! fixtures can describe the content revealed by each scroll step.
+//! The recorded `diagnostics` are reported once every time the page is loaded.
+//! Warm contexts of the pool keep the current page and the cookies between tasks.
+//! Blocked URLs fail navigation and drop the recorded responses they match, while init
+//! scripts are accepted and ignored since no script of the page runs.
+//! Nothing is rendered, so screenshots are a blank 1x1 PNG.
+
+use super::{Backend, TaskFn};
+use crate::{BlockRule, Diagnostics, Emulation, Error, PoolConfig, Proxy, Response, Result, ScreenshotOptions};
+use base64::{Engine, engine::general_purpose::STANDARD};
+use dashmap::DashMap;
+use lazy_static::lazy_static;
+use regex::Regex;
+use scraper::{ElementRef, Html, Selector};
+use serde::Deserialize;
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+lazy_static! {
+    /// Matches `document.querySelector('sel').property`
+    static ref QUERY_PROPERTY: Regex = Regex::new(
+        r#"^document\.querySelector\((['"])(.+?)['"]\)\??\.(textContent|innerText|innerHTML|outerHTML)$"#
+    )
+    .unwrap();
+    /// Matches `document.querySelector('sel').getAttribute('name')`
+    static ref QUERY_ATTRIBUTE: Regex = Regex::new(
+        r#"^document\.querySelector\((['"])(.+?)['"]\)\??\.getAttribute\(['"](.+?)['"]\)$"#
+    )
+    .unwrap();
+    /// Matches `document.querySelectorAll('sel').length`
+    static ref QUERY_COUNT: Regex =
+        Regex::new(r#"^document\.querySelectorAll\((['"])(.+?)['"]\)\.length$"#).unwrap();
+    /// Blank 1x1 PNG returned as the screenshot of every page
+    static ref BLANK_PNG: Vec<u8> = STANDARD
+        .decode("iVBORw0KGgoAAAANSUhEUgAAAAEAAAABCAYAAAAfFcSJAAAADUlEQVR42mNkYPhfDwAChwGA60e6kgAAAABJRU5ErkJggg==")
+        .unwrap();
+}
+
+/// Result recorded for the scripts containing a given fragment
+#[derive(Debug, Clone, Deserialize)]
+struct Evaluation {
+    /// Fragment the script must contain, compared with whitespace collapsed
+    #[serde(rename = "match")]
+    fragment: String,
+    /// Arguments a function call must receive, any arguments match when missing
+    #[serde(default)]
+    args: Option<serde_json::Value>,
+    /// Scroll step the result belongs to, any step matches when missing
+    #[serde(default)]
+    scroll: Option<usize>,
+    /// Value returned by the script, strings are returned as they are
+    result: serde_json::Value,
+}
+
+/// A recorded page
+#[derive(Debug, Clone, Deserialize)]
+struct Page {
+    url: String,
+    #[serde(default)]
+    html: Option<String>,
+    #[serde(default)]
+    evaluations: Vec<Evaluation>,
+    #[serde(default)]
+    responses: Vec<Response>,
+    #[serde(default)]
+    diagnostics: Diagnostics,
+}
+
+/// State of a context while its task runs
+#[derive(Default)]
+struct ReplayContext {
+    /// URL of the current page, if any
+    url: Option<String>,
+    /// Cookies set by the task
+    cookies: Option<String>,
+    /// Patterns registered with `capture_responses`
+    patterns: Vec<Regex>,
+    /// Rules registered with `block_urls`
+    blocked: Vec<BlockRule>,
+    /// Number of recorded responses of the current page already returned
+    consumed: Vec<usize>,
+    /// Number of scroll steps since the page was loaded
+    scrolls: usize,
+    /// Position in the document tree of the element of each handle, the handle being the index plus one
+    elements: Vec<usize>,
+    /// Diagnostics of the pages loaded since the last call to `diagnostics`
+    diagnostics: Diagnostics,
+    /// Set when the task was cancelled
+    cancelled: bool,
+}
+
+/// State a warm context keeps between the tasks of its key
+#[derive(Default)]
+struct WarmContext {
+    /// URL of the page left by the last task
+    url: Option<String>,
+    /// Cookies left by the last task
+    cookies: Option<String>,
+    /// Number of tasks that ran in the context
+    uses: usize,
+    /// Set when the context must not be reused
+    discard: bool,
+}
+
+/// Backend that replays recorded pages instead of driving a browser
+///
+/// Navigation only succeeds for URLs with a fixture, and interactions such as clicks
+/// only check that the selector matches the recorded page
+pub struct ReplayBackend {
+    /// Recorded pages indexed by URL
+    pages: HashMap<String, Page>,
+    /// Contexts of the running tasks
+    contexts: DashMap<i64, ReplayContext>,
+    /// Limits of the pool of warm contexts
+    pool: Mutex<PoolConfig>,
+    /// Idle warm contexts by key
+    idle: Mutex<HashMap<String, Vec<WarmContext>>>,
+    /// Warm contexts of the running pooled tasks
+    warm: DashMap<i64, WarmContext>,
+}
+
+impl ReplayBackend {
+    /// Loads every page fixture of a directory
+    ///
+    /// # Arguments
+    /// * `dir` - Directory holding the `.json` fixtures and their `.html` files
+    ///
+    /// # Returns
+    /// The backend, or `Error::Failed` if a fixture could not be read
+    pub fn new<P: AsRef<Path>>(dir: P) -> Result<Self> {
+        let entries = std::fs::read_dir(dir.as_ref())
+            .map_err(|e| Error::Failed(format!("Failed to read fixtures: {}", e)))?;
+
+        let mut pages = HashMap::new();
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.extension().and_then(|e| e.to_str()) != Some("json") {
+                continue;
+            }
+
+            let read = |path: &Path| {
+                std::fs::read_to_string(path).map_err(|e| {
+                    Error::Failed(format!("Failed to read fixture {}: {}", path.display(), e))
+                })
+            };
+
+            let mut page: Page = serde_json::from_str(&read(&path)?).map_err(|e| {
+                Error::Failed(format!("Invalid fixture {}: {}", path.display(), e))
+            })?;
+
+            if page.html.is_none() {
+                let html_path = path.with_extension("html");
+                page.html = Some(if html_path.exists() {
+                    read(&html_path)?
+                } else {
+                    String::new()
+                });
+            }
+
+            pages.insert(normalize_url(&page.url), page);
+        }
+
+        Ok(Self {
+            pages,
+            contexts: DashMap::new(),
+            pool: Mutex::new(PoolConfig::default()),
+            idle: Mutex::new(HashMap::new()),
+            warm: DashMap::new(),
+        })
+    }
+
+    /// Runs a function with the state of a running context
+    fn with_context<T>(
+        &self,
+        context_id: i64,
+        f: impl FnOnce(&mut ReplayContext) -> Result<T>,
+    ) -> Result<T> {
+        let mut context = self.contexts.entry(context_id).or_default();
+        if context.cancelled {
+            return Err(Error::ContextClosed);
+        }
+        f(&mut context)
+    }
+
+    /// Returns the page currently loaded in a context
+    fn page(&self, context_id: i64) -> Result<Option<&Page>> {
+        self.with_context(context_id, |context| Ok(context.url.clone()))
+            .map(|url| url.and_then(|url| self.pages.get(&url)))
+    }
+
+    /// Returns the HTML of the page currently loaded in a context
+    fn html(&self, context_id: i64) -> Result<String> {
+        Ok(self
+            .page(context_id)?
+            .and_then(|page| page.html.clone())
+            .unwrap_or_default())
+    }
+
+    /// Counts the elements of the current page matching a CSS selector
+    fn count(&self, context_id: i64, selector: &str) -> Result<usize> {
+        let selector = parse_selector(selector)?;
+        let document = Html::parse_document(&self.html(context_id)?);
+        Ok(document.select(&selector).count())
+    }
+
+    /// Fails unless an element of the current page matches the CSS selector
+    fn require(&self, context_id: i64, selector: &str) -> Result<()> {
+        match self.count(context_id, selector)? {
+            0 => Err(Error::Failed(format!("No element matches {}", selector))),
+            _ => Ok(()),
+        }
+    }
+
+    /// Resolves an element handle against the parsed current page
+    fn resolve<'a>(&self, context_id: i64, document: &'a Html, element: i64) -> Result<ElementRef<'a>> {
+        let position = self.with_context(context_id, |context| {
+            Ok(usize::try_from(element - 1)
+                .ok()
+                .and_then(|index| context.elements.get(index).copied()))
+        })?;
+
+        position
+            .and_then(|position| document.tree.nodes().nth(position))
+            .and_then(ElementRef::wrap)
+            .ok_or_else(|| Error::Failed(format!("Element handle {} does not exist", element)))
+    }
+
+    /// Runs a function with the element of a handle on the current page
+    fn with_element<T>(&self, context_id: i64, element: i64, f: impl FnOnce(ElementRef) -> T) -> Result<T> {
+        let document = Html::parse_document(&self.html(context_id)?);
+        self.resolve(context_id, &document, element).map(f)
+    }
+
+    /// Returns the recorded responses of the current page that match a predicate
+    /// and were not returned yet, marking them as returned
+    fn take_responses(
+        &self,
+        context_id: i64,
+        matches: impl Fn(&str) -> bool,
+        limit: usize,
+    ) -> Result<Vec<Response>> {
+        let page = self.page(context_id)?;
+        self.with_context(context_id, |context| {
+            let mut responses = Vec::new();
+            for (index, response) in page.iter().flat_map(|p| p.responses.iter()).enumerate() {
+                if responses.len() == limit {
+                    break;
+                }
+                let blocked = context.blocked.iter().any(|rule| rule.matches(&response.url));
+                if !blocked && !context.consumed.contains(&index) && matches(&response.url) {
+                    context.consumed.push(index);
+                    responses.push(response.clone());
+                }
+            }
+            Ok(responses)
+        })
+    }
+
+    /// Answers a script from the recordings of the page or from the page DOM
+    fn answer(&self, context_id: i64, expr: &str) -> Result<String> {
+        let page = self.page(context_id)?;
+        let script = collapse_whitespace(expr);
+
+        // Recorded evaluations take precedence over the DOM queries
+        let scrolls = self.with_context(context_id, |context| Ok(context.scrolls))?;
+        if let Some(result) = recorded(page, &script, None, scrolls) {
+            return Ok(match result {
+                serde_json::Value::String(value) => value.clone(),
+                serde_json::Value::Null => String::new(),
+                value => value.to_string(),
+            });
+        }
+
+        let url = page.map(|page| page.url.clone()).unwrap_or_else(|| "about:blank".to_string());
+        let html = page.and_then(|page| page.html.clone()).unwrap_or_default();
+        let document = Html::parse_document(&html);
+        let query = script.trim().trim_end_matches(';').trim();
+
+        match query {
+            "location.href" | "window.location.href" | "document.URL" => return Ok(url),
+            "location.pathname" | "window.location.pathname" => {
+                return Ok(url::Url::parse(&url)
+                    .map(|url| url.path().to_string())
+                    .unwrap_or_default());
+            }
+            "document.title" => {
+                let selector = parse_selector("title")?;
+                return Ok(document
+                    .select(&selector)
+                    .next()
+                    .map(|e| e.text().collect::<String>())
+                    .unwrap_or_default());
+            }
+            "document.documentElement.outerHTML" => return Ok(html),
+            _ => {}
+        }
+
+        if let Some(captures) = QUERY_PROPERTY.captures(query) {
+            let selector = parse_selector(&captures[2])?;
+            let element = document
+                .select(&selector)
+                .next()
+                .ok_or_else(|| Error::JsException(format!("No element matches {}", &captures[2])))?;
+
+            return Ok(match &captures[3] {
+                "innerHTML" => element.inner_html(),
+                "outerHTML" => element.html(),
+                _ => element.text().collect(),
+            });
+        }
+
+        if let Some(captures) = QUERY_ATTRIBUTE.captures(query) {
+            let selector = parse_selector(&captures[2])?;
+            return Ok(document
+                .select(&selector)
+                .next()
+                .and_then(|element| element.attr(&captures[3]))
+                .unwrap_or_default()
+                .to_string());
+        }
+
+        if let Some(captures) = QUERY_COUNT.captures(query) {
+            let selector = parse_selector(&captures[2])?;
+            return Ok(document.select(&selector).count().to_string());
+        }
+
+        Err(Error::JsException(format!(
+            "No recorded result for script on {}",
+            url
+        )))
+    }
+
+    /// Answers a function call from the recordings of the page, returning the result as JSON
+    fn answer_call(&self, context_id: i64, function: &str, args: &str) -> Result<String> {
+        let page = self.page(context_id)?;
+        let args: serde_json::Value = match args.trim() {
+            "" => serde_json::Value::Array(Vec::new()),
+            args => serde_json::from_str(args).map_err(|e| Error::Failed(e.to_string()))?,
+        };
+
+        let scrolls = self.with_context(context_id, |context| Ok(context.scrolls))?;
+        recorded(page, &collapse_whitespace(function), Some(&args), scrolls)
+            .map(|result| result.to_string())
+            .ok_or_else(|| {
+                Error::JsException(format!(
+                    "No recorded result for function on {}",
+                    page.map(|page| page.url.as_str()).unwrap_or("about:blank")
+                ))
+            })
+    }
+}
+
+impl Backend for ReplayBackend {
+    fn run(&self, context_id: i64, task: TaskFn) -> Result<()> {
+        let cancelled = self
+            .contexts
+            .get(&context_id)
+            .map(|context| context.cancelled)
+            .unwrap_or_default();
+
+        if cancelled {
+            self.contexts.remove(&context_id);
+            return Err(Error::ContextClosed);
+        }
+
+        task(context_id);
+        self.contexts.remove(&context_id);
+        Ok(())
+    }
+
+    fn run_pooled(&self, context_id: i64, key: &str, task: TaskFn) -> Result<()> {
+        let warm = self
+            .idle
+            .lock()
+            .ok()
+            .and_then(|mut idle| idle.get_mut(key)?.pop())
+            .unwrap_or_default();
+
+        // The task starts on the page and with the cookies the previous task of the key left
+        {
+            let mut context = self.contexts.entry(context_id).or_default();
+            context.url = warm.url.clone();
+            context.cookies = warm.cookies.clone();
+        }
+        self.warm.insert(context_id, warm);
+
+        let result = self.run(context_id, task);
+
+        if let Some((_, mut warm)) = self.warm.remove(&context_id) {
+            warm.uses += 1;
+            let limits = self.pool.lock().map(|pool| pool.clone()).unwrap_or_default();
+            let reuse = result.is_ok() && !warm.discard && warm.uses < limits.max_uses;
+            if let Ok(mut idle) = self.idle.lock() {
+                let idle = idle.entry(key.to_string()).or_default();
+                if reuse && idle.len() < limits.max_idle {
+                    idle.push(warm);
+                }
+            }
+        }
+        result
+    }
+
+    fn configure_pool(&self, config: &PoolConfig) {
+        if let Ok(mut pool) = self.pool.lock() {
+            *pool = config.clone();
+        }
+    }
+
+    fn context_uses(&self, context_id: i64) -> usize {
+        self.warm.get(&context_id).map(|warm| warm.uses).unwrap_or_default()
+    }
+
+    fn discard_context(&self, context_id: i64) {
+        if let Some(mut warm) = self.warm.get_mut(&context_id) {
+            warm.discard = true;
+        }
+    }
+
+    fn cancel(&self, context_id: i64) {
+        self.contexts.entry(context_id).or_default().cancelled = true;
+    }
+
+    fn close_context(&self, context_id: i64) {
+        // Warm contexts keep the page and the cookies for the next task of their key
+        if let Some((_, context)) = self.contexts.remove(&context_id)
+            && let Some(mut warm) = self.warm.get_mut(&context_id)
+        {
+            warm.url = context.url;
+            warm.cookies = context.cookies;
+        }
+    }
+
+    fn navigate(&self, context_id: i64, url: &str) -> Result<()> {
+        let url = normalize_url(url);
+        let blocked = self.with_context(context_id, |context| {
+            Ok(context.blocked.iter().any(|rule| rule.matches(&url)))
+        })?;
+        if blocked {
+            return Err(Error::Navigation(format!("net::ERR_BLOCKED_BY_CLIENT at {}", url)));
+        }
+
+        let Some(page) = self.pages.get(&url) else {
+            return Err(Error::Navigation(format!("No fixture recorded for {}", url)));
+        };
+
+        self.with_context(context_id, |context| {
+            context.diagnostics.extend(page.diagnostics.clone());
+            context.url = Some(url);
+            context.consumed.clear();
+            context.scrolls = 0;
+            context.elements.clear();
+            Ok(())
+        })
+    }
+
+    fn set_user_agent(&self, context_id: i64, _user_agent: &str) -> Result<()> {
+        self.with_context(context_id, |_| Ok(()))
+    }
+
+    fn emulate(&self, context_id: i64, _emulation: &Emulation) -> Result<()> {
+        self.with_context(context_id, |_| Ok(()))
+    }
+
+    fn set_proxy(&self, context_id: i64, _proxy: &Proxy) -> Result<()> {
+        self.with_context(context_id, |_| Ok(()))
+    }
+
+    fn block_urls(&self, context_id: i64, rules: &[BlockRule]) -> Result<()> {
+        self.with_context(context_id, |context| {
+            context.blocked.extend_from_slice(rules);
+            Ok(())
+        })
+    }
+
+    fn add_init_script(&self, context_id: i64, _script: &str) -> Result<()> {
+        self.with_context(context_id, |_| Ok(()))
+    }
+
+    fn wait_for_element(&self, context_id: i64, selector: &str, _timeout: i64) -> Result<()> {
+        match self.count(context_id, selector)? {
+            0 => Err(Error::Timeout),
+            _ => Ok(()),
+        }
+    }
+
+    fn wait_for_network_idle(&self, context_id: i64, _idle: i64, _timeout: i64) -> Result<()> {
+        self.with_context(context_id, |_| Ok(()))
+    }
+
+    fn wait_for_function(&self, context_id: i64, function: &str, args: &str, _timeout: i64) -> Result<()> {
+        let result: serde_json::Value = serde_json::from_str(&self.answer_call(context_id, function, args)?)
+            .map_err(|e| Error::Failed(e.to_string()))?;
+
+        match result {
+            serde_json::Value::Null | serde_json::Value::Bool(false) => Err(Error::Timeout),
+            serde_json::Value::Number(n) if n.as_f64() == Some(0.0) => Err(Error::Timeout),
+            serde_json::Value::String(s) if s.is_empty() => Err(Error::Timeout),
+            _ => Ok(()),
+        }
+    }
+
+    fn wait_for_navigation(&self, context_id: i64, _timeout: i64) -> Result<String> {
+        // Recorded pages never navigate by themselves
+        self.with_context(context_id, |_| Err(Error::Timeout))
+    }
+
+    fn wait_for_selector_count(
+        &self,
+        context_id: i64,
+        selector: &str,
+        count: usize,
+        _timeout: i64,
+    ) -> Result<usize> {
+        match self.count(context_id, selector)? {
+            found if found >= count => Ok(found),
+            _ => Err(Error::Timeout),
+        }
+    }
+
+    fn write_input(&self, context_id: i64, selector: &str, _text: &str) -> Result<()> {
+        self.require(context_id, selector)
+    }
+
+    fn click_element(&self, context_id: i64, selector: &str) -> Result<()> {
+        self.require(context_id, selector)
+    }
+
+    fn hover_element(&self, context_id: i64, selector: &str) -> Result<()> {
+        self.require(context_id, selector)
+    }
+
+    fn mouse_move(&self, context_id: i64, _x: f64, _y: f64) -> Result<()> {
+        self.with_context(context_id, |_| Ok(()))
+    }
+
+    fn mouse_wheel(&self, context_id: i64, _delta_x: f64, delta_y: f64) -> Result<()> {
+        // Scrolling down with the wheel reveals the next recorded scroll step
+        self.with_context(context_id, |context| {
+            if delta_y > 0.0 {
+                context.scrolls += 1;
+            }
+            Ok(())
+        })
+    }
+
+    fn press_key(&self, context_id: i64, _key: &str) -> Result<()> {
+        self.with_context(context_id, |_| Ok(()))
+    }
+
+    fn select_option(&self, context_id: i64, selector: &str, values: &[String]) -> Result<Vec<String>> {
+        let select = parse_selector(selector)?;
+        let option = parse_selector("option")?;
+        let document = Html::parse_document(&self.html(context_id)?);
+        let element = document
+            .select(&select)
+            .find(|element| element.value().name() == "select")
+            .ok_or_else(|| Error::JsException(format!("No select element matches {}", selector)))?;
+
+        let multiple = element.attr("multiple").is_some();
+        let mut selected = Vec::new();
+        for option in element.select(&option) {
+            let label = option.text().collect::<String>().trim().to_string();
+            let value = option.attr("value").map(str::to_string).unwrap_or_else(|| label.clone());
+            if values.contains(&value) || values.contains(&label) {
+                selected.push(value);
+                if !multiple {
+                    break;
+                }
+            }
+        }
+        Ok(selected)
+    }
+
+    fn focus_element(&self, context_id: i64, selector: &str) -> Result<()> {
+        self.require(context_id, selector)
+    }
+
+    fn blur_element(&self, context_id: i64, selector: &str) -> Result<()> {
+        self.require(context_id, selector)
+    }
+
+    fn upload_files(&self, context_id: i64, selector: &str, _files: &[PathBuf]) -> Result<()> {
+        self.require(context_id, selector)
+    }
+
+    fn query_selector_all(&self, context_id: i64, parent: Option<i64>, selector: &str) -> Result<Vec<i64>> {
+        let selector = parse_selector(selector)?;
+        let document = Html::parse_document(&self.html(context_id)?);
+        let positions: HashMap<_, usize> = document
+            .tree
+            .nodes()
+            .enumerate()
+            .map(|(position, node)| (node.id(), position))
+            .collect();
+
+        let found: Vec<usize> = match parent {
+            Some(parent) => self
+                .resolve(context_id, &document, parent)?
+                .select(&selector)
+                .map(|element| positions[&element.id()])
+                .collect(),
+            None => document
+                .select(&selector)
+                .map(|element| positions[&element.id()])
+                .collect(),
+        };
+
+        self.with_context(context_id, |context| {
+            Ok(found
+                .into_iter()
+                .map(|position| {
+                    context.elements.push(position);
+                    context.elements.len() as i64
+                })
+                .collect())
+        })
+    }
+
+    fn element_text(&self, context_id: i64, element: i64) -> Result<String> {
+        self.with_element(context_id, element, |element| element.text().collect())
+    }
+
+    fn element_attribute(&self, context_id: i64, element: i64, name: &str) -> Result<Option<String>> {
+        self.with_element(context_id, element, |element| element.attr(name).map(str::to_string))
+    }
+
+    fn element_inner_html(&self, context_id: i64, element: i64) -> Result<String> {
+        self.with_element(context_id, element, |element| element.inner_html())
+    }
+
+    fn click_handle(&self, context_id: i64, element: i64) -> Result<()> {
+        self.with_element(context_id, element, |_| ())
+    }
+
+    fn hover_handle(&self, context_id: i64, element: i64) -> Result<()> {
+        self.with_element(context_id, element, |_| ())
+    }
+
+    fn string_cookies(&self, context_id: i64) -> Result<String> {
+        self.with_context(context_id, |context| {
+            Ok(context.cookies.clone().unwrap_or_else(|| "[]".to_string()))
+        })
+    }
+
+    fn set_string_cookies(&self, context_id: i64, cookies: &str) -> Result<()> {
+        self.with_context(context_id, |context| {
+            context.cookies = Some(cookies.to_string());
+            Ok(())
+        })
+    }
+
+    fn evaluate(&self, context_id: i64, expr: &str) -> Result<String> {
+        self.answer(context_id, expr)
+    }
+
+    fn async_evaluate(&self, context_id: i64, expr: &str) -> Result<String> {
+        self.answer(context_id, expr)
+    }
+
+    fn call_function(&self, context_id: i64, function: &str, args: &str) -> Result<String> {
+        self.answer_call(context_id, function, args)
+    }
+
+    fn async_call_function(&self, context_id: i64, function: &str, args: &str) -> Result<String> {
+        self.answer_call(context_id, function, args)
+    }
+
+    fn scroll(&self, context_id: i64) -> Result<()> {
+        self.with_context(context_id, |context| {
+            context.scrolls += 1;
+            Ok(())
+        })
+    }
+
+    fn get_html(&self, context_id: i64) -> Result<String> {
+        self.html(context_id)
+    }
+
+    fn screenshot(&self, context_id: i64, _options: &ScreenshotOptions) -> Result<Vec<u8>> {
+        match self.page(context_id)? {
+            Some(_) => Ok(BLANK_PNG.clone()),
+            None => Err(Error::Failed("No page loaded".to_string())),
+        }
+    }
+
+    fn capture_responses(&self, context_id: i64, pattern: &str) -> Result<()> {
+        let pattern = Regex::new(pattern).map_err(|e| Error::Failed(e.to_string()))?;
+        self.with_context(context_id, |context| {
+            context.patterns.push(pattern);
+            Ok(())
+        })
+    }
+
+    fn responses(&self, context_id: i64) -> Result<Vec<Response>> {
+        let patterns = self.with_context(context_id, |context| Ok(context.patterns.clone()))?;
+        self.take_responses(
+            context_id,
+            |url| patterns.iter().any(|p| p.is_match(url)),
+            usize::MAX,
+        )
+    }
+
+    fn wait_for_response(&self, context_id: i64, pattern: &str, _timeout: i64) -> Result<Response> {
+        let pattern = Regex::new(pattern).map_err(|e| Error::Failed(e.to_string()))?;
+        self.take_responses(context_id, |url| pattern.is_match(url), 1)?
+            .pop()
+            .ok_or(Error::Timeout)
+    }
+
+    fn diagnostics(&self, context_id: i64) -> Result<Diagnostics> {
+        self.with_context(context_id, |context| Ok(std::mem::take(&mut context.diagnostics)))
+    }
+}
+
+/// Finds the recorded result of a script on a page
+/// Recordings with `args` only match function calls receiving exactly those arguments,
+/// and among the matches the one for the latest scroll step reached wins
+fn recorded<'a>(
+    page: Option<&'a Page>,
+    script: &str,
+    args: Option<&serde_json::Value>,
+    scrolls: usize,
+) -> Option<&'a serde_json::Value> {
+    page?
+        .evaluations
+        .iter()
+        .rev()
+        .filter(|evaluation| {
+            script.contains(&collapse_whitespace(&evaluation.fragment))
+                && evaluation.scroll.is_none_or(|step| step <= scrolls)
+                && match (&evaluation.args, args) {
+                    (Some(expected), Some(args)) => expected == args,
+                    (Some(_), None) => false,
+                    (None, _) => true,
+                }
+        })
+        .max_by_key(|evaluation| evaluation.scroll.map(|step| step + 1).unwrap_or_default())
+        .map(|evaluation| &evaluation.result)
+}
+
+/// Parses a CSS selector, reporting invalid selectors as script exceptions like a browser would
+fn parse_selector(selector: &str) -> Result<Selector> {
+    Selector::parse(selector)
+        .map_err(|e| Error::JsException(format!("Invalid selector {}: {}", selector, e)))
+}
+
+/// Normalizes a URL so that fixtures match regardless of a trailing slash
+fn normalize_url(url: &str) -> String {
+    url::Url::parse(url)
+        .map(|url| url.to_string())
+        .unwrap_or_else(|_| url.to_string())
+        .trim_end_matches('/')
+        .to_string()
+}
+
+/// Collapses runs of whitespace into single spaces
+fn collapse_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
@@ -0,0 +1,127 @@
+//! Console messages, uncaught exceptions and failed requests recorded while a task runs
+
+use serde::{Deserialize, Serialize};
+use tracing::{debug, info, warn};
+
+/// A message written to the browser console by the page
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ConsoleMessage {
+    /// Console method used, e.g. `log`, `warning` or `error`
+    pub level: String,
+    /// Arguments of the call joined by spaces
+    pub text: String,
+    /// Script that wrote the message, empty if unknown
+    pub url: String,
+    /// One based line of the call in the script, 0 if unknown
+    pub line: i64,
+}
+
+/// An exception the page did not catch
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct PageError {
+    /// Exception description, including its stack when available
+    pub message: String,
+    /// Script that threw the exception, empty if unknown
+    pub url: String,
+    /// One based line of the exception, 0 if unknown
+    pub line: i64,
+    /// One based column of the exception, 0 if unknown
+    pub column: i64,
+}
+
+/// A network request that failed or got an HTTP error status
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct FailedRequest {
+    /// The requested URL
+    pub url: String,
+    /// HTTP method of the request
+    pub method: String,
+    /// Resource type reported by the browser, e.g. `XHR` or `Script`
+    pub resource_type: String,
+    /// HTTP status code, 0 when no response was received
+    pub status: i64,
+    /// Network error or status text
+    pub error: String,
+}
+
+/// Diagnostics of the pages visited by a task
+/// Returned by `Scraper::execute_with_diagnostics` and `Context::diagnostics`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct Diagnostics {
+    /// Messages written to the console
+    pub console: Vec<ConsoleMessage>,
+    /// Exceptions the page did not catch
+    pub exceptions: Vec<PageError>,
+    /// Requests that failed or got an HTTP error status
+    pub failed_requests: Vec<FailedRequest>,
+}
+
+impl Diagnostics {
+    /// Returns whether nothing was recorded
+    pub fn is_empty(&self) -> bool {
+        self.console.is_empty() && self.exceptions.is_empty() && self.failed_requests.is_empty()
+    }
+
+    /// Appends the diagnostics recorded later by the same task
+    pub fn extend(&mut self, other: Diagnostics) {
+        self.console.extend(other.console);
+        self.exceptions.extend(other.exceptions);
+        self.failed_requests.extend(other.failed_requests);
+    }
+
+    /// Emits every diagnostic as a `tracing` event tagged with the context ID
+    /// Uncaught exceptions are warnings, console errors and failed requests are info
+    /// and the rest of the console is debug
+    ///
+    /// # Arguments
+    /// * `context_id` - Context that recorded the diagnostics
+    pub fn log(&self, context_id: i64) {
+        for exception in &self.exceptions {
+            warn!(
+                context_id,
+                url = %exception.url,
+                line = exception.line,
+                column = exception.column,
+                "Uncaught page exception: {}",
+                exception.message
+            );
+        }
+
+        for message in &self.console {
+            match message.level.as_str() {
+                "error" | "assert" => info!(
+                    context_id,
+                    url = %message.url,
+                    line = message.line,
+                    "Console {}: {}",
+                    message.level,
+                    message.text
+                ),
+                _ => debug!(
+                    context_id,
+                    url = %message.url,
+                    line = message.line,
+                    "Console {}: {}",
+                    message.level,
+                    message.text
+                ),
+            }
+        }
+
+        for request in &self.failed_requests {
+            info!(
+                context_id,
+                method = %request.method,
+                resource_type = %request.resource_type,
+                status = request.status,
+                "Request to {} failed: {}",
+                request.url,
+                request.error
+            );
+        }
+    }
+}
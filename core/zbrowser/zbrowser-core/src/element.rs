@@ -0,0 +1,101 @@
+//! Element handles, for reading and interacting with elements without writing JavaScript
+
+use crate::{Context, Result};
+
+/// Reference to an element of the page loaded in a context
+///
+/// Handles are returned by `Context::query_selector_all` and stay valid until the page
+/// navigates. Queries pierce open shadow roots and same origin iframes, so web components
+/// like Reddit's `faceplate-*` elements can be searched with plain CSS selectors
+pub struct ElementHandle<'a> {
+    /// Context owning the element
+    context: &'a Context,
+    /// Handle ID given by the backend
+    id: i64,
+}
+
+impl ElementHandle<'_> {
+    /// Returns the handle ID given by the backend
+    pub fn id(&self) -> i64 {
+        self.id
+    }
+
+    /// Returns the text content of the element and its descendants
+    pub fn text(&self) -> Result<String> {
+        self.context.backend.element_text(self.context.id, self.id)
+    }
+
+    /// Returns the value of an attribute
+    ///
+    /// # Arguments
+    /// * `name` - Attribute name
+    ///
+    /// # Returns
+    /// The value, or `None` if the element does not have the attribute
+    pub fn attr<T: AsRef<str>>(&self, name: T) -> Result<Option<String>> {
+        self.context
+            .backend
+            .element_attribute(self.context.id, self.id, name.as_ref())
+    }
+
+    /// Returns the HTML of the children of the element
+    pub fn inner_html(&self) -> Result<String> {
+        self.context.backend.element_inner_html(self.context.id, self.id)
+    }
+
+    /// Scrolls the element into view and clicks its center with the mouse
+    /// Fails if the element takes no space on the page
+    pub fn click(&self) -> Result<()> {
+        self.context.backend.click_handle(self.context.id, self.id)
+    }
+
+    /// Scrolls the element into view and moves the mouse over its center
+    /// Fails if the element takes no space on the page
+    pub fn hover(&self) -> Result<()> {
+        self.context.backend.hover_handle(self.context.id, self.id)
+    }
+
+    /// Finds the descendants of the element matching a CSS selector
+    pub fn query_selector_all<T: AsRef<str>>(&self, selector: T) -> Result<Vec<ElementHandle<'_>>> {
+        self.context.query(Some(self.id), selector.as_ref())
+    }
+
+    /// Finds the first descendant of the element matching a CSS selector
+    pub fn query_selector<T: AsRef<str>>(&self, selector: T) -> Result<Option<ElementHandle<'_>>> {
+        Ok(self.query_selector_all(selector)?.into_iter().next())
+    }
+}
+
+impl Context {
+    /// Finds the elements matching a CSS selector under a parent handle, or in the whole page
+    fn query(&self, parent: Option<i64>, selector: &str) -> Result<Vec<ElementHandle<'_>>> {
+        let ids = self.backend.query_selector_all(self.id, parent, selector)?;
+        Ok(ids
+            .into_iter()
+            .map(|id| ElementHandle { context: self, id })
+            .collect())
+    }
+
+    /// Finds the elements of the page matching a CSS selector
+    /// The search pierces open shadow roots and same origin iframes
+    ///
+    /// # Arguments
+    /// * `selector` - CSS selector
+    ///
+    /// # Returns
+    /// Handles of the matching elements, empty if none matches
+    pub fn query_selector_all<T: AsRef<str>>(&self, selector: T) -> Result<Vec<ElementHandle<'_>>> {
+        self.query(None, selector.as_ref())
+    }
+
+    /// Finds the first element of the page matching a CSS selector
+    ///
+    /// # Arguments
+    /// * `selector` - CSS selector
+    ///
+    /// # Returns
+    /// Handle of the element, or `None` if no element matches
+    pub fn query_selector<T: AsRef<str>>(&self, selector: T) -> Result<Option<ElementHandle<'_>>> {
+        Ok(self.query_selector_all(selector)?.into_iter().next())
+    }
+}
@@ -0,0 +1,137 @@
+//! Device, language and location emulation of browser contexts
+
+use serde::Serialize;
+
+/// Size and kind of the screen a context renders for
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Viewport {
+    /// Width in CSS pixels
+    pub width: u32,
+    /// Height in CSS pixels
+    pub height: u32,
+    /// Ratio between device pixels and CSS pixels
+    pub device_scale_factor: f64,
+    /// Whether pages see a mobile device, which enables the mobile layout of sites
+    pub mobile: bool,
+    /// Whether touch events are emulated
+    pub touch: bool,
+}
+
+impl Viewport {
+    /// Creates a desktop viewport of the given size
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            device_scale_factor: 1.0,
+            mobile: false,
+            touch: false,
+        }
+    }
+
+    /// Full HD desktop screen, the size of the window launched by the scraper
+    pub fn desktop() -> Self {
+        Self::new(1920, 1080)
+    }
+
+    /// Typical phone screen with touch support
+    pub fn mobile() -> Self {
+        Self {
+            width: 390,
+            height: 844,
+            device_scale_factor: 3.0,
+            mobile: true,
+            touch: true,
+        }
+    }
+
+    /// Sets the ratio between device pixels and CSS pixels
+    pub fn with_scale(mut self, device_scale_factor: f64) -> Self {
+        self.device_scale_factor = device_scale_factor;
+        self
+    }
+}
+
+/// Position reported by the Geolocation API
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Geolocation {
+    /// Latitude in degrees
+    pub latitude: f64,
+    /// Longitude in degrees
+    pub longitude: f64,
+    /// Accuracy in meters
+    pub accuracy: f64,
+}
+
+/// Overrides applied to a context by `Context::emulate`
+/// Only the settings given are changed, the rest keep their current value
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Emulation {
+    /// Screen size and device kind
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub viewport: Option<Viewport>,
+    /// User agent string
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user_agent: Option<String>,
+    /// Value of the `Accept-Language` header and `navigator.languages`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub accept_language: Option<String>,
+    /// ICU locale used by `Intl` and date formatting, e.g. `es-MX`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub locale: Option<String>,
+    /// IANA time zone, e.g. `America/Mexico_City`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timezone: Option<String>,
+    /// Position reported to pages, the geolocation permission is granted with it
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub geolocation: Option<Geolocation>,
+}
+
+impl Emulation {
+    /// Creates an emulation that changes nothing
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the screen size and device kind
+    pub fn viewport(mut self, viewport: Viewport) -> Self {
+        self.viewport = Some(viewport);
+        self
+    }
+
+    /// Sets the user agent string
+    pub fn user_agent<T: Into<String>>(mut self, user_agent: T) -> Self {
+        self.user_agent = Some(user_agent.into());
+        self
+    }
+
+    /// Sets the `Accept-Language` header, e.g. `es-MX,es;q=0.9`
+    pub fn accept_language<T: Into<String>>(mut self, accept_language: T) -> Self {
+        self.accept_language = Some(accept_language.into());
+        self
+    }
+
+    /// Sets the ICU locale, e.g. `es-MX`
+    pub fn locale<T: Into<String>>(mut self, locale: T) -> Self {
+        self.locale = Some(locale.into());
+        self
+    }
+
+    /// Sets the IANA time zone, e.g. `America/Mexico_City`
+    pub fn timezone<T: Into<String>>(mut self, timezone: T) -> Self {
+        self.timezone = Some(timezone.into());
+        self
+    }
+
+    /// Sets the position reported to pages, with an accuracy of 100 meters
+    pub fn geolocation(mut self, latitude: f64, longitude: f64) -> Self {
+        self.geolocation = Some(Geolocation {
+            latitude,
+            longitude,
+            accuracy: 100.0,
+        });
+        self
+    }
+}
@@ -0,0 +1,76 @@
+//! Error types returned by browser contexts and scrapers
+
+use std::{fmt, time::Duration};
+
+/// Result type used across the browser automation API
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// Errors reported by the native browser layer
+/// Each variant matches one of the result codes returned by the Go exports
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// The operation did not finish before its deadline
+    Timeout,
+    /// The page could not be loaded
+    Navigation(String),
+    /// The evaluated script threw an exception
+    JsException(String),
+    /// The browser context was closed or no longer exists
+    ContextClosed,
+    /// Any other browser or protocol failure
+    Failed(String),
+    /// The whole task exceeded the limit given to `Scraper::execute_with_timeout`
+    TaskTimeout(Duration),
+    /// The named session is missing or was rejected by the site
+    SessionInvalid(String),
+    /// The connection to the remote browser was lost while the task ran,
+    /// or it was not restored in time for the task to start
+    Disconnected(String),
+    /// The scheduler of the scraper already had the given number of tasks waiting for a worker
+    QueueFull(usize),
+    /// The browser or the renderer of the tab crashed while the task ran,
+    /// or it was not relaunched in time for the task to start
+    BrowserCrashed(String),
+    /// The task went over a resource limit of its tab and the tab was closed
+    LimitExceeded(String),
+}
+
+impl Error {
+    /// Builds an error from a native result code and the message recorded for it
+    ///
+    /// # Arguments
+    /// * `code` - Non-zero result code returned by the native layer
+    /// * `message` - Error message recorded by the native layer
+    pub fn from_code(code: i64, message: String) -> Self {
+        match code {
+            2 => Error::Timeout,
+            3 => Error::Navigation(message),
+            4 => Error::JsException(message),
+            5 => Error::ContextClosed,
+            6 => Error::Disconnected(message),
+            7 => Error::BrowserCrashed(message),
+            8 => Error::LimitExceeded(message),
+            _ => Error::Failed(message),
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Timeout => write!(f, "Operation timed out"),
+            Error::Navigation(message) => write!(f, "Navigation failed: {}", message),
+            Error::JsException(message) => write!(f, "JavaScript exception: {}", message),
+            Error::ContextClosed => write!(f, "Browser context is closed"),
+            Error::Failed(message) => write!(f, "Browser operation failed: {}", message),
+            Error::TaskTimeout(limit) => write!(f, "Task exceeded its time limit of {:?}", limit),
+            Error::SessionInvalid(name) => write!(f, "Session {} is no longer valid", name),
+            Error::Disconnected(message) => write!(f, "Remote browser disconnected: {}", message),
+            Error::QueueFull(queued) => write!(f, "Task queue is full with {} waiting tasks", queued),
+            Error::BrowserCrashed(message) => write!(f, "Browser crashed: {}", message),
+            Error::LimitExceeded(message) => write!(f, "Task went over a resource limit: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
@@ -0,0 +1,102 @@
+//! Request blocking rules and init scripts of a context
+
+use crate::{Context, Error, Result};
+use regex::Regex;
+
+/// Rule blocking the requests of a context whose URL matches it
+/// Every rule is kept as a regular expression matched against the full request URL
+#[derive(Debug, Clone)]
+pub struct BlockRule {
+    regex: Regex,
+}
+
+impl BlockRule {
+    /// Creates a rule from a glob matched against the whole URL
+    /// `*` matches any run of characters and `?` a single one
+    ///
+    /// # Arguments
+    /// * `glob` - Pattern such as `*://*.doubleclick.net/*` or `*.mp4`
+    pub fn glob(glob: &str) -> Self {
+        let mut pattern = String::from("^");
+        for c in glob.chars() {
+            match c {
+                '*' => pattern.push_str(".*"),
+                '?' => pattern.push('.'),
+                c => pattern.push_str(&regex::escape(&c.to_string())),
+            }
+        }
+        pattern.push('$');
+
+        Self {
+            regex: Regex::new(&pattern).expect("escaped globs are valid regular expressions"),
+        }
+    }
+
+    /// Creates a rule from a regular expression, matching anywhere in the URL unless anchored
+    ///
+    /// # Arguments
+    /// * `regex` - Regular expression in the syntax shared by Rust and Go
+    ///
+    /// # Returns
+    /// The rule, or `Error::Failed` if the expression is invalid
+    pub fn regex(regex: &str) -> Result<Self> {
+        let regex = Regex::new(regex).map_err(|e| Error::Failed(e.to_string()))?;
+        Ok(Self { regex })
+    }
+
+    /// Creates a rule blocking every request to a domain and its subdomains,
+    /// whatever the scheme, port or path
+    ///
+    /// # Arguments
+    /// * `domain` - Domain such as `doubleclick.net`
+    pub fn domain(domain: &str) -> Self {
+        let pattern = format!(
+            r"^[a-z][a-z0-9+.-]*://([^/?#]*\.)?{}([:/?#]|$)",
+            regex::escape(&domain.trim_matches('.').to_lowercase())
+        );
+
+        Self {
+            regex: Regex::new(&pattern).expect("escaped domains are valid regular expressions"),
+        }
+    }
+
+    /// Returns the regular expression of the rule
+    pub fn pattern(&self) -> &str {
+        self.regex.as_str()
+    }
+
+    /// Returns whether the rule blocks a URL
+    pub fn matches(&self, url: &str) -> bool {
+        self.regex.is_match(url)
+    }
+}
+
+impl PartialEq for BlockRule {
+    fn eq(&self, other: &Self) -> bool {
+        self.pattern() == other.pattern()
+    }
+}
+
+impl Eq for BlockRule {}
+
+impl Context {
+    /// Fails the requests of the context whose URL matches one of the rules
+    /// The rules apply until the task ends, along with the resource types blocked by the scraper,
+    /// so analytics, ads or video requests can be dropped while the scripts of the page still load
+    ///
+    /// # Arguments
+    /// * `rules` - Rules matched against the full URL of each request
+    pub fn block_urls(&self, rules: &[BlockRule]) -> Result<()> {
+        self.backend.block_urls(self.id, rules)
+    }
+
+    /// Evaluates a script before the scripts of every page the context loads
+    /// The script runs from the next navigation until the task ends,
+    /// the current page is not affected
+    ///
+    /// # Arguments
+    /// * `script` - JavaScript source of the script
+    pub fn add_init_script<T: AsRef<str>>(&self, script: T) -> Result<()> {
+        self.backend.add_init_script(self.id, script.as_ref())
+    }
+}
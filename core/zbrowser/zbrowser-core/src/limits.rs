@@ -0,0 +1,55 @@
+//! Resource limits of tasks and the browser failures a scraper recovered from
+
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// Resources a task may use in its browser tab
+/// Tabs of tasks going over a limit are closed and replaced by fresh ones,
+/// and the task fails with `Error::LimitExceeded`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ResourceLimits {
+    /// Maximum JavaScript heap of the page in megabytes, no limit when `None`
+    pub max_heap_mb: Option<u64>,
+    /// Maximum time a task runs in the browser, no limit when `None`
+    /// Unlike `Scraper::execute_with_timeout` it does not count the time spent in the queue
+    pub max_task_time: Option<Duration>,
+}
+
+impl ResourceLimits {
+    /// Creates limits that bound nothing
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the maximum JavaScript heap of the page
+    ///
+    /// # Arguments
+    /// * `megabytes` - Heap size in megabytes after which the tab is closed
+    pub fn with_max_heap_mb(mut self, megabytes: u64) -> Self {
+        self.max_heap_mb = Some(megabytes);
+        self
+    }
+
+    /// Sets the maximum time a task runs in the browser
+    ///
+    /// # Arguments
+    /// * `limit` - Duration after which the task is stopped and its tab closed
+    pub fn with_max_task_time(mut self, limit: Duration) -> Self {
+        self.max_task_time = Some(limit);
+        self
+    }
+}
+
+/// Browser failures a scraper recovered from since it was created
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct BrowserMetrics {
+    /// Browsers or renderers that crashed or stopped answering
+    pub crashes: u64,
+    /// Connections to a remote browser that were lost
+    pub disconnects: u64,
+    /// Tabs reopened after a crash, a lost connection or a resource limit
+    pub restarts: u64,
+    /// Tabs closed because a task went over a resource limit
+    pub limit_kills: u64,
+}
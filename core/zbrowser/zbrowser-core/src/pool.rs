@@ -0,0 +1,50 @@
+//! Warm contexts reused between the tasks of a session
+
+use crate::Context;
+
+/// Limits of the pool of warm contexts used by `Scraper::execute_pooled`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PoolConfig {
+    /// Tasks a warm context runs before it is closed and replaced by a fresh one
+    pub max_uses: usize,
+    /// Idle warm contexts kept for each key, further contexts are closed when their task ends
+    pub max_idle: usize,
+}
+
+impl Default for PoolConfig {
+    fn default() -> Self {
+        Self {
+            max_uses: 50,
+            max_idle: 4,
+        }
+    }
+}
+
+impl PoolConfig {
+    /// Creates pool limits
+    ///
+    /// # Arguments
+    /// * `max_uses` - Tasks a warm context runs before it is replaced, at least one
+    /// * `max_idle` - Idle warm contexts kept for each key, at least one
+    pub fn new(max_uses: usize, max_idle: usize) -> Self {
+        Self {
+            max_uses: max_uses.max(1),
+            max_idle: max_idle.max(1),
+        }
+    }
+}
+
+impl Context {
+    /// Returns the number of earlier tasks that ran in this context
+    /// Always zero for tasks executed outside the pool
+    pub fn uses(&self) -> usize {
+        self.backend.context_uses(self.id)
+    }
+
+    /// Returns whether the context is a warm context left by an earlier task of its key
+    /// Warm contexts keep the cookies, user agent, emulation and proxy set by that task,
+    /// so the task can skip restoring them
+    pub fn is_warm(&self) -> bool {
+        self.uses() > 0
+    }
+}
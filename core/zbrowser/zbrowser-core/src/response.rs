@@ -0,0 +1,45 @@
+//! Network responses captured from a browser context
+
+use crate::{Error, Result};
+use serde::{Deserialize, de::DeserializeOwned};
+use std::collections::HashMap;
+
+/// A network response captured by `Context::capture_responses` or `Context::wait_for_response`
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct Response {
+    /// The requested URL
+    pub url: String,
+    /// HTTP method of the request
+    pub method: String,
+    /// HTTP status code
+    pub status: i64,
+    /// MIME type reported by the browser
+    pub mime_type: String,
+    /// Response headers
+    pub headers: HashMap<String, String>,
+    /// Decoded response body
+    pub body: String,
+}
+
+impl Response {
+    /// Parses the response body as JSON
+    /// 
+    /// # Type Parameters
+    /// * `T` - Type to deserialize the body into
+    /// 
+    /// # Returns
+    /// The parsed body, or `Error::Failed` if the body is not valid JSON for `T`
+    pub fn json<T: DeserializeOwned>(&self) -> Result<T> {
+        serde_json::from_str(&self.body)
+            .map_err(|e| Error::Failed(format!("Invalid JSON body from {}: {}", self.url, e)))
+    }
+
+    /// Returns the value of a header, ignoring the case of its name
+    pub fn header<T: AsRef<str>>(&self, name: T) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name.as_ref()))
+            .map(|(_, value)| value.as_str())
+    }
+}
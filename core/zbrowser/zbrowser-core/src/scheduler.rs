@@ -0,0 +1,461 @@
+//! Scheduling of the tasks of a scraper
+//!
+//! At most `workers` tasks run at once, the rest wait in a bounded queue. When a worker
+//! frees up, the waiting task with the highest priority starts, and tasks of the same
+//! priority are taken from each tenant in turn, so one tenant queueing many tasks does
+//! not starve the others.
+
+use crate::{Error, Result};
+use serde::Serialize;
+use std::{
+    collections::{BTreeMap, HashMap, VecDeque},
+    future::Future,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+use tokio::sync::oneshot;
+
+tokio::task_local! {
+    /// Options applied to the tasks executed inside `TaskOptions::scope`
+    static TASK_OPTIONS: TaskOptions;
+}
+
+/// Upper bounds in milliseconds of the buckets of the time histograms
+const BUCKET_BOUNDS_MS: [u64; 12] = [
+    10, 50, 100, 250, 500, 1_000, 2_500, 5_000, 10_000, 30_000, 60_000, 300_000,
+];
+
+/// Priority of a task, waiting tasks with a higher priority start first
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Priority {
+    /// Background work such as follow-up lookups
+    Low,
+    /// Default priority
+    #[default]
+    Normal,
+    /// Work a user is waiting for
+    High,
+}
+
+/// Scheduling options of a task
+///
+/// They are given to `Scraper::execute_with_options`, or applied with `scope` to every
+/// task executed by a future, including tasks started deep inside library code
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TaskOptions {
+    /// Priority of the task
+    pub priority: Priority,
+    /// Tenant the task belongs to, tasks without tenant share one queue
+    pub tenant: Option<String>,
+}
+
+impl TaskOptions {
+    /// Creates options with normal priority and no tenant
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the options of the enclosing `scope`, or the defaults outside of any scope
+    pub fn current() -> Self {
+        TASK_OPTIONS.try_with(Clone::clone).unwrap_or_default()
+    }
+
+    /// Sets the priority of the task
+    pub fn with_priority(mut self, priority: Priority) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    /// Sets the tenant of the task, e.g. the user it runs for
+    pub fn with_tenant<T: Into<String>>(mut self, tenant: T) -> Self {
+        self.tenant = Some(tenant.into());
+        self
+    }
+
+    /// Runs a future with these options applied to every task it executes
+    /// Futures moved to other tokio tasks with `tokio::spawn` do not inherit them
+    ///
+    /// # Arguments
+    /// * `future` - Future executing the tasks
+    ///
+    /// # Returns
+    /// The output of the future
+    pub async fn scope<F: Future>(self, future: F) -> F::Output {
+        TASK_OPTIONS.scope(self, future).await
+    }
+}
+
+/// Limits of the scheduler of a scraper
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SchedulerConfig {
+    /// Number of tasks that run at once
+    pub workers: usize,
+    /// Number of tasks that may wait for a worker, further tasks fail with `Error::QueueFull`
+    pub max_queue: usize,
+}
+
+impl Default for SchedulerConfig {
+    fn default() -> Self {
+        Self {
+            workers: std::thread::available_parallelism().map_or(4, |n| n.get()),
+            max_queue: usize::MAX,
+        }
+    }
+}
+
+impl SchedulerConfig {
+    /// Creates a configuration running the given number of tasks at once, with an unbounded queue
+    ///
+    /// # Arguments
+    /// * `workers` - Number of tasks that run at once, at least one
+    pub fn new(workers: usize) -> Self {
+        Self {
+            workers: workers.max(1),
+            ..Default::default()
+        }
+    }
+
+    /// Sets the number of tasks that may wait for a worker
+    pub fn with_max_queue(mut self, max_queue: usize) -> Self {
+        self.max_queue = max_queue;
+        self
+    }
+}
+
+/// Histogram of durations with fixed buckets
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Histogram {
+    /// Upper bound in milliseconds of each bucket but the last, which has no bound
+    pub bounds_ms: Vec<u64>,
+    /// Number of durations in each bucket, one more than `bounds_ms`
+    pub counts: Vec<u64>,
+    /// Number of durations recorded
+    pub count: u64,
+    /// Sum of the durations recorded, in milliseconds
+    pub sum_ms: u64,
+}
+
+impl Default for Histogram {
+    fn default() -> Self {
+        Self {
+            bounds_ms: BUCKET_BOUNDS_MS.to_vec(),
+            counts: vec![0; BUCKET_BOUNDS_MS.len() + 1],
+            count: 0,
+            sum_ms: 0,
+        }
+    }
+}
+
+impl Histogram {
+    /// Records a duration
+    pub fn record(&mut self, duration: Duration) {
+        let ms = duration.as_millis().min(u64::MAX as u128) as u64;
+        let bucket = self.bounds_ms.partition_point(|&bound| bound < ms);
+        self.counts[bucket] += 1;
+        self.count += 1;
+        self.sum_ms = self.sum_ms.saturating_add(ms);
+    }
+
+    /// Returns the mean of the durations recorded, if any
+    pub fn mean(&self) -> Option<Duration> {
+        (self.count > 0).then(|| Duration::from_millis(self.sum_ms / self.count))
+    }
+
+    /// Returns the upper bound of the bucket holding the given quantile, if any duration was recorded
+    /// `None` is also returned when the quantile falls in the last bucket, which has no bound
+    ///
+    /// # Arguments
+    /// * `quantile` - Quantile between 0 and 1, e.g. 0.95
+    pub fn quantile(&self, quantile: f64) -> Option<Duration> {
+        if self.count == 0 {
+            return None;
+        }
+
+        let rank = ((self.count as f64) * quantile.clamp(0.0, 1.0)).ceil().max(1.0) as u64;
+        let mut seen = 0;
+        for (bucket, count) in self.counts.iter().enumerate() {
+            seen += count;
+            if seen >= rank {
+                return self.bounds_ms.get(bucket).map(|&ms| Duration::from_millis(ms));
+            }
+        }
+        None
+    }
+}
+
+/// Snapshot of the state of a scheduler, returned by `Scraper::metrics`
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SchedulerMetrics {
+    /// Number of tasks that run at once
+    pub workers: usize,
+    /// Tasks running, each one with its browser context
+    pub active: usize,
+    /// Tasks waiting for a worker
+    pub queued: usize,
+    /// Tasks waiting for a worker by priority
+    pub queued_by_priority: BTreeMap<Priority, usize>,
+    /// Tasks waiting for a worker by tenant, tasks without tenant are under an empty name
+    pub queued_by_tenant: BTreeMap<String, usize>,
+    /// Tasks that ran to completion, successfully or not
+    pub completed: u64,
+    /// Tasks rejected because the queue was full
+    pub rejected: u64,
+    /// Time tasks waited for a worker
+    pub wait: Histogram,
+    /// Time tasks ran
+    pub run: Histogram,
+}
+
+/// A task waiting for a worker
+struct Waiter {
+    id: u64,
+    queued_at: Instant,
+    sender: oneshot::Sender<Permit>,
+}
+
+/// Waiting tasks of one priority, taken from each tenant in turn
+#[derive(Default)]
+struct Lane {
+    /// Tenants with waiting tasks, in the order they get their next turn
+    turns: VecDeque<String>,
+    /// Waiting tasks of each tenant in arrival order
+    tasks: HashMap<String, VecDeque<Waiter>>,
+}
+
+impl Lane {
+    fn push(&mut self, tenant: String, waiter: Waiter) {
+        let tasks = self.tasks.entry(tenant.clone()).or_default();
+        if tasks.is_empty() {
+            self.turns.push_back(tenant);
+        }
+        tasks.push_back(waiter);
+    }
+
+    fn pop(&mut self) -> Option<Waiter> {
+        let tenant = self.turns.pop_front()?;
+        let tasks = self.tasks.get_mut(&tenant)?;
+        let waiter = tasks.pop_front();
+
+        if tasks.is_empty() {
+            self.tasks.remove(&tenant);
+        } else {
+            self.turns.push_back(tenant);
+        }
+        waiter
+    }
+
+    fn remove(&mut self, tenant: &str, id: u64) -> bool {
+        let Some(tasks) = self.tasks.get_mut(tenant) else {
+            return false;
+        };
+        let Some(position) = tasks.iter().position(|waiter| waiter.id == id) else {
+            return false;
+        };
+
+        tasks.remove(position);
+        if tasks.is_empty() {
+            self.tasks.remove(tenant);
+            self.turns.retain(|turn| turn != tenant);
+        }
+        true
+    }
+}
+
+/// Mutable state of a scheduler
+#[derive(Default)]
+struct State {
+    active: usize,
+    queued: usize,
+    next_id: u64,
+    lanes: BTreeMap<Priority, Lane>,
+    completed: u64,
+    rejected: u64,
+    wait: Histogram,
+    run: Histogram,
+}
+
+/// State shared by a scheduler and its permits
+struct Shared {
+    config: SchedulerConfig,
+    state: Mutex<State>,
+}
+
+impl Shared {
+    /// Frees the worker of a finished task and starts the next waiting tasks
+    fn release(self: &Arc<Self>, started: Instant) {
+        let Ok(mut state) = self.state.lock() else {
+            return;
+        };
+        state.active -= 1;
+        state.completed += 1;
+        state.run.record(started.elapsed());
+        self.dispatch(&mut state);
+    }
+
+    /// Hands free workers to the waiting tasks, highest priority first
+    fn dispatch(self: &Arc<Self>, state: &mut State) {
+        while state.active < self.config.workers {
+            let Some(waiter) = state.lanes.values_mut().rev().find_map(Lane::pop) else {
+                return;
+            };
+            state.queued -= 1;
+
+            let permit = Permit {
+                shared: Some(Arc::clone(self)),
+                started: Instant::now(),
+            };
+            match waiter.sender.send(permit) {
+                Ok(()) => {
+                    state.active += 1;
+                    state.wait.record(waiter.queued_at.elapsed());
+                }
+                // The task gave up while the permit was handed over, its worker stays free
+                Err(mut permit) => permit.shared = None,
+            }
+        }
+    }
+}
+
+/// Right of a task to run on a worker, the worker is freed when it is dropped
+pub(crate) struct Permit {
+    shared: Option<Arc<Shared>>,
+    started: Instant,
+}
+
+impl Drop for Permit {
+    fn drop(&mut self) {
+        if let Some(shared) = self.shared.take() {
+            shared.release(self.started);
+        }
+    }
+}
+
+/// Removes a waiting task from the queue when the caller gives up before it started
+struct Queued<'a> {
+    shared: &'a Arc<Shared>,
+    priority: Priority,
+    tenant: String,
+    id: u64,
+    waiting: bool,
+}
+
+impl Drop for Queued<'_> {
+    fn drop(&mut self) {
+        if !self.waiting {
+            return;
+        }
+        if let Ok(mut state) = self.shared.state.lock() {
+            let removed = state
+                .lanes
+                .get_mut(&self.priority)
+                .is_some_and(|lane| lane.remove(&self.tenant, self.id));
+            if removed {
+                state.queued -= 1;
+            }
+        }
+    }
+}
+
+/// Admits the tasks of a scraper to its workers
+pub(crate) struct Scheduler {
+    shared: Arc<Shared>,
+}
+
+impl Scheduler {
+    /// Creates a scheduler with the given limits
+    pub(crate) fn new(config: SchedulerConfig) -> Self {
+        Self {
+            shared: Arc::new(Shared {
+                config,
+                state: Mutex::new(State::default()),
+            }),
+        }
+    }
+
+    /// Waits for a worker to be available for a task
+    ///
+    /// # Arguments
+    /// * `options` - Priority and tenant of the task
+    ///
+    /// # Returns
+    /// The permit to hold while the task runs, or `Error::QueueFull` if too many tasks are waiting
+    pub(crate) async fn acquire(&self, options: &TaskOptions) -> Result<Permit> {
+        let tenant = options.tenant.clone().unwrap_or_default();
+
+        let (id, receiver) = {
+            let mut state = self
+                .shared
+                .state
+                .lock()
+                .map_err(|_| Error::Failed("Scheduler state is poisoned".to_string()))?;
+
+            if state.active < self.shared.config.workers && state.queued == 0 {
+                state.active += 1;
+                state.wait.record(Duration::ZERO);
+                return Ok(Permit {
+                    shared: Some(Arc::clone(&self.shared)),
+                    started: Instant::now(),
+                });
+            }
+
+            if state.queued >= self.shared.config.max_queue {
+                state.rejected += 1;
+                return Err(Error::QueueFull(state.queued));
+            }
+
+            let (sender, receiver) = oneshot::channel();
+            let id = state.next_id;
+            state.next_id += 1;
+            state.queued += 1;
+            state.lanes.entry(options.priority).or_default().push(
+                tenant.clone(),
+                Waiter {
+                    id,
+                    queued_at: Instant::now(),
+                    sender,
+                },
+            );
+            (id, receiver)
+        };
+
+        let mut queued = Queued {
+            shared: &self.shared,
+            priority: options.priority,
+            tenant,
+            id,
+            waiting: true,
+        };
+        let permit = receiver.await;
+        queued.waiting = false;
+
+        permit.map_err(|_| Error::Failed("Scheduler dropped the task".to_string()))
+    }
+
+    /// Returns the current state of the scheduler
+    pub(crate) fn metrics(&self) -> SchedulerMetrics {
+        let Ok(state) = self.shared.state.lock() else {
+            return SchedulerMetrics::default();
+        };
+
+        let mut metrics = SchedulerMetrics {
+            workers: self.shared.config.workers,
+            active: state.active,
+            queued: state.queued,
+            completed: state.completed,
+            rejected: state.rejected,
+            wait: state.wait.clone(),
+            run: state.run.clone(),
+            ..Default::default()
+        };
+        for (priority, lane) in &state.lanes {
+            for (tenant, tasks) in &lane.tasks {
+                *metrics.queued_by_priority.entry(*priority).or_default() += tasks.len();
+                *metrics.queued_by_tenant.entry(tenant.clone()).or_default() += tasks.len();
+            }
+        }
+        metrics
+    }
+}
@@ -0,0 +1,141 @@
+//! Harvesting of items from pages that load more content while scrolling
+
+use crate::{Context, Error, Result, function_args, parse_result};
+use serde::{Serialize, de::DeserializeOwned};
+use std::{
+    collections::HashSet,
+    time::{Duration, Instant},
+};
+
+/// Limits of a `Context::scroll_collect` run
+/// The run stops at whichever limit is reached first
+#[derive(Debug, Clone)]
+pub struct ScrollOptions {
+    /// Number of unique items to collect
+    pub target: usize,
+    /// Maximum number of scroll steps
+    pub max_scrolls: usize,
+    /// Time without new items after which the page is considered exhausted
+    pub stall_timeout: Duration,
+    /// Quiet network period awaited after each scroll step
+    pub settle: Duration,
+}
+
+impl Default for ScrollOptions {
+    fn default() -> Self {
+        Self {
+            target: usize::MAX,
+            max_scrolls: 30,
+            stall_timeout: Duration::from_secs(5),
+            settle: Duration::from_millis(500),
+        }
+    }
+}
+
+impl ScrollOptions {
+    /// Creates options that stop once the given number of unique items was collected
+    ///
+    /// # Arguments
+    /// * `target` - Number of unique items to collect
+    pub fn new(target: usize) -> Self {
+        Self {
+            target,
+            ..Default::default()
+        }
+    }
+
+    /// Sets the maximum number of scroll steps
+    pub fn with_max_scrolls(mut self, max_scrolls: usize) -> Self {
+        self.max_scrolls = max_scrolls;
+        self
+    }
+
+    /// Sets the time without new items after which the run stops
+    pub fn with_stall_timeout(mut self, stall_timeout: Duration) -> Self {
+        self.stall_timeout = stall_timeout;
+        self
+    }
+
+    /// Sets the quiet network period awaited after each scroll step
+    pub fn with_settle(mut self, settle: Duration) -> Self {
+        self.settle = settle;
+        self
+    }
+}
+
+impl Context {
+    /// Scrolls the page down by one viewport
+    pub fn scroll(&self) -> Result<()> {
+        self.backend.scroll(self.id)
+    }
+
+    /// Scrolls the page repeatedly, extracting items after each step
+    ///
+    /// The extraction function runs before the first scroll and after every step, and must
+    /// return an array of items. Items are deduplicated by key, so lists that recycle their
+    /// elements while scrolling (like X's timeline) are collected completely
+    ///
+    /// # Arguments
+    /// * `extract` - JavaScript function declaration returning the visible items,
+    ///   awaited if it returns a Promise
+    /// * `args` - Arguments of the function, as in `evaluate_json`
+    /// * `key` - Function returning the identity of an item
+    /// * `options` - Target count, scroll depth and stall timeout of the run
+    ///
+    /// # Returns
+    /// The unique items in the order they were found, at most `options.target`
+    pub fn scroll_collect<T, A, K>(
+        &self,
+        extract: &str,
+        args: A,
+        key: K,
+        options: &ScrollOptions,
+    ) -> Result<Vec<T>>
+    where
+        T: DeserializeOwned,
+        A: Serialize,
+        K: Fn(&T) -> String,
+    {
+        let args = function_args(args)?;
+        let settle = options.settle.as_millis() as i64;
+        let stall = options.stall_timeout.as_millis() as i64;
+
+        let mut seen = HashSet::new();
+        let mut items = Vec::new();
+        let mut last_new = Instant::now();
+
+        for step in 0..=options.max_scrolls {
+            let batch: Vec<T> = parse_result(&self.backend.async_call_function(self.id, extract, &args)?)?;
+
+            let before = items.len();
+            for item in batch {
+                if seen.insert(key(&item)) {
+                    items.push(item);
+                }
+            }
+
+            if items.len() >= options.target {
+                items.truncate(options.target);
+                break;
+            }
+
+            if items.len() > before {
+                last_new = Instant::now();
+            } else if last_new.elapsed() >= options.stall_timeout {
+                break;
+            }
+
+            if step == options.max_scrolls {
+                break;
+            }
+
+            self.scroll()?;
+            match self.backend.wait_for_network_idle(self.id, settle, settle.max(stall)) {
+                Ok(()) | Err(Error::Timeout) => {}
+                Err(e) => return Err(e),
+            }
+        }
+
+        Ok(items)
+    }
+}
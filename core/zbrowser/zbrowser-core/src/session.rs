@@ -0,0 +1,189 @@
+//! Named browser sessions persisted to disk
+
+use crate::{Error, Result};
+use dashmap::DashMap;
+use serde::{Deserialize, Serialize};
+use std::{
+    path::PathBuf,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use tracing::{info, warn};
+
+/// Default lifetime of a saved session when none is configured
+const DEFAULT_MAX_AGE: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+
+/// A cookie jar saved under a name, usually `platform:account`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Session {
+    /// Name the session is stored under
+    pub name: String,
+    /// Cookies in the format returned by `Context::string_cookies`
+    pub cookies: serde_json::Value,
+    /// Unix timestamp (seconds) of when the session was saved
+    pub saved_at: u64,
+    /// Unix timestamp (seconds) after which the session is discarded
+    pub expires_at: u64,
+}
+
+impl Session {
+    /// Returns true if the session has reached its expiry time
+    pub fn is_expired(&self) -> bool {
+        now() >= self.expires_at
+    }
+}
+
+/// Store of named sessions backed by one JSON file per session
+///
+/// Sessions are cached in memory and written to `{dir}/{name}.json`, so they survive restarts.
+/// A session expires after the store's max age or when its last cookie expires, whichever comes first
+pub struct SessionStore {
+    /// Directory holding the session files
+    dir: PathBuf,
+    /// Maximum lifetime of a session
+    max_age: Duration,
+    /// Sessions loaded or saved during this run
+    sessions: DashMap<String, Session>,
+}
+
+impl SessionStore {
+    /// Creates a session store that persists sessions in the given directory
+    /// The directory is created on the first save
+    ///
+    /// # Arguments
+    /// * `dir` - Directory holding the session files
+    pub fn new<P: Into<PathBuf>>(dir: P) -> Self {
+        Self {
+            dir: dir.into(),
+            max_age: DEFAULT_MAX_AGE,
+            sessions: DashMap::new(),
+        }
+    }
+
+    /// Sets the maximum lifetime of the sessions saved from now on
+    ///
+    /// # Arguments
+    /// * `max_age` - Time after which a saved session is discarded
+    pub fn with_max_age(mut self, max_age: Duration) -> Self {
+        self.max_age = max_age;
+        self
+    }
+
+    /// Returns the session stored under the given name if it exists and has not expired
+    /// Expired sessions are removed from the store
+    ///
+    /// # Arguments
+    /// * `name` - Name of the session
+    pub fn get<T: AsRef<str>>(&self, name: T) -> Option<Session> {
+        let name = name.as_ref();
+        let session = match self.sessions.get(name) {
+            Some(session) => session.clone(),
+            None => self.load(name)?,
+        };
+
+        if session.is_expired() {
+            info!("Session {} expired", name);
+            self.remove(name);
+            return None;
+        }
+
+        self.sessions.insert(name.to_string(), session.clone());
+        Some(session)
+    }
+
+    /// Saves a cookie jar under the given name, replacing any previous session
+    ///
+    /// # Arguments
+    /// * `name` - Name of the session
+    /// * `cookies` - Cookies in the format returned by `Context::string_cookies`
+    ///
+    /// # Returns
+    /// The saved session, or `Error::Failed` if the cookies are invalid or could not be written
+    pub fn save<T: AsRef<str>, U: AsRef<str>>(&self, name: T, cookies: U) -> Result<Session> {
+        let name = name.as_ref();
+        let cookies: serde_json::Value = serde_json::from_str(cookies.as_ref())
+            .map_err(|e| Error::Failed(format!("Invalid cookies for session {}: {}", name, e)))?;
+
+        let saved_at = now();
+        let mut expires_at = saved_at + self.max_age.as_secs();
+        if let Some(last_cookie) = last_cookie_expiry(&cookies) {
+            expires_at = expires_at.min(last_cookie);
+        }
+
+        let session = Session {
+            name: name.to_string(),
+            cookies,
+            saved_at,
+            expires_at,
+        };
+
+        let json = serde_json::to_string_pretty(&session).map_err(|e| Error::Failed(e.to_string()))?;
+        std::fs::create_dir_all(&self.dir)
+            .and_then(|_| std::fs::write(self.path(name), json))
+            .map_err(|e| Error::Failed(format!("Failed to save session {}: {}", name, e)))?;
+
+        self.sessions.insert(name.to_string(), session.clone());
+        Ok(session)
+    }
+
+    /// Removes the session stored under the given name, from memory and disk
+    ///
+    /// # Arguments
+    /// * `name` - Name of the session
+    pub fn remove<T: AsRef<str>>(&self, name: T) {
+        let name = name.as_ref();
+        self.sessions.remove(name);
+
+        let path = self.path(name);
+        if path.exists()
+            && let Err(e) = std::fs::remove_file(&path)
+        {
+            warn!("Failed to remove session file {}: {}", path.display(), e);
+        }
+    }
+
+    /// Reads a session from disk
+    fn load(&self, name: &str) -> Option<Session> {
+        let content = std::fs::read_to_string(self.path(name)).ok()?;
+        match serde_json::from_str(&content) {
+            Ok(session) => Some(session),
+            Err(e) => {
+                warn!("Ignoring invalid session file for {}: {}", name, e);
+                None
+            }
+        }
+    }
+
+    /// Path of the file holding a session
+    /// Characters that are not valid in file names on every platform are replaced
+    fn path(&self, name: &str) -> PathBuf {
+        let file_name: String = name
+            .chars()
+            .map(|c| match c {
+                'a'..='z' | 'A'..='Z' | '0'..='9' | '-' | '_' | '.' | '@' => c,
+                _ => '_',
+            })
+            .collect();
+
+        self.dir.join(format!("{}.json", file_name))
+    }
+}
+
+/// Returns the latest expiry among the persistent cookies of a jar
+/// Session cookies report a negative expiry and are ignored
+fn last_cookie_expiry(cookies: &serde_json::Value) -> Option<u64> {
+    cookies
+        .as_array()?
+        .iter()
+        .filter_map(|cookie| cookie.get("expires")?.as_f64())
+        .filter(|expires| *expires > 0.0)
+        .map(|expires| expires as u64)
+        .max()
+}
+
+/// Current Unix timestamp in seconds
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
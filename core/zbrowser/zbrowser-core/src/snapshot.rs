@@ -0,0 +1,90 @@
+//! Screenshots and DOM snapshots of browser contexts
+
+use crate::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+/// Region of the page in CSS pixels from the top left corner of the document
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Clip {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+/// Area captured by `Context::screenshot`
+/// The visible viewport is captured by default
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScreenshotOptions {
+    /// Capture the whole scrollable page instead of the viewport
+    pub full_page: bool,
+    /// Capture only this region, takes precedence over `full_page`
+    pub clip: Option<Clip>,
+}
+
+impl ScreenshotOptions {
+    /// Captures the visible viewport
+    pub fn viewport() -> Self {
+        Self::default()
+    }
+
+    /// Captures the whole scrollable page
+    pub fn full_page() -> Self {
+        Self {
+            full_page: true,
+            clip: None,
+        }
+    }
+
+    /// Captures a region of the page
+    ///
+    /// # Arguments
+    /// * `x`, `y` - Top left corner in CSS pixels from the top left corner of the document
+    /// * `width`, `height` - Size of the region in CSS pixels
+    pub fn clip(x: f64, y: f64, width: f64, height: f64) -> Self {
+        Self {
+            full_page: false,
+            clip: Some(Clip { x, y, width, height }),
+        }
+    }
+}
+
+/// State of a page at a point of a task, enough to inspect it offline
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Snapshot {
+    /// URL of the page
+    pub url: String,
+    /// Title of the page
+    pub title: String,
+    /// HTML of the page, including open shadow roots
+    pub html: String,
+    /// Cookies of the context, in the format of `Context::string_cookies`
+    pub cookies: String,
+}
+
+impl Context {
+    /// Captures a PNG image of the page
+    ///
+    /// # Arguments
+    /// * `options` - Area to capture
+    ///
+    /// # Returns
+    /// The PNG image, or `Error::Failed` if the page could not be captured
+    pub fn screenshot(&self, options: &ScreenshotOptions) -> Result<Vec<u8>> {
+        self.backend.screenshot(self.id, options)
+    }
+
+    /// Records the URL, title, HTML and cookies of the current page
+    ///
+    /// # Returns
+    /// The snapshot, or the error of the first part that could not be read
+    pub fn snapshot(&self) -> Result<Snapshot> {
+        Ok(Snapshot {
+            url: self.evaluate("location.href")?,
+            title: self.evaluate("document.title")?,
+            html: self.get_html()?,
+            cookies: self.string_cookies()?,
+        })
+    }
+}
@@ -1,25 +1,48 @@
-fn main() -> anyhow::Result<()> {
-    let current_dir = String::from(env!("CARGO_MANIFEST_DIR"));
-    let current_dir = std::path::Path::new(&current_dir);
-    let workspace_root = current_dir
-        .parent()
-        .ok_or(anyhow::anyhow!("Could not get workspace root"))?;
+//! Development utilities of zbrowser
+//!
+//! * `zbrowser-utils` or `zbrowser-utils clean` deletes the native build artifacts
+//! * `zbrowser-utils repl [--remote <url>] [--replay <dir>]` opens an interactive session on a browser tab
+//! * `zbrowser-utils run <recipe> [--remote <url>] [--replay <dir>]` runs the commands of a recipe file
+//!
+//! The REPL and the recipe runner link the native scraper library, so they are only
+//! built with the `repl` feature (`cargo repl` and `cargo recipe` from the workspace)
+
+mod clean;
+#[cfg(feature = "repl")]
+mod repl;
+
+/// Usage printed by `help`
+const USAGE: &str = "\
+Usage:
+  zbrowser-utils [clean]                   Delete the native build artifacts
+  zbrowser-utils repl [options]            Open an interactive session on a browser tab
+  zbrowser-utils run <recipe> [options]    Run the commands of a recipe file
 
-    let src_go_dir = workspace_root.join("zbrowser-core/src-go");
-    let libscraper_h = src_go_dir.join("libscraper.h");
-    let libscraper_a = src_go_dir.join("libscraper.a");
-    let bindings = workspace_root.join("zbrowser-core/bindings.rs");
-    let common_dir = workspace_root.join("common/common.h");
+Options:
+  --remote <url>    Connect to a remote browser instead of launching Chrome
+  --replay <dir>    Replay the recorded pages of a fixture directory";
 
-    let _ = std::fs::remove_file(bindings);
-    let _ = std::fs::remove_file(libscraper_h);
-    let _ = std::fs::remove_file(libscraper_a);
-    let _ = std::fs::remove_file(common_dir);
+fn main() -> anyhow::Result<()> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match args.first().map(String::as_str) {
+        None | Some("clean") => clean::run(),
+        Some("repl" | "run") => interactive(&args),
+        Some("help" | "--help" | "-h") => {
+            println!("{}", USAGE);
+            Ok(())
+        }
+        Some(command) => anyhow::bail!("Unknown command {}\n\n{}", command, USAGE),
+    }
+}
 
-    std::process::Command::new("cargo")
-        .current_dir(workspace_root.join("zbrowser-core"))
-        .args(&["clean"])
-        .output()?;
+/// Runs the REPL or a recipe
+#[cfg(feature = "repl")]
+fn interactive(args: &[String]) -> anyhow::Result<()> {
+    repl::main(args)
+}
 
-    Ok(())
+/// Reports that the REPL and the recipe runner were not built
+#[cfg(not(feature = "repl"))]
+fn interactive(_args: &[String]) -> anyhow::Result<()> {
+    anyhow::bail!("zbrowser-utils was built without the repl feature, rebuild it with --features repl")
 }
@@ -0,0 +1,459 @@
+//! Interactive REPL and recipe runner on top of `Scraper` and `Context`
+//!
+//! Both read the same commands, one per line. Recipes are text files with one command
+//! per line, where empty lines and lines starting with `#` are skipped. Arguments holding
+//! spaces are quoted with `"` or `'`, except the script of `eval` which takes the rest of
+//! the line. The whole session runs in a single task, so the page, the cookies and the
+//! state left by a command are seen by the next one
+
+use anyhow::{Context as _, anyhow, bail};
+use std::{
+    fs,
+    io::{self, BufRead, Write},
+    path::{Path, PathBuf},
+};
+use zbrowser::{Context, ScreenshotOptions, Scraper};
+
+/// Timeout of `wait` when none is given, in milliseconds
+const WAIT_TIMEOUT_MS: i64 = 10_000;
+/// Quiet period of `idle` when none is given, in milliseconds
+const IDLE_MS: i64 = 500;
+/// Timeout of `idle` when none is given, in milliseconds
+const IDLE_TIMEOUT_MS: i64 = 15_000;
+/// Characters of the text of each element printed by `query`
+const PREVIEW_CHARS: usize = 80;
+
+/// Commands printed by `help`
+const HELP: &str = "\
+Commands:
+  navigate <url>                  Load a page
+  wait <selector> [timeout_ms]    Wait for an element to be ready
+  idle [quiet_ms] [timeout_ms]    Wait until no request is in flight
+  eval <script>                   Evaluate JavaScript and print the result
+  query <selector>                Print the elements matching a selector and their text
+  click <selector>                Click an element
+  type <selector> <text>          Type text into an input
+  html [file]                     Print the HTML of the page or save it to a file
+  cookies save <file>             Save the cookies of the browser to a file
+  cookies load <file>             Restore cookies saved with cookies save
+  screenshot <file> [full]        Save a PNG of the viewport, or of the whole page with full
+  run <recipe>                    Run the commands of a recipe file
+  help                            Print this list
+  exit                            End the session";
+
+/// Options of the `repl` and `run` commands
+#[derive(Debug, Default, PartialEq)]
+struct Options {
+    /// Recipe to run, the REPL is opened when `None`
+    recipe: Option<PathBuf>,
+    /// Remote browser to connect to instead of launching Chrome
+    remote: Option<String>,
+    /// Fixture directory to replay instead of driving a browser
+    replay: Option<PathBuf>,
+}
+
+impl Options {
+    /// Parses the arguments of the `repl` or `run` command, the command name included
+    fn parse(args: &[String]) -> anyhow::Result<Self> {
+        let mut options = Self::default();
+        let mut args = args.iter();
+        let command = args.next().map(String::as_str);
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--remote" => options.remote = Some(args.next().ok_or(anyhow!("--remote needs a URL"))?.clone()),
+                "--replay" => options.replay = Some(args.next().ok_or(anyhow!("--replay needs a directory"))?.into()),
+                flag if flag.starts_with("--") => bail!("Unknown option {}", flag),
+                recipe if command == Some("run") && options.recipe.is_none() => options.recipe = Some(recipe.into()),
+                other => bail!("Unexpected argument {}", other),
+            }
+        }
+
+        if command == Some("run") && options.recipe.is_none() {
+            bail!("Usage: zbrowser-utils run <recipe> [--remote <url>] [--replay <dir>]");
+        }
+        Ok(options)
+    }
+}
+
+/// A command of the REPL or of a recipe
+#[derive(Debug, PartialEq)]
+enum Command {
+    Navigate(String),
+    Wait { selector: String, timeout: i64 },
+    Idle { idle: i64, timeout: i64 },
+    Eval(String),
+    Query(String),
+    Click(String),
+    Type { selector: String, text: String },
+    Html(Option<PathBuf>),
+    SaveCookies(PathBuf),
+    LoadCookies(PathBuf),
+    Screenshot { path: PathBuf, full_page: bool },
+    Run(PathBuf),
+    Help,
+    Exit,
+}
+
+impl Command {
+    /// Parses a line of the REPL or of a recipe
+    ///
+    /// # Returns
+    /// The command, `None` for empty lines and comments, or an error describing the usage
+    fn parse(line: &str) -> anyhow::Result<Option<Self>> {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            return Ok(None);
+        }
+
+        let (name, rest) = match line.split_once(char::is_whitespace) {
+            Some((name, rest)) => (name, rest.trim()),
+            None => (line, ""),
+        };
+        let args = split_args(rest)?;
+        let args: Vec<&str> = args.iter().map(String::as_str).collect();
+
+        let command = match (name, args.as_slice()) {
+            ("navigate" | "goto", [url]) => Command::Navigate(url.to_string()),
+            ("wait", [selector]) => Command::Wait {
+                selector: selector.to_string(),
+                timeout: WAIT_TIMEOUT_MS,
+            },
+            ("wait", [selector, timeout]) => Command::Wait {
+                selector: selector.to_string(),
+                timeout: parse_ms(timeout)?,
+            },
+            ("idle", []) => Command::Idle {
+                idle: IDLE_MS,
+                timeout: IDLE_TIMEOUT_MS,
+            },
+            ("idle", [idle]) => Command::Idle {
+                idle: parse_ms(idle)?,
+                timeout: IDLE_TIMEOUT_MS,
+            },
+            ("idle", [idle, timeout]) => Command::Idle {
+                idle: parse_ms(idle)?,
+                timeout: parse_ms(timeout)?,
+            },
+            // The script keeps its quotes, so it is taken verbatim
+            ("eval", _) if !rest.is_empty() => Command::Eval(rest.to_string()),
+            ("query", [selector]) => Command::Query(selector.to_string()),
+            ("click", [selector]) => Command::Click(selector.to_string()),
+            ("type", [selector, text]) => Command::Type {
+                selector: selector.to_string(),
+                text: text.to_string(),
+            },
+            ("html", []) => Command::Html(None),
+            ("html", [path]) => Command::Html(Some(path.into())),
+            ("cookies", ["save", path]) => Command::SaveCookies(path.into()),
+            ("cookies", ["load", path]) => Command::LoadCookies(path.into()),
+            ("screenshot", [path]) => Command::Screenshot {
+                path: path.into(),
+                full_page: false,
+            },
+            ("screenshot", [path, "full"]) => Command::Screenshot {
+                path: path.into(),
+                full_page: true,
+            },
+            ("run", [path]) => Command::Run(path.into()),
+            ("help", []) => Command::Help,
+            ("exit" | "quit", []) => Command::Exit,
+            (name, _) => match HELP.lines().find(|usage| usage.trim_start().starts_with(&format!("{} ", name))) {
+                Some(usage) => bail!("Usage: {}", usage.trim()),
+                None => bail!("Unknown command {}, type help to list the commands", name),
+            },
+        };
+        Ok(Some(command))
+    }
+
+    /// Runs the command in the context of the session
+    ///
+    /// # Arguments
+    /// * `context` - Context of the session
+    /// * `running` - Recipes being run by the enclosing `run` commands, outermost first
+    ///
+    /// # Returns
+    /// `false` once the session must end, also when a nested recipe ran `exit`
+    fn execute(self, context: &Context, running: &mut Vec<PathBuf>) -> anyhow::Result<bool> {
+        match self {
+            Command::Navigate(url) => context.navigate(url)?,
+            Command::Wait { selector, timeout } => context.wait_for_element(selector, timeout)?,
+            Command::Idle { idle, timeout } => context.wait_for_network_idle(idle, timeout)?,
+            Command::Eval(script) => println!("{}", context.evaluate(script)?),
+            Command::Query(selector) => {
+                let elements = context.query_selector_all(&selector)?;
+                println!("{} elements match {}", elements.len(), selector);
+                for (index, element) in elements.iter().enumerate() {
+                    println!("  [{}] {}", index, preview(&element.text()?));
+                }
+            }
+            Command::Click(selector) => context.click_element(selector)?,
+            Command::Type { selector, text } => context.write_input(selector, text)?,
+            Command::Html(None) => println!("{}", context.get_html()?),
+            Command::Html(Some(path)) => save(&path, context.get_html()?)?,
+            Command::SaveCookies(path) => save(&path, context.string_cookies()?)?,
+            Command::LoadCookies(path) => {
+                let cookies = fs::read_to_string(&path).with_context(|| format!("Could not read {}", path.display()))?;
+                context.set_string_cookies(cookies)?;
+            }
+            Command::Screenshot { path, full_page } => {
+                let options = if full_page {
+                    ScreenshotOptions::full_page()
+                } else {
+                    ScreenshotOptions::viewport()
+                };
+                save(&path, context.screenshot(&options)?)?;
+            }
+            Command::Run(path) => return run_recipe(context, &path, running),
+            Command::Help => println!("{}", HELP),
+            Command::Exit => return Ok(false),
+        }
+        Ok(true)
+    }
+}
+
+/// Runs the `repl` or `run` command
+///
+/// # Arguments
+/// * `args` - Arguments of the command, the command name included
+pub fn main(args: &[String]) -> anyhow::Result<()> {
+    let options = Options::parse(args)?;
+    let scraper = match &options.replay {
+        Some(dir) => Scraper::replay(dir)?,
+        None => Scraper::new(options.remote.clone(), 1, vec![])?,
+    };
+
+    let runtime = tokio::runtime::Runtime::new()?;
+    let recipe = options.recipe;
+    runtime.block_on(scraper.execute(move |context| {
+        Ok(match &recipe {
+            Some(path) => run_recipe(&context, path, &mut Vec::new()).map(|_| ()),
+            None => repl(&context),
+        })
+    }))?
+}
+
+/// Reads commands from the standard input until `exit` or the end of the input
+/// Failed commands print their error and the session goes on
+fn repl(context: &Context) -> anyhow::Result<()> {
+    println!("zbrowser REPL, type help to list the commands");
+
+    let mut stdin = io::stdin().lock();
+    loop {
+        print!("> ");
+        io::stdout().flush()?;
+
+        let mut line = String::new();
+        if stdin.read_line(&mut line)? == 0 {
+            return Ok(());
+        }
+
+        match Command::parse(&line).and_then(|command| command.map_or(Ok(true), |c| c.execute(context, &mut Vec::new()))) {
+            Ok(true) => {}
+            Ok(false) => return Ok(()),
+            Err(e) => eprintln!("error: {:#}", e),
+        }
+    }
+}
+
+/// Runs the commands of a recipe file, stopping at the first one that fails
+/// A recipe that runs itself, directly or through other recipes, fails before running anything
+///
+/// # Arguments
+/// * `context` - Context of the session
+/// * `path` - Recipe file
+/// * `running` - Recipes being run by the enclosing `run` commands, outermost first
+///
+/// # Returns
+/// `false` if the recipe ran `exit`, so the enclosing recipes and the REPL stop too
+fn run_recipe(context: &Context, path: &Path, running: &mut Vec<PathBuf>) -> anyhow::Result<bool> {
+    let recipe = fs::read_to_string(path).with_context(|| format!("Could not read recipe {}", path.display()))?;
+    let canonical = fs::canonicalize(path)?;
+    if running.contains(&canonical) {
+        bail!("Recipe {} runs itself", path.display());
+    }
+
+    running.push(canonical);
+    let result = run_lines(context, path, &recipe, running);
+    running.pop();
+    result
+}
+
+/// Runs the lines of a recipe, see `run_recipe`
+fn run_lines(context: &Context, path: &Path, recipe: &str, running: &mut Vec<PathBuf>) -> anyhow::Result<bool> {
+    for (index, line) in recipe.lines().enumerate() {
+        let Some(command) = Command::parse(line).with_context(|| format!("{}:{}", path.display(), index + 1))? else {
+            continue;
+        };
+
+        println!("> {}", line.trim());
+        let go_on = command
+            .execute(context, running)
+            .with_context(|| format!("{}:{}: {}", path.display(), index + 1, line.trim()))?;
+        if !go_on {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
+
+/// Splits the arguments of a command on whitespace, keeping quoted arguments together
+/// A backslash inside quotes escapes the next character
+fn split_args(text: &str) -> anyhow::Result<Vec<String>> {
+    let mut args = Vec::new();
+    let mut chars = text.chars();
+    let mut current: Option<String> = None;
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' | '\'' => {
+                let arg = current.get_or_insert_with(String::new);
+                loop {
+                    match chars.next() {
+                        Some(end) if end == c => break,
+                        Some('\\') => arg.extend(chars.next()),
+                        Some(other) => arg.push(other),
+                        None => bail!("Missing closing {}", c),
+                    }
+                }
+            }
+            c if c.is_whitespace() => args.extend(current.take()),
+            c => current.get_or_insert_with(String::new).push(c),
+        }
+    }
+    args.extend(current);
+    Ok(args)
+}
+
+/// Parses a duration in milliseconds
+fn parse_ms(value: &str) -> anyhow::Result<i64> {
+    value
+        .parse()
+        .map_err(|_| anyhow!("{} is not a number of milliseconds", value))
+}
+
+/// Returns the first characters of a text on a single line
+fn preview(text: &str) -> String {
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    match text.char_indices().nth(PREVIEW_CHARS) {
+        Some((end, _)) => format!("{}…", &text[..end]),
+        None => text,
+    }
+}
+
+/// Writes the output of a command to a file
+fn save<C: AsRef<[u8]>>(path: &Path, contents: C) -> anyhow::Result<()> {
+    let contents = contents.as_ref();
+    fs::write(path, contents).with_context(|| format!("Could not write {}", path.display()))?;
+    println!("Saved {} bytes to {}", contents.len(), path.display());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Test case for the command parser
+    /// Quoted arguments keep their spaces, eval keeps its script verbatim and
+    /// wrong arguments report the usage of the command
+    #[test]
+    fn test_parse() {
+        assert_eq!(Command::parse("  # comment").unwrap(), None);
+        assert_eq!(
+            Command::parse(r#"wait "article a[href*='/p/']" 5000"#).unwrap(),
+            Some(Command::Wait {
+                selector: "article a[href*='/p/']".to_string(),
+                timeout: 5000,
+            })
+        );
+        assert_eq!(
+            Command::parse(r#"eval document.querySelector("h1").textContent"#).unwrap(),
+            Some(Command::Eval(r#"document.querySelector("h1").textContent"#.to_string()))
+        );
+        assert_eq!(
+            Command::parse("screenshot page.png full").unwrap(),
+            Some(Command::Screenshot {
+                path: "page.png".into(),
+                full_page: true,
+            })
+        );
+
+        let usage = Command::parse("cookies keep file").unwrap_err().to_string();
+        assert!(usage.starts_with("Usage: cookies save <file>"), "{}", usage);
+        assert!(Command::parse("wait h1 soon").is_err());
+        assert!(Command::parse("type 'unclosed").is_err());
+        assert!(Command::parse("fly away").is_err());
+    }
+
+    /// Test case for the recipe runner
+    /// Runs the example recipe against the recorded example.com page
+    #[tokio::test]
+    async fn test_run_recipe() {
+        let args = [
+            "run".to_string(),
+            concat!(env!("CARGO_MANIFEST_DIR"), "/recipes/example.recipe").to_string(),
+            "--replay".to_string(),
+            concat!(env!("CARGO_MANIFEST_DIR"), "/../zbrowser-core/fixtures/example").to_string(),
+        ];
+        let options = Options::parse(&args).unwrap();
+        let scraper = Scraper::replay(options.replay.unwrap()).unwrap();
+
+        let recipe = options.recipe.unwrap();
+        let result = scraper
+            .execute(move |context| Ok(run_recipe(&context, &recipe, &mut Vec::new()).map_err(|e| e.to_string())))
+            .await
+            .unwrap();
+        assert_eq!(result, Ok(true));
+
+        let failing = scraper
+            .execute(|context| {
+                let commands = ["navigate https://www.example.com", "wait .missing 10"];
+                Ok(commands
+                    .iter()
+                    .map(|line| Command::parse(line).unwrap().unwrap().execute(&context, &mut Vec::new()))
+                    .collect::<anyhow::Result<Vec<bool>>>()
+                    .is_err())
+            })
+            .await
+            .unwrap();
+        assert!(failing, "A missing element should fail the recipe");
+    }
+
+    /// Test case for nested recipes
+    /// Recipes that run themselves are rejected and `exit` in a nested recipe ends every recipe
+    #[tokio::test]
+    async fn test_nested_recipes() {
+        let dir = std::env::temp_dir().join(format!("zbrowser-recipes-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let recipe = |name: &str, lines: &[String]| {
+            let path = dir.join(name);
+            fs::write(&path, lines.join("\n")).unwrap();
+            path
+        };
+
+        let outer = dir.join("outer.recipe");
+        let inner = recipe("inner.recipe", &[format!("run {}", outer.display())]);
+        recipe("outer.recipe", &[format!("run {}", inner.display())]);
+        let exit = recipe("exit.recipe", &["navigate https://www.example.com".to_string(), "exit".to_string()]);
+        // The missing page would fail the recipe if it ran after the nested exit
+        let parent = recipe(
+            "parent.recipe",
+            &[format!("run {}", exit.display()), "navigate https://www.example.org".to_string()],
+        );
+
+        let scraper = Scraper::replay(concat!(env!("CARGO_MANIFEST_DIR"), "/../zbrowser-core/fixtures/example")).unwrap();
+        let (cycle, exited) = scraper
+            .execute(move |context| {
+                let cycle = run_recipe(&context, &outer, &mut Vec::new()).map_err(|e| format!("{:#}", e));
+                let exited = run_recipe(&context, &parent, &mut Vec::new()).map_err(|e| format!("{:#}", e));
+                Ok((cycle, exited))
+            })
+            .await
+            .unwrap();
+        fs::remove_dir_all(&dir).ok();
+
+        let cycle = cycle.unwrap_err();
+        assert!(cycle.contains("outer.recipe runs itself"), "{}", cycle);
+        assert_eq!(exited, Ok(false), "Exit should stop the recipes that ran the nested one");
+    }
+}